@@ -0,0 +1,24 @@
+use backend::bench::LargeMeeting;
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+fn assemble_meeting(c: &mut Criterion) {
+    let mut group = c.benchmark_group("assemble_meeting");
+    for (participants, dates) in [(10, 10), (100, 50), (500, 200)] {
+        let meeting = LargeMeeting::new(participants, dates);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{participants}x{dates}")),
+            &meeting,
+            |b, meeting| {
+                b.iter_batched(
+                    || meeting.clone(),
+                    LargeMeeting::assemble,
+                    BatchSize::LargeInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, assemble_meeting);
+criterion_main!(benches);
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
+use time::{Date, OffsetDateTime, Time};
+use time_tz::{OffsetDateTimeExt, Tz};
 
+use crate::app::availability;
 use crate::database::models;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -19,3 +22,329 @@ impl From<models::Vote> for Vote {
         }
     }
 }
+
+impl From<Vote> for models::Vote {
+    fn from(value: Vote) -> Self {
+        match value {
+            Vote::No => Self::No,
+            Vote::Maybe => Self::Maybe,
+            Vote::Yes => Self::Ok,
+        }
+    }
+}
+
+/// Option that participants vote on. It is either a whole day
+/// or a time slot starting at given instant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum DateOption {
+    AllDay {
+        #[serde(with = "super::serde_rfc_3339::date")]
+        date: Date,
+    },
+    TimeSlot {
+        #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+        starts_at: OffsetDateTime,
+        duration_minutes: u16,
+    },
+}
+
+impl DateOption {
+    /// Creates option from `proposed_date` table columns. Returns `None`
+    /// if columns do not describe neither a whole day nor a time slot.
+    pub(crate) fn from_columns(
+        date: Option<Date>,
+        starts_at: Option<OffsetDateTime>,
+        duration_minutes: Option<i32>,
+    ) -> Option<Self> {
+        match (date, starts_at, duration_minutes) {
+            (Some(date), None, None) => Some(Self::AllDay { date }),
+            (None, Some(starts_at), Some(duration_minutes)) => Some(Self::TimeSlot {
+                starts_at,
+                duration_minutes: duration_minutes.try_into().ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Splits option into `date`, `starts_at` and `duration_minutes`
+    /// columns of `proposed_date` table.
+    pub(crate) fn into_columns(self) -> (Option<Date>, Option<OffsetDateTime>, Option<i32>) {
+        match self {
+            Self::AllDay { date } => (Some(date), None, None),
+            Self::TimeSlot {
+                starts_at,
+                duration_minutes,
+            } => (None, Some(starts_at), Some(duration_minutes.into())),
+        }
+    }
+
+    /// Day of the option in timezone `tz`. Time slots fall on the day
+    /// on which they start.
+    pub(crate) fn day(self, tz: &Tz) -> Date {
+        match self {
+            Self::AllDay { date } => date,
+            Self::TimeSlot { starts_at, .. } => starts_at.to_timezone(tz).date(),
+        }
+    }
+
+    /// Key ordering options chronologically in timezone `tz`: by day, with
+    /// the whole day before time slots of that day, which are ordered by
+    /// their start and duration.
+    pub(crate) fn chronological_key(self, tz: &Tz) -> (Date, Option<(OffsetDateTime, u16)>) {
+        let slot = match self {
+            Self::AllDay { .. } => None,
+            Self::TimeSlot {
+                starts_at,
+                duration_minutes,
+            } => Some((starts_at, duration_minutes)),
+        };
+        (self.day(tz), slot)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<Weekday> for time::Weekday {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Monday => Self::Monday,
+            Weekday::Tuesday => Self::Tuesday,
+            Weekday::Wednesday => Self::Wednesday,
+            Weekday::Thursday => Self::Thursday,
+            Weekday::Friday => Self::Friday,
+            Weekday::Saturday => Self::Saturday,
+            Weekday::Sunday => Self::Sunday,
+        }
+    }
+}
+
+/// What happens with proposed dates that fall on a holiday (or weekend,
+/// if meeting excludes weekends).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum HolidayPolicy {
+    /// Dates are accepted and marked in meeting snapshot
+    #[default]
+    Flag,
+    /// Dates are rejected
+    Reject,
+}
+
+impl From<models::HolidayPolicy> for HolidayPolicy {
+    fn from(value: models::HolidayPolicy) -> Self {
+        match value {
+            models::HolidayPolicy::Flag => Self::Flag,
+            models::HolidayPolicy::Reject => Self::Reject,
+        }
+    }
+}
+
+impl From<HolidayPolicy> for models::HolidayPolicy {
+    fn from(value: HolidayPolicy) -> Self {
+        match value {
+            HolidayPolicy::Flag => Self::Flag,
+            HolidayPolicy::Reject => Self::Reject,
+        }
+    }
+}
+
+/// Settings chosen by meeting creator.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct MeetingSettings {
+    /// Name of the holiday calendar, e.g. `PL`
+    #[serde(default)]
+    pub(crate) holiday_calendar: Option<String>,
+    #[serde(default)]
+    pub(crate) holiday_policy: HolidayPolicy,
+    /// Treat Saturdays and Sundays like holidays
+    #[serde(default)]
+    pub(crate) exclude_weekends: bool,
+    /// Every participant, not only the creator, may propose dates
+    #[serde(default)]
+    pub(crate) participants_can_propose: bool,
+    /// Dates proposed by participants must be approved by the creator
+    /// before anyone can vote on them
+    #[serde(default)]
+    pub(crate) proposals_require_approval: bool,
+    /// Participants see only their own votes until voting is closed
+    #[serde(default)]
+    pub(crate) hide_results: bool,
+    /// Votes are reported only as counts per date, without revealing who
+    /// cast them
+    #[serde(default)]
+    pub(crate) anonymous_votes: bool,
+    /// Best-scoring date becomes the final date once voting is closed
+    #[serde(default)]
+    pub(crate) auto_finalize: bool,
+    /// Quorum: a date is only viable if at least this many participants
+    /// voted `yes` on it
+    #[serde(default)]
+    pub(crate) min_yes_votes: Option<u16>,
+    /// A date is only viable if at most this many participants voted `yes`
+    /// on it
+    #[serde(default)]
+    pub(crate) max_capacity: Option<u16>,
+    /// Participants can not join under a name that differs from name of
+    /// another participant only in letter case
+    #[serde(default)]
+    pub(crate) unique_participant_names: bool,
+}
+
+impl From<models::MeetingSettings> for MeetingSettings {
+    fn from(value: models::MeetingSettings) -> Self {
+        let models::MeetingSettings {
+            holiday_calendar,
+            holiday_policy,
+            exclude_weekends,
+            participants_can_propose,
+            proposals_require_approval,
+            hide_results,
+            anonymous_votes,
+            auto_finalize,
+            min_yes_votes,
+            max_capacity,
+            unique_participant_names,
+        } = value;
+        Self {
+            holiday_calendar,
+            holiday_policy: holiday_policy.into(),
+            exclude_weekends,
+            participants_can_propose,
+            proposals_require_approval,
+            hide_results,
+            anonymous_votes,
+            auto_finalize,
+            // Database only stores values that were converted from `u16`
+            min_yes_votes: min_yes_votes.and_then(|v| u16::try_from(v).ok()),
+            max_capacity: max_capacity.and_then(|v| u16::try_from(v).ok()),
+            unique_participant_names,
+        }
+    }
+}
+
+/// Kind of the meeting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum MeetingMode {
+    /// Participants vote on proposed dates
+    Poll,
+    /// Participants mark when they are free on availability grid
+    Availability,
+}
+
+/// Definition of availability grid. Days and hours are in meeting's timezone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AvailabilityGrid {
+    #[serde(with = "super::serde_rfc_3339::date")]
+    pub(crate) first_day: Date,
+    #[serde(with = "super::serde_rfc_3339::date")]
+    pub(crate) last_day: Date,
+    #[serde(with = "super::serde_rfc_3339::time")]
+    pub(crate) day_starts_at: Time,
+    #[serde(with = "super::serde_rfc_3339::time")]
+    pub(crate) day_ends_at: Time,
+    pub(crate) slot_minutes: u16,
+}
+
+impl From<availability::AvailabilityGrid> for AvailabilityGrid {
+    fn from(value: availability::AvailabilityGrid) -> Self {
+        let availability::AvailabilityGrid {
+            first_day,
+            last_day,
+            day_starts_at,
+            day_ends_at,
+            slot_minutes,
+        } = value;
+        Self {
+            first_day,
+            last_day,
+            day_starts_at,
+            day_ends_at,
+            slot_minutes,
+        }
+    }
+}
+
+/// Formats version of meeting as an HTTP entity tag.
+pub(crate) fn version_entity_tag(version: i64) -> String {
+    format!("\"{version}\"")
+}
+
+/// Parses version of meeting from a strong HTTP entity tag.
+pub(crate) fn parse_version_entity_tag(tag: &str) -> Option<i64> {
+    tag.trim()
+        .strip_prefix('"')?
+        .strip_suffix('"')?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    #[test]
+    fn version_entity_tags() {
+        assert_eq!(version_entity_tag(7), r#""7""#);
+        assert_eq!(parse_version_entity_tag(r#""7""#), Some(7));
+        assert_eq!(parse_version_entity_tag(r#" "12" "#), Some(12));
+        assert_eq!(parse_version_entity_tag("7"), None);
+        assert_eq!(parse_version_entity_tag(r#"W/"7""#), None);
+        assert_eq!(parse_version_entity_tag(r#""abc""#), None);
+    }
+
+    #[test]
+    fn serialize_date_option() {
+        #[rustfmt::skip]
+        let all_day = DateOption::AllDay { date: date!(2023-07-24) };
+        #[rustfmt::skip]
+        let time_slot = DateOption::TimeSlot {
+            starts_at: datetime!(2023-07-24 14:00 UTC),
+            duration_minutes: 60,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&all_day).unwrap(),
+            r#"{"date":"2023-07-24"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&time_slot).unwrap(),
+            r#"{"starts_at":"2023-07-24T14:00:00Z","duration_minutes":60}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_date_option() {
+        #[rustfmt::skip]
+        let all_day = DateOption::AllDay { date: date!(2023-07-24) };
+        #[rustfmt::skip]
+        let time_slot = DateOption::TimeSlot {
+            starts_at: datetime!(2023-07-24 14:00 +2),
+            duration_minutes: 60,
+        };
+
+        assert_eq!(
+            all_day,
+            serde_json::from_str(r#"{"date":"2023-07-24"}"#).unwrap()
+        );
+        assert_eq!(
+            time_slot,
+            serde_json::from_str(
+                r#"{"starts_at":"2023-07-24T14:00:00+02:00","duration_minutes":60}"#
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use super::common::{AvailabilityGrid, DateOption, MeetingSettings, Vote};
+use crate::app::{availability, business_logic};
+use crate::database::models;
+
+/// Version of the meeting export document format produced by this server.
+pub(crate) const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// Complete, versioned snapshot of a meeting used for backups and for
+/// moving meetings between environments.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MeetingExport {
+    /// Version of the document format
+    pub(crate) format_version: u32,
+    pub(crate) meeting: ExportedMeeting,
+    pub(crate) participants: Vec<ExportedParticipant>,
+    pub(crate) proposed_dates: Vec<ExportedProposedDate>,
+    pub(crate) votes: Vec<ExportedVote>,
+    pub(crate) comments: Vec<ExportedComment>,
+    /// Availability of participants. Always empty for polls.
+    #[serde(default)]
+    pub(crate) availability: Vec<ExportedAvailability>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportedMeeting {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    /// Id of the participant that created the meeting
+    pub(crate) created_by: Uuid,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) created_at: OffsetDateTime,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) expires_at: OffsetDateTime,
+    #[serde(default = "default_timezone")]
+    pub(crate) timezone: String,
+    /// Grid of availability meeting. `None` for polls.
+    #[serde(default)]
+    pub(crate) availability_grid: Option<AvailabilityGrid>,
+    #[serde(default)]
+    pub(crate) settings: MeetingSettings,
+    #[serde(default, with = "super::serde_rfc_3339::offset_date_time::option")]
+    pub(crate) voting_closes_at: Option<OffsetDateTime>,
+    #[serde(default, with = "super::serde_rfc_3339::offset_date_time::option")]
+    pub(crate) voting_closed_at: Option<OffsetDateTime>,
+    /// Id of the proposed date chosen as the outcome of voting
+    #[serde(default)]
+    pub(crate) final_date_id: Option<Uuid>,
+}
+
+fn default_timezone() -> String {
+    business_logic::DEFAULT_TIMEZONE.to_owned()
+}
+
+/// Exported participant. Secret tokens are deliberately not a part of
+/// the export, new ones are generated when the meeting is imported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportedParticipant {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    #[serde(default)]
+    pub(crate) timezone: Option<String>,
+    #[serde(default)]
+    pub(crate) required: bool,
+    /// Missing in exports made before join times were recorded, meeting's
+    /// creation time is used instead
+    #[serde(default, with = "super::serde_rfc_3339::offset_date_time::option")]
+    pub(crate) joined_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportedProposedDate {
+    pub(crate) id: Uuid,
+    #[serde(flatten)]
+    pub(crate) option: DateOption,
+    #[serde(default)]
+    pub(crate) proposed_by: Option<Uuid>,
+    #[serde(default = "default_approved")]
+    pub(crate) approved: bool,
+}
+
+fn default_approved() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportedVote {
+    pub(crate) participant_id: Uuid,
+    pub(crate) date_id: Uuid,
+    pub(crate) vote: Vote,
+    pub(crate) comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportedComment {
+    pub(crate) id: Uuid,
+    pub(crate) written_by: Uuid,
+    pub(crate) message: String,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) posted_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ExportedAvailability {
+    pub(crate) participant_id: Uuid,
+    /// One `0` (busy) or `1` (free) character for every slot of the grid
+    pub(crate) slots: String,
+}
+
+impl TryFrom<models::MeetingExport> for MeetingExport {
+    type Error = anyhow::Error;
+
+    fn try_from(value: models::MeetingExport) -> Result<Self, Self::Error> {
+        let models::MeetingExport {
+            meeting,
+            participants,
+            proposed_dates,
+            votes,
+            comments,
+            availability_grid,
+            availability,
+        } = value;
+
+        let availability_grid = availability_grid
+            .map(availability::AvailabilityGrid::try_from)
+            .transpose()?;
+        let availability = availability
+            .into_iter()
+            .map(|participant| {
+                let grid = availability_grid
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("availability of meeting without grid"))?;
+                let slots = availability::AvailabilitySlots::from_bytes(&participant.slots, grid)?;
+                Ok(ExportedAvailability {
+                    participant_id: participant.user_id,
+                    slots: slots.to_string(),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        let meeting = ExportedMeeting {
+            id: meeting.id,
+            name: meeting.name,
+            description: meeting.description,
+            created_by: meeting.created_by,
+            created_at: meeting.created_at,
+            expires_at: meeting.expires_at,
+            timezone: meeting.timezone,
+            availability_grid: availability_grid.map(Into::into),
+            settings: meeting.settings.into(),
+            voting_closes_at: meeting.voting_closes_at,
+            voting_closed_at: meeting.voting_closed_at,
+            final_date_id: meeting.final_date_id,
+        };
+        let participants = participants
+            .into_iter()
+            .map(|participant| ExportedParticipant {
+                id: participant.id,
+                name: participant.name,
+                timezone: participant.timezone,
+                required: participant.required,
+                joined_at: Some(participant.joined_at),
+            })
+            .collect();
+        let proposed_dates = proposed_dates
+            .into_iter()
+            .map(|date| {
+                DateOption::from_columns(date.date, date.starts_at, date.duration_minutes)
+                    .map(|option| ExportedProposedDate {
+                        id: date.id,
+                        option,
+                        proposed_by: date.proposed_by,
+                        approved: date.approved,
+                    })
+                    .ok_or_else(|| anyhow!("failed to validate proposed date: {:?}", date))
+            })
+            .collect::<Result<_, _>>()?;
+        let votes = votes
+            .into_iter()
+            .map(|vote| ExportedVote {
+                participant_id: vote.user_id,
+                date_id: vote.proposed_date_id,
+                vote: vote.vote.into(),
+                comment: vote.comment,
+            })
+            .collect();
+        let comments = comments
+            .into_iter()
+            .map(|comment| ExportedComment {
+                id: comment.id,
+                written_by: comment.user_id,
+                message: comment.message,
+                posted_at: comment.posted_at,
+            })
+            .collect();
+
+        Ok(Self {
+            format_version: EXPORT_FORMAT_VERSION,
+            meeting,
+            participants,
+            proposed_dates,
+            votes,
+            comments,
+            availability,
+        })
+    }
+}
@@ -1,16 +1,109 @@
-use serde::Deserialize;
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header, request::Parts, StatusCode},
+};
+use serde::{Deserialize, Deserializer};
+use time::{Date, OffsetDateTime, Time};
+use tracing::info;
 use uuid::Uuid;
 
+use super::common::{
+    parse_version_entity_tag, AvailabilityGrid, DateOption, MeetingSettings, Vote, Weekday,
+};
+use super::export::MeetingExport;
+
+/// Version of meeting sent in `If-Match` header. Requests changing meeting
+/// details or dates must carry the version they were based on, so that
+/// concurrent changes do not silently overwrite each other.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IfMatch(pub(crate) i64);
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for IfMatch {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(header::IF_MATCH) else {
+            info!("Missing If-Match header");
+            return Err(StatusCode::PRECONDITION_REQUIRED);
+        };
+        value
+            .to_str()
+            .ok()
+            .and_then(parse_version_entity_tag)
+            .map(Self)
+            .ok_or_else(|| {
+                info!(?value, "Invalid If-Match header");
+                StatusCode::BAD_REQUEST
+            })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AccountCredentials {
+    pub(crate) account_id: Uuid,
+    pub(crate) account_token: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct CreateAccountData {
+    pub(crate) name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AccountToken {
+    pub(crate) account_token: Uuid,
+}
+
+/// Links existing participant of a meeting to an account.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct LinkAccountData {
+    pub(crate) account_token: Uuid,
+    pub(crate) meeting_id: Uuid,
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub(crate) struct CreateMeetingData {
     pub(crate) meeting_name: String,
     pub(crate) meeting_description: Option<String>,
+    /// IANA timezone of the meeting. Defaults to UTC.
+    pub(crate) meeting_timezone: Option<String>,
     pub(crate) user_name: String,
+    /// IANA timezone in which dates are displayed to the creator
+    pub(crate) user_timezone: Option<String>,
+    /// Grid on which participants mark their availability. Meetings
+    /// created without grid are polls.
+    pub(crate) availability_grid: Option<AvailabilityGrid>,
+    #[serde(default)]
+    pub(crate) settings: MeetingSettings,
+    /// Deadline after which participants can no longer vote
+    #[serde(default, with = "super::serde_rfc_3339::offset_date_time::option")]
+    pub(crate) voting_closes_at: Option<OffsetDateTime>,
+    /// Account to which the creator is linked
+    #[serde(default)]
+    pub(crate) account: Option<AccountCredentials>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub(crate) struct JoinMeetingData {
     pub(crate) name: String,
+    /// IANA timezone in which dates are displayed to the participant
+    pub(crate) timezone: Option<String>,
+    /// Account to which the participant is linked
+    #[serde(default)]
+    pub(crate) account: Option<AccountCredentials>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct GetMeetingQuery {
+    /// Participant whose timezone is used to display time slots
+    pub(crate) user_id: Option<Uuid>,
+    /// Secret token of the participant. Required to see own votes in
+    /// meetings that hide results.
+    pub(crate) user_token: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -19,3 +112,113 @@ pub(crate) struct PostCommentData {
     pub(crate) user_token: Uuid,
     pub(crate) message: String,
 }
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct UserCredentials {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ImportMeetingData {
+    pub(crate) admin_token: Uuid,
+    pub(crate) meeting: MeetingExport,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AddProposedDateData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    #[serde(flatten)]
+    pub(crate) option: DateOption,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SetAvailabilityData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    /// One `0` (busy) or `1` (free) character for every slot of the grid
+    pub(crate) slots: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct GenerateProposedDatesData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    #[serde(flatten)]
+    pub(crate) rule: DateRuleData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct DateRuleData {
+    #[serde(with = "super::serde_rfc_3339::date")]
+    pub(crate) first_day: Date,
+    #[serde(with = "super::serde_rfc_3339::date")]
+    pub(crate) last_day: Date,
+    /// Days of week on which dates are generated. Empty means every day.
+    #[serde(default)]
+    pub(crate) weekdays: Vec<Weekday>,
+    #[serde(default, with = "super::serde_rfc_3339::date::vec")]
+    pub(crate) excluded_dates: Vec<Date>,
+    /// RRULE-like recurrence, e.g. `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO`
+    pub(crate) recurrence: Option<String>,
+    /// Generate time slots instead of whole days
+    pub(crate) time_slot: Option<DailyTimeSlotData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct DailyTimeSlotData {
+    /// Wall-clock time in meeting's timezone
+    #[serde(with = "super::serde_rfc_3339::time")]
+    pub(crate) starts_at: Time,
+    pub(crate) duration_minutes: u16,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct CastVoteData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    pub(crate) date_id: Uuid,
+    pub(crate) vote: Vote,
+    pub(crate) comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct UpdateMeetingData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    /// New name. Name is not changed if this field is missing.
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    /// New description. Description is not changed if this field is
+    /// missing and is cleared if it is `null`.
+    #[serde(default, deserialize_with = "deserialize_present")]
+    pub(crate) description: Option<Option<String>>,
+}
+
+/// Deserializes field that is present in the input, so that `null` can be
+/// told apart from a missing field (which is handled by `#[serde(default)]`).
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct FinalizeMeetingData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    pub(crate) date_id: Uuid,
+    /// Finalize even if the date is not viable
+    #[serde(default)]
+    pub(crate) force: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SetParticipantRequiredData {
+    pub(crate) user_id: Uuid,
+    pub(crate) user_token: Uuid,
+    pub(crate) required: bool,
+}
@@ -1,4 +1,5 @@
 pub(crate) mod common;
+pub(crate) mod export;
 pub(crate) mod input;
 pub(crate) mod output;
 pub(crate) mod serde_rfc_3339;
@@ -1,83 +1,99 @@
-use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
 use serde::Serialize;
-use time::{Date, OffsetDateTime};
+use serde_json::Value as JsonValue;
+use time::{Duration, OffsetDateTime, UtcOffset};
+use time_tz::{timezones, OffsetDateTimeExt, Tz};
 use uuid::Uuid;
 
-use super::common::Vote;
+use super::common::{AvailabilityGrid, DateOption, MeetingMode, MeetingSettings, Vote};
+use crate::app::holidays::HolidayCalendars;
+use crate::app::validation::ValidationErrors;
+use crate::app::{availability, business_logic, results};
 use crate::database::models;
 
-pub(crate) enum ValidatedParticipantsProposedDatesVotes {
-    Participant {
-        user_id: Uuid,
-        name: String,
-    },
-    ProposedDate {
-        date_id: Uuid,
-        date: Date,
-    },
-    Vote {
-        user_id: Uuid,
-        name: String,
-        date_id: Uuid,
-        date: Date,
-        vote: Vote,
-        comment: Option<String>,
-    },
-}
-
-impl TryFrom<models::ParticipantsProposedDatesVotes> for ValidatedParticipantsProposedDatesVotes {
-    type Error = anyhow::Error;
-
-    fn try_from(value: models::ParticipantsProposedDatesVotes) -> Result<Self, Self::Error> {
-        use models::ParticipantsProposedDatesVotes as PPDV;
-
-        let create_base_error = |value| anyhow!("failed to validate row: {:?}", value);
-
-        match (value.user_id, value.date_id) {
-            (None, None) => Err(create_base_error(value)),
-
-            (Some(user_id), None) => {
-                let name = if let Some(name) = value.name {
-                    name
-                } else {
-                    return Err(create_base_error(value));
-                };
-                Ok(Self::Participant { user_id, name })
-            }
-            (None, Some(date_id)) => {
-                let date = value.date.ok_or_else(|| create_base_error(value))?;
-                Ok(Self::ProposedDate { date_id, date })
-            }
-            (Some(user_id), Some(date_id)) => match (value.name, value.date, value.vote) {
-                (Some(name), Some(date), Some(vote)) => Ok(Self::Vote {
-                    user_id,
-                    name,
-                    date_id,
-                    date,
-                    vote: vote.into(),
-                    comment: value.comment,
-                }),
-                (name, _, vote) => Err(create_base_error(PPDV {
-                    name,
-                    vote,
-                    ..value
-                })),
-            },
-        }
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub(crate) struct Participant {
     pub(crate) id: Uuid,
     pub(crate) name: String,
+    /// Display timezone of the participant
+    pub(crate) timezone: Option<String>,
+    /// Whether meeting can only take place on dates this participant can attend
+    pub(crate) required: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub(crate) struct ProposedDate {
     pub(crate) id: Uuid,
-    #[serde(with = "super::serde_rfc_3339::date")]
-    pub(crate) date: Date,
+    /// Proposed day or time slot. Start of time slot is in UTC.
+    #[serde(flatten)]
+    pub(crate) option: DateOption,
+    /// Time slot rendered in display timezone. Always `None` for whole days.
+    pub(crate) local: Option<LocalTimeSlot>,
+    /// Participant that proposed the date
+    pub(crate) proposed_by: Option<Uuid>,
+    /// Dates proposed by participants may await creator's approval,
+    /// until then no one can vote on them
+    pub(crate) approved: bool,
+    /// Name of the holiday in meeting's holiday calendar on which date falls
+    pub(crate) holiday: Option<String>,
+    /// Date falls on a weekend and meeting excludes weekends
+    pub(crate) weekend: bool,
+}
+
+impl ProposedDate {
+    fn new(id: Uuid, option: DateOption, proposed_by: Option<Uuid>, approved: bool) -> Self {
+        Self {
+            id,
+            option,
+            local: None,
+            proposed_by,
+            approved,
+            holiday: None,
+            weekend: false,
+        }
+    }
+
+    /// Marks date that falls on a holiday or an excluded weekend.
+    fn mark(&mut self, settings: &MeetingSettings, holidays: &HolidayCalendars, tz: &Tz) {
+        let marks = holidays.marks(settings, self.option, tz);
+        self.holiday = marks.holiday;
+        self.weekend = marks.weekend;
+    }
+}
+
+/// Wall-clock rendering of a time slot in a given timezone.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct LocalTimeSlot {
+    /// IANA name of the timezone
+    pub(crate) timezone: String,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) starts_at: OffsetDateTime,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) ends_at: OffsetDateTime,
+}
+
+impl LocalTimeSlot {
+    /// Renders `option` in timezone with given name. Returns `None` for
+    /// whole days and for timezones unknown to the bundled database.
+    fn new(option: DateOption, timezone: &str) -> Option<Self> {
+        let DateOption::TimeSlot {
+            starts_at,
+            duration_minutes,
+        } = option
+        else {
+            return None;
+        };
+        let tz = timezones::get_by_name(timezone)?;
+        let ends_at = starts_at + Duration::minutes(duration_minutes.into());
+
+        Some(Self {
+            timezone: timezone.to_owned(),
+            starts_at: starts_at.to_timezone(tz),
+            ends_at: ends_at.to_timezone(tz),
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -88,6 +104,41 @@ pub(crate) struct ParticipantVote {
     pub(crate) comment: Option<String>,
 }
 
+/// Votes cast on a single proposed date, without revealing their authors.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct DateVoteCounts {
+    pub(crate) date_id: Uuid,
+    pub(crate) yes: u32,
+    pub(crate) maybe: u32,
+    pub(crate) no: u32,
+    /// Vote comments in alphabetical order, so that they can not be
+    /// matched with participants
+    pub(crate) comments: Vec<String>,
+}
+
+impl DateVoteCounts {
+    fn new(date_id: Uuid) -> Self {
+        Self {
+            date_id,
+            yes: 0,
+            maybe: 0,
+            no: 0,
+            comments: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, vote: &ParticipantVote) {
+        match vote.vote {
+            Vote::Yes => self.yes += 1,
+            Vote::Maybe => self.maybe += 1,
+            Vote::No => self.no += 1,
+        }
+        if let Some(ref comment) = vote.comment {
+            self.comments.push(comment.clone());
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct MeetingInfo {
     /// Name of the meeting
@@ -99,6 +150,22 @@ pub(crate) struct MeetingInfo {
     /// Date and time of meeting creation
     #[serde(with = "super::serde_rfc_3339::offset_date_time")]
     pub(crate) created_at: OffsetDateTime,
+    /// IANA name of the timezone in which meeting takes place
+    pub(crate) timezone: String,
+    /// Whether participants vote on dates or mark their availability
+    pub(crate) mode: MeetingMode,
+    pub(crate) settings: MeetingSettings,
+    /// Deadline after which participants can no longer vote
+    #[serde(with = "super::serde_rfc_3339::offset_date_time::option")]
+    pub(crate) voting_closes_at: Option<OffsetDateTime>,
+    /// Date and time at which creator closed voting
+    #[serde(with = "super::serde_rfc_3339::offset_date_time::option")]
+    pub(crate) voting_closed_at: Option<OffsetDateTime>,
+    /// Id of the proposed date chosen as the outcome of voting
+    pub(crate) final_date_id: Option<Uuid>,
+    /// Version of meeting that must be sent in `If-Match` header of
+    /// requests changing meeting details or dates
+    pub(crate) version: i64,
 }
 
 impl From<models::MeetingInfo> for MeetingInfo {
@@ -108,16 +175,43 @@ impl From<models::MeetingInfo> for MeetingInfo {
             description,
             created_by,
             created_at,
+            timezone,
+            has_availability_grid,
+            settings,
+            voting_closes_at,
+            voting_closed_at,
+            final_date_id,
+            version,
         } = value;
+        let mode = if has_availability_grid {
+            MeetingMode::Availability
+        } else {
+            MeetingMode::Poll
+        };
         Self {
             name,
             description,
             created_by,
             created_at,
+            timezone,
+            mode,
+            settings: settings.into(),
+            voting_closes_at,
+            voting_closed_at,
+            final_date_id,
+            version,
         }
     }
 }
 
+impl MeetingInfo {
+    /// Whether participants can no longer vote, either because creator
+    /// closed voting or because the voting deadline has passed.
+    pub(crate) fn is_voting_closed(&self) -> bool {
+        business_logic::is_voting_closed(self.voting_closed_at, self.voting_closes_at)
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct MeetingComment {
     /// Comment message
@@ -149,70 +243,118 @@ pub(crate) struct Meeting {
     #[serde(flatten)]
     pub(crate) meeting_info: MeetingInfo,
     pub(crate) comments: Vec<MeetingComment>,
+    /// Participants in order in which they joined, participants that joined
+    /// at the same time are ordered by id
     pub(crate) participants: Vec<Participant>,
+    /// Chronologically by day in meeting's timezone, whole day before time
+    /// slots of that day, which are ordered by their start and duration
     pub(crate) proposed_dates: Vec<ProposedDate>,
+    /// Votes ordered by participant, as in `participants`, and then by date,
+    /// as in `proposed_dates`
     pub(crate) votes: Vec<ParticipantVote>,
+    /// Number of votes cast on every proposed date
+    pub(crate) vote_counts: Vec<DateVoteCounts>,
+    /// Whether votes of other participants were left out of `votes`
+    /// and `vote_counts`
+    pub(crate) results_hidden: bool,
 }
 
 impl Meeting {
+    /// Assembles meeting from database rows. Time slots are rendered in the
+    /// timezone of `viewer` participant, or in meeting's timezone if viewer
+    /// is unknown or did not set their timezone. Dates are marked according
+    /// to holiday settings of the meeting.
+    ///
+    /// Participants and dates keep the order in which storage returns them,
+    /// that is by join time and chronologically. Votes are ordered by their
+    /// participant and then by their date.
     pub(crate) fn new(
         meeting_info: models::MeetingInfo,
         comments: Vec<models::MeetingComment>,
-        participants_proposed_dates_votes: Vec<models::ParticipantsProposedDatesVotes>,
+        participants_dates_votes: models::ParticipantsDatesVotes,
+        viewer: Option<Uuid>,
+        holidays: &HolidayCalendars,
     ) -> Result<Self> {
-        let meeting_info = meeting_info.into();
+        let meeting_info: MeetingInfo = meeting_info.into();
         let model_comments = comments;
         let mut comments = Vec::with_capacity(model_comments.len());
         comments.extend(model_comments.into_iter().map(Into::into));
 
-        let mut participants = Vec::new();
-        let mut proposed_dates = Vec::new();
-        let mut votes = Vec::new();
-
-        for row in participants_proposed_dates_votes {
-            match row.try_into()? {
-                ValidatedParticipantsProposedDatesVotes::Participant { user_id, name } => {
-                    let participant = Participant { id: user_id, name };
-                    if !participants.contains(&participant) {
-                        participants.push(participant);
-                    }
-                }
-                ValidatedParticipantsProposedDatesVotes::ProposedDate { date_id, date } => {
-                    let proposed_date = ProposedDate { id: date_id, date };
-                    if !proposed_dates.contains(&proposed_date) {
-                        proposed_dates.push(proposed_date);
-                    }
-                }
-                ValidatedParticipantsProposedDatesVotes::Vote {
-                    user_id,
-                    name,
-                    date_id,
-                    date,
-                    vote,
-                    comment,
-                } => {
-                    let participant = Participant { id: user_id, name };
-                    let proposed_date = ProposedDate { id: date_id, date };
-                    let participant_vote = ParticipantVote {
-                        participant_id: user_id,
-                        date_id,
-                        vote,
-                        comment,
-                    };
-
-                    if !participants.contains(&participant) {
-                        participants.push(participant);
-                    }
-                    if !proposed_dates.contains(&proposed_date) {
-                        proposed_dates.push(proposed_date);
-                    }
-                    if votes.contains(&participant_vote) {
-                        bail!("vote already in list: {:?}", vote);
-                    }
-                    votes.push(participant_vote);
-                }
+        let participants = participants_dates_votes
+            .participants
+            .into_iter()
+            .map(|participant| Participant {
+                id: participant.id,
+                name: participant.name,
+                timezone: participant.timezone,
+                required: participant.required,
+            })
+            .collect::<Vec<_>>();
+        let mut proposed_dates = participants_dates_votes
+            .proposed_dates
+            .into_iter()
+            .map(|date| {
+                let option =
+                    DateOption::from_columns(date.date, date.starts_at, date.duration_minutes)
+                        .ok_or_else(|| anyhow!("failed to validate proposed date: {:?}", date))?;
+                Ok(ProposedDate::new(
+                    date.id,
+                    option,
+                    date.proposed_by,
+                    date.approved,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut votes = participants_dates_votes
+            .votes
+            .into_iter()
+            .map(|vote| ParticipantVote {
+                participant_id: vote.user_id,
+                date_id: vote.proposed_date_id,
+                vote: vote.vote.into(),
+                comment: vote.comment,
+            })
+            .collect::<Vec<_>>();
+
+        let participant_indices = participants
+            .iter()
+            .enumerate()
+            .map(|(index, participant)| (participant.id, index))
+            .collect::<HashMap<_, _>>();
+        let date_indices = proposed_dates
+            .iter()
+            .enumerate()
+            .map(|(index, date)| (date.id, index))
+            .collect::<HashMap<_, _>>();
+        votes.sort_by_cached_key(|vote| {
+            (
+                participant_indices.get(&vote.participant_id).copied(),
+                date_indices.get(&vote.date_id).copied(),
+            )
+        });
+
+        let display_timezone = viewer
+            .and_then(|viewer| participant_indices.get(&viewer))
+            .and_then(|&index| participants[index].timezone.as_deref())
+            .unwrap_or(&meeting_info.timezone);
+        let meeting_tz = meeting_timezone(&meeting_info.timezone)?;
+        for proposed_date in &mut proposed_dates {
+            proposed_date.local = LocalTimeSlot::new(proposed_date.option, display_timezone);
+            proposed_date.mark(&meeting_info.settings, holidays, meeting_tz);
+        }
+
+        let mut vote_counts = proposed_dates
+            .iter()
+            .map(|date| DateVoteCounts::new(date.id))
+            .collect::<Vec<_>>();
+        for vote in &votes {
+            if let Some(&index) = date_indices.get(&vote.date_id) {
+                vote_counts[index].add(vote);
             }
         }
+        for counts in &mut vote_counts {
+            counts.comments.sort_unstable();
+        }
 
         Ok(Self {
             meeting_info,
@@ -220,6 +362,251 @@ impl Meeting {
             participants,
             proposed_dates,
             votes,
+            vote_counts,
+            results_hidden: false,
+        })
+    }
+
+    /// Leaves out all votes except those cast by `visible_to` participant.
+    pub(crate) fn hide_votes(&mut self, visible_to: Option<Uuid>) {
+        self.votes
+            .retain(|vote| Some(vote.participant_id) == visible_to);
+        self.vote_counts.clear();
+        self.results_hidden = true;
+    }
+
+    /// Leaves out votes of everyone except `visible_to` participant, so that
+    /// results are only reported as counts in `vote_counts`.
+    pub(crate) fn anonymize_votes(&mut self, visible_to: Option<Uuid>) {
+        self.votes
+            .retain(|vote| Some(vote.participant_id) == visible_to);
+    }
+}
+
+/// Outcome of voting on a single proposed date.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct DateResult {
+    pub(crate) date_id: Uuid,
+    pub(crate) yes: u32,
+    pub(crate) maybe: u32,
+    pub(crate) no: u32,
+    /// Every `yes` is worth two points and every `maybe` one point
+    pub(crate) score: u32,
+    /// Whether some required participant voted `no` on this date. Reported
+    /// even in meetings with anonymous votes, as it decides `viable` and the
+    /// best date. If only one participant is required, this reveals their
+    /// vote; anonymity only hides which of the required participants voted.
+    pub(crate) blocked: bool,
+    /// Required participants that voted `no` on this date. Always empty in
+    /// meetings with anonymous votes.
+    pub(crate) blocked_by: Vec<Uuid>,
+    /// Whether this date is not blocked and satisfies quorum and capacity
+    /// of the meeting
+    pub(crate) viable: bool,
+}
+
+/// Results of voting in a poll.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Results {
+    /// Approved proposed dates in chronological order
+    pub(crate) dates: Vec<DateResult>,
+    /// Best-scoring viable date
+    pub(crate) best_date_id: Option<Uuid>,
+}
+
+impl Results {
+    pub(crate) fn new(
+        tallies: Vec<results::DateTally>,
+        quorum: &results::Quorum,
+        anonymous_votes: bool,
+    ) -> Self {
+        let best_date_id = results::best_date(&tallies, quorum);
+        let dates = tallies
+            .into_iter()
+            .map(|tally| DateResult {
+                viable: tally.is_viable(quorum),
+                date_id: tally.date_id,
+                yes: tally.yes,
+                maybe: tally.maybe,
+                no: tally.no,
+                score: tally.score(),
+                blocked: tally.is_blocked(),
+                blocked_by: if anonymous_votes {
+                    Vec::new()
+                } else {
+                    tally.blocked_by
+                },
+            })
+            .collect();
+
+        Self {
+            dates,
+            best_date_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HistoryAction {
+    DetailsUpdated,
+    DateProposed,
+    DateApproved,
+    DateRejected,
+    VoteCast,
+    CommentPosted,
+    ParticipantRequiredChanged,
+    VotingClosed,
+    MeetingFinalized,
+}
+
+impl From<models::HistoryAction> for HistoryAction {
+    fn from(value: models::HistoryAction) -> Self {
+        match value {
+            models::HistoryAction::DetailsUpdated => Self::DetailsUpdated,
+            models::HistoryAction::DateProposed => Self::DateProposed,
+            models::HistoryAction::DateApproved => Self::DateApproved,
+            models::HistoryAction::DateRejected => Self::DateRejected,
+            models::HistoryAction::VoteCast => Self::VoteCast,
+            models::HistoryAction::CommentPosted => Self::CommentPosted,
+            models::HistoryAction::ParticipantRequiredChanged => Self::ParticipantRequiredChanged,
+            models::HistoryAction::VotingClosed => Self::VotingClosed,
+            models::HistoryAction::MeetingFinalized => Self::MeetingFinalized,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct HistoryEntry {
+    /// Entries are numbered in the order they were recorded
+    pub(crate) id: i64,
+    /// User that made the change. `None` if user was deleted or if it
+    /// would reveal an anonymous vote.
+    pub(crate) actor_id: Option<Uuid>,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) recorded_at: OffsetDateTime,
+    pub(crate) action: HistoryAction,
+    pub(crate) proposed_date_id: Option<Uuid>,
+    /// Changed values before the change, `None` if they did not exist
+    pub(crate) old_value: Option<JsonValue>,
+    /// Changed values after the change, `None` if they were deleted
+    pub(crate) new_value: Option<JsonValue>,
+}
+
+/// Changes of details, votes, comments, proposed dates and required
+/// participants of a meeting, and its closing and finalization.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct MeetingHistory {
+    pub(crate) entries: Vec<HistoryEntry>,
+}
+
+impl MeetingHistory {
+    pub(crate) fn new(history: Vec<models::HistoryEntry>, anonymous_votes: bool) -> Self {
+        let entries = history
+            .into_iter()
+            .map(|entry| {
+                let action = entry.action.into();
+                let actor_id = match action {
+                    HistoryAction::VoteCast if anonymous_votes => None,
+                    _ => entry.actor_id,
+                };
+                HistoryEntry {
+                    id: entry.id,
+                    actor_id,
+                    recorded_at: entry.recorded_at,
+                    action,
+                    proposed_date_id: entry.proposed_date_id,
+                    old_value: entry.old_value,
+                    new_value: entry.new_value,
+                }
+            })
+            .collect();
+
+        Self { entries }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ParticipantAvailability {
+    pub(crate) participant_id: Uuid,
+    /// One `0` (busy) or `1` (free) character for every slot of the grid
+    pub(crate) slots: String,
+}
+
+/// Time window in which every participant is free.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CommonWindow {
+    /// Window as a time slot starting at UTC instant
+    #[serde(flatten)]
+    pub(crate) option: DateOption,
+    /// Window rendered in meeting's timezone
+    pub(crate) local: Option<LocalTimeSlot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Availability {
+    #[serde(flatten)]
+    pub(crate) grid: AvailabilityGrid,
+    /// IANA name of the timezone in which grid is laid out
+    pub(crate) timezone: String,
+    pub(crate) participants: Vec<ParticipantAvailability>,
+    /// Number of free participants in every slot, one row per day
+    pub(crate) heatmap: Vec<Vec<u32>>,
+    /// Longest windows in which every participant is free, longest first
+    pub(crate) common_windows: Vec<CommonWindow>,
+}
+
+impl Availability {
+    /// Maximal number of common windows returned.
+    const MAX_COMMON_WINDOWS: usize = 10;
+
+    pub(crate) fn new(
+        timezone: String,
+        grid: models::AvailabilityGrid,
+        participants: Vec<models::ParticipantAvailability>,
+    ) -> Result<Self> {
+        let grid = availability::AvailabilityGrid::try_from(grid)?;
+        let tz = meeting_timezone(&timezone)?;
+
+        let slots = participants
+            .iter()
+            .map(|participant| {
+                availability::AvailabilitySlots::from_bytes(&participant.slots, &grid)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let heatmap = availability::heatmap(&grid, &slots);
+        let common_windows = availability::common_windows(&grid, &slots)
+            .into_iter()
+            .take(Self::MAX_COMMON_WINDOWS)
+            .map(|window| {
+                let option = DateOption::TimeSlot {
+                    starts_at: grid
+                        .slot_starts_at(window.day, window.first_slot, tz)
+                        .to_offset(UtcOffset::UTC),
+                    duration_minutes: (window.slot_count * usize::from(grid.slot_minutes))
+                        .try_into()?,
+                };
+                Ok(CommonWindow {
+                    option,
+                    local: LocalTimeSlot::new(option, &timezone),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let participants = participants
+            .into_iter()
+            .zip(slots)
+            .map(|(participant, slots)| ParticipantAvailability {
+                participant_id: participant.user_id,
+                slots: slots.to_string(),
+            })
+            .collect();
+
+        Ok(Self {
+            grid: grid.into(),
+            timezone,
+            participants,
+            heatmap,
+            common_windows,
         })
     }
 }
@@ -231,8 +618,142 @@ pub(crate) struct CreatedMeeting {
     pub(crate) meeting_id: Uuid,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CreatedAccount {
+    pub(crate) id: Uuid,
+    pub(crate) secret_token: Uuid,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ParticipantRole {
+    Creator,
+    Participant,
+}
+
+/// Meeting in which account takes part, with credentials of the account's
+/// participant in it.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AccountMeeting {
+    pub(crate) meeting_id: Uuid,
+    pub(crate) name: String,
+    #[serde(with = "super::serde_rfc_3339::offset_date_time")]
+    pub(crate) created_at: OffsetDateTime,
+    pub(crate) role: ParticipantRole,
+    pub(crate) user_id: Uuid,
+    pub(crate) user_secret_token: Uuid,
+}
+
+impl From<models::AccountMeeting> for AccountMeeting {
+    fn from(value: models::AccountMeeting) -> Self {
+        let role = if value.created_by == value.user_id {
+            ParticipantRole::Creator
+        } else {
+            ParticipantRole::Participant
+        };
+        Self {
+            meeting_id: value.meeting_id,
+            name: value.name,
+            created_at: value.created_at,
+            role,
+            user_id: value.user_id,
+            user_secret_token: value.user_secret_token,
+        }
+    }
+}
+
+/// Meetings created or joined by an account, newest first.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AccountMeetings {
+    pub(crate) meetings: Vec<AccountMeeting>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub(crate) struct JoinMeetingResponse {
     pub(crate) id: Uuid,
     pub(crate) secret_token: Uuid,
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ImportedParticipant {
+    pub(crate) id: Uuid,
+    pub(crate) secret_token: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct FinalizedMeeting {
+    pub(crate) final_date_id: Uuid,
+    /// Reasons why the final date is not viable. Only non-empty if
+    /// finalization was forced.
+    pub(crate) warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ImportedMeeting {
+    pub(crate) meeting_id: Uuid,
+    /// Newly generated credentials of every imported participant
+    pub(crate) participants: Vec<ImportedParticipant>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct AddedProposedDate {
+    pub(crate) id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct GeneratedProposedDates {
+    /// Generated dates in chronological order. Time slots are rendered
+    /// in meeting's timezone.
+    pub(crate) proposed_dates: Vec<ProposedDate>,
+}
+
+impl GeneratedProposedDates {
+    pub(crate) fn new(
+        proposed_dates: &[business_logic::ProposedDate],
+        meeting_info: &MeetingInfo,
+        holidays: &HolidayCalendars,
+    ) -> Result<Self> {
+        let tz = meeting_timezone(&meeting_info.timezone)?;
+        let proposed_dates = proposed_dates
+            .iter()
+            .map(|date| {
+                let mut proposed_date =
+                    ProposedDate::new(date.id, date.option, date.proposed_by, date.approved);
+                proposed_date.local = LocalTimeSlot::new(date.option, &meeting_info.timezone);
+                proposed_date.mark(&meeting_info.settings, holidays, tz);
+                proposed_date
+            })
+            .collect();
+        Ok(Self { proposed_dates })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InvalidField {
+    pub(crate) field: String,
+    pub(crate) message: String,
+}
+
+/// Body of responses to requests with invalid fields
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct InvalidFields {
+    pub(crate) errors: Vec<InvalidField>,
+}
+
+impl From<ValidationErrors> for InvalidFields {
+    fn from(errors: ValidationErrors) -> Self {
+        let errors = errors
+            .0
+            .into_iter()
+            .map(|error| InvalidField {
+                field: error.field.to_owned(),
+                message: error.message,
+            })
+            .collect();
+        Self { errors }
+    }
+}
+
+fn meeting_timezone(timezone: &str) -> Result<&'static Tz> {
+    timezones::get_by_name(timezone).ok_or_else(|| anyhow!("unknown meeting timezone `{timezone}`"))
+}
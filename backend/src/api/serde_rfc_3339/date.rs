@@ -32,6 +32,29 @@ pub(crate) fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Da
     deserializer.deserialize_str(Rfc3339DateVisitor)
 }
 
+/// (De)serializes list of dates as RFC 3339 formatted strings.
+pub(crate) mod vec {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use time::Date;
+
+    #[derive(Serialize, Deserialize)]
+    struct Rfc3339Date(#[serde(with = "super")] Date);
+
+    pub(crate) fn serialize<S: Serializer>(
+        dates: &[Date],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(dates.iter().copied().map(Rfc3339Date))
+    }
+
+    pub(crate) fn deserialize<'a, D: Deserializer<'a>>(
+        deserializer: D,
+    ) -> Result<Vec<Date>, D::Error> {
+        let dates = Vec::<Rfc3339Date>::deserialize(deserializer)?;
+        Ok(dates.into_iter().map(|Rfc3339Date(date)| date).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,4 +80,17 @@ mod tests {
 
         assert_eq!(expected, serde_json::from_str(r#""2023-07-24""#).unwrap());
     }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Bar(#[serde(with = "super::vec")] Vec<Date>);
+
+    #[test]
+    fn vec_roundtrip() {
+        #[rustfmt::skip]
+        let bar = Bar(vec![date!(2023-07-24), date!(2023-07-25)]);
+
+        let s = serde_json::to_string(&bar).unwrap();
+        assert_eq!(s, r#"["2023-07-24","2023-07-25"]"#);
+        assert_eq!(bar, serde_json::from_str(&s).unwrap());
+    }
 }
@@ -1,2 +1,3 @@
 pub(crate) mod date;
 pub(crate) mod offset_date_time;
+pub(crate) mod time;
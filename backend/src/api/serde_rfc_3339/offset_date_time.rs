@@ -37,6 +37,29 @@ pub(crate) fn deserialize<'a, D: Deserializer<'a>>(
     deserializer.deserialize_str(Rfc3339OffsetDateTimeVisitor)
 }
 
+/// (De)serializes optional date time as RFC 3339 formatted string or `null`.
+pub(crate) mod option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use time::OffsetDateTime;
+
+    #[derive(Serialize, Deserialize)]
+    struct Rfc3339OffsetDateTime(#[serde(with = "super")] OffsetDateTime);
+
+    pub(crate) fn serialize<S: Serializer>(
+        date: &Option<OffsetDateTime>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        date.map(Rfc3339OffsetDateTime).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'a, D: Deserializer<'a>>(
+        deserializer: D,
+    ) -> Result<Option<OffsetDateTime>, D::Error> {
+        let date = Option::<Rfc3339OffsetDateTime>::deserialize(deserializer)?;
+        Ok(date.map(|Rfc3339OffsetDateTime(date)| date))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +103,25 @@ mod tests {
         let actual: Foo = serde_json::from_str(str).unwrap();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn option() {
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        struct Bar(#[serde(with = "super::option")] Option<OffsetDateTime>);
+
+        #[rustfmt::skip]
+        let some = Bar(Some(datetime!(2023-07-24 12:34:56 UTC)));
+        let none = Bar(None);
+
+        assert_eq!(
+            serde_json::to_string(&some).unwrap(),
+            r#""2023-07-24T12:34:56Z""#
+        );
+        assert_eq!(serde_json::to_string(&none).unwrap(), "null");
+        assert_eq!(
+            serde_json::from_str::<Bar>(r#""2023-07-24T12:34:56Z""#).unwrap(),
+            some
+        );
+        assert_eq!(serde_json::from_str::<Bar>("null").unwrap(), none);
+    }
 }
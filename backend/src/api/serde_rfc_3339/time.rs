@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+use std::fmt;
+
+use serde::ser::Error as _;
+use serde::{de, Deserializer, Serialize, Serializer};
+use time::{format_description::FormatItem, macros::format_description, Time};
+
+const RFC3339_FORMAT: &[FormatItem<'_>] = format_description!("[hour]:[minute]:[second]");
+
+struct Rfc3339TimeVisitor;
+
+impl<'de> de::Visitor<'de> for Rfc3339TimeVisitor {
+    type Value = Time;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a RFC 3339 formatted partial time")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Time::parse(v, &RFC3339_FORMAT).map_err(E::custom)
+    }
+}
+
+pub(crate) fn serialize<S: Serializer>(time: &Time, serializer: S) -> Result<S::Ok, S::Error> {
+    time.format(&RFC3339_FORMAT)
+        .map_err(S::Error::custom)?
+        .serialize(serializer)
+}
+
+pub(crate) fn deserialize<'a, D: Deserializer<'a>>(deserializer: D) -> Result<Time, D::Error> {
+    deserializer.deserialize_str(Rfc3339TimeVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use time::macros::time;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Foo(#[serde(with = "super")] Time);
+
+    #[test]
+    fn serialize() {
+        let foo = Foo(time!(09:05:00));
+
+        let s = serde_json::to_string(&foo).unwrap();
+        assert_eq!(s, r#""09:05:00""#);
+    }
+
+    #[test]
+    fn deserialize() {
+        let expected = Foo(time!(09:05:00));
+
+        assert_eq!(expected, serde_json::from_str(r#""09:05:00""#).unwrap());
+    }
+}
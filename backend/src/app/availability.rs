@@ -0,0 +1,343 @@
+use std::fmt;
+
+use anyhow::{anyhow, bail, Context, Result};
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+use time_tz::Tz;
+
+use super::business_logic::assume_timezone;
+use crate::database::models;
+
+/// Grid of time slots on which participants of availability meetings mark
+/// when they are free. Grid spans every day from `first_day` to `last_day`
+/// and on each of them the window from `day_starts_at` to `day_ends_at`
+/// (in meeting's timezone), divided into slots of `slot_minutes` each.
+///
+/// Slots are indexed day by day, so slot `n` of day `d` has index
+/// `d * slots_per_day + n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AvailabilityGrid {
+    pub(crate) first_day: Date,
+    pub(crate) last_day: Date,
+    pub(crate) day_starts_at: Time,
+    pub(crate) day_ends_at: Time,
+    pub(crate) slot_minutes: u16,
+}
+
+impl AvailabilityGrid {
+    /// Maximal number of days that grid can span.
+    const MAX_DAYS: i64 = 31;
+    /// Shortest allowed slot.
+    const MIN_SLOT_MINUTES: u16 = 5;
+
+    pub(crate) fn new(
+        first_day: Date,
+        last_day: Date,
+        day_starts_at: Time,
+        day_ends_at: Time,
+        slot_minutes: u16,
+    ) -> Result<Self> {
+        let validate = || {
+            if last_day < first_day {
+                bail!("last day is before first day");
+            }
+            if (last_day - first_day).whole_days() >= Self::MAX_DAYS {
+                bail!("grid spans more than {} days", Self::MAX_DAYS);
+            }
+            if day_ends_at <= day_starts_at {
+                bail!("day window ends before it starts");
+            }
+            if slot_minutes < Self::MIN_SLOT_MINUTES {
+                bail!("slots are shorter than {} minutes", Self::MIN_SLOT_MINUTES);
+            }
+            let window_minutes = (day_ends_at - day_starts_at).whole_minutes();
+            if window_minutes % i64::from(slot_minutes) != 0 {
+                bail!("day window of {window_minutes} minutes is not divisible into {slot_minutes} minute slots");
+            }
+            Ok(())
+        };
+        validate().context("failed to validate availability grid")?;
+
+        Ok(Self {
+            first_day,
+            last_day,
+            day_starts_at,
+            day_ends_at,
+            slot_minutes,
+        })
+    }
+
+    pub(crate) fn days(&self) -> usize {
+        (self.last_day - self.first_day).whole_days() as usize + 1
+    }
+
+    pub(crate) fn slots_per_day(&self) -> usize {
+        let window_minutes = (self.day_ends_at - self.day_starts_at).whole_minutes();
+        (window_minutes / i64::from(self.slot_minutes)) as usize
+    }
+
+    pub(crate) fn slot_count(&self) -> usize {
+        self.days() * self.slots_per_day()
+    }
+
+    /// Returns instant at which slot `slot` of day `day` starts, when the
+    /// grid is laid out in timezone `tz`.
+    pub(crate) fn slot_starts_at(&self, day: usize, slot: usize, tz: &Tz) -> OffsetDateTime {
+        let date = self.first_day + Duration::days(day as i64);
+        let local = PrimitiveDateTime::new(date, self.day_starts_at)
+            + Duration::minutes(slot as i64 * i64::from(self.slot_minutes));
+        assume_timezone(local, tz)
+    }
+}
+
+impl TryFrom<models::AvailabilityGrid> for AvailabilityGrid {
+    type Error = anyhow::Error;
+
+    fn try_from(value: models::AvailabilityGrid) -> Result<Self, Self::Error> {
+        let slot_minutes = value
+            .slot_minutes
+            .try_into()
+            .with_context(|| format!("invalid slot length: {}", value.slot_minutes))?;
+        Self::new(
+            value.first_day,
+            value.last_day,
+            value.day_starts_at,
+            value.day_ends_at,
+            slot_minutes,
+        )
+    }
+}
+
+/// Availability of a single participant: one flag for every slot of the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AvailabilitySlots(Vec<bool>);
+
+impl AvailabilitySlots {
+    /// Parses string of `0` (busy) and `1` (free) characters, one for every
+    /// slot of `grid`.
+    pub(crate) fn parse(slots: &str, grid: &AvailabilityGrid) -> Result<Self> {
+        let slots = slots
+            .chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                c => Err(anyhow!("invalid slot character `{c}`")),
+            })
+            .collect::<Result<Vec<_>>>()
+            .context("failed to parse availability")?;
+        if slots.len() != grid.slot_count() {
+            return Err(
+                anyhow!("expected {} slots, got {}", grid.slot_count(), slots.len())
+                    .context("failed to parse availability"),
+            );
+        }
+
+        Ok(Self(slots))
+    }
+
+    /// Unpacks bitmap created with [`AvailabilitySlots::to_bytes`].
+    pub(crate) fn from_bytes(bytes: &[u8], grid: &AvailabilityGrid) -> Result<Self> {
+        let slot_count = grid.slot_count();
+        if bytes.len() != slot_count.div_ceil(8) {
+            bail!(
+                "bitmap of {} bytes does not match grid of {} slots",
+                bytes.len(),
+                slot_count
+            );
+        }
+
+        let slots = (0..slot_count)
+            .map(|i| bytes[i / 8] & (0x80 >> (i % 8)) != 0)
+            .collect();
+        Ok(Self(slots))
+    }
+
+    /// Packs flags into a bitmap, most significant bit first.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.0.len().div_ceil(8)];
+        for (i, _) in self.0.iter().enumerate().filter(|(_, free)| **free) {
+            bytes[i / 8] |= 0x80 >> (i % 8);
+        }
+        bytes
+    }
+
+    pub(crate) fn is_free(&self, slot: usize) -> bool {
+        self.0[slot]
+    }
+}
+
+impl fmt::Display for AvailabilitySlots {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for free in &self.0 {
+            f.write_str(if *free { "1" } else { "0" })?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns number of free participants for every slot, one row per day.
+pub(crate) fn heatmap(
+    grid: &AvailabilityGrid,
+    participants: &[AvailabilitySlots],
+) -> Vec<Vec<u32>> {
+    let slots_per_day = grid.slots_per_day();
+    (0..grid.days())
+        .map(|day| {
+            (0..slots_per_day)
+                .map(|slot| {
+                    let index = day * slots_per_day + slot;
+                    participants.iter().filter(|p| p.is_free(index)).count() as u32
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Consecutive slots of a single day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SlotRange {
+    pub(crate) day: usize,
+    pub(crate) first_slot: usize,
+    pub(crate) slot_count: usize,
+}
+
+/// Returns maximal ranges of slots in which every participant is free,
+/// longest first. Ranges of equal length are ordered chronologically.
+pub(crate) fn common_windows(
+    grid: &AvailabilityGrid,
+    participants: &[AvailabilitySlots],
+) -> Vec<SlotRange> {
+    if participants.is_empty() {
+        return Vec::new();
+    }
+
+    let slots_per_day = grid.slots_per_day();
+    let mut windows = Vec::new();
+    for day in 0..grid.days() {
+        let mut start = None;
+        for slot in 0..=slots_per_day {
+            let everyone_free = slot < slots_per_day
+                && participants
+                    .iter()
+                    .all(|p| p.is_free(day * slots_per_day + slot));
+            match (everyone_free, start) {
+                (true, None) => start = Some(slot),
+                (false, Some(first_slot)) => {
+                    windows.push(SlotRange {
+                        day,
+                        first_slot,
+                        slot_count: slot - first_slot,
+                    });
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Sort is stable, so windows of equal length stay in chronological order
+    windows.sort_by_key(|window| std::cmp::Reverse(window.slot_count));
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime, time};
+    use time_tz::timezones;
+
+    fn grid() -> AvailabilityGrid {
+        #[rustfmt::skip]
+        let grid = AvailabilityGrid::new(date!(2023-07-24), date!(2023-07-25), time!(09:00), time!(11:00), 30);
+        grid.unwrap()
+    }
+
+    #[test]
+    fn grid_dimensions() {
+        let grid = grid();
+
+        assert_eq!(grid.days(), 2);
+        assert_eq!(grid.slots_per_day(), 4);
+        assert_eq!(grid.slot_count(), 8);
+    }
+
+    #[test]
+    fn invalid_grid() {
+        #[rustfmt::skip]
+        let invalid = [
+            AvailabilityGrid::new(date!(2023-07-25), date!(2023-07-24), time!(09:00), time!(11:00), 30),
+            AvailabilityGrid::new(date!(2023-07-24), date!(2023-08-24), time!(09:00), time!(11:00), 30),
+            AvailabilityGrid::new(date!(2023-07-24), date!(2023-07-25), time!(11:00), time!(09:00), 30),
+            AvailabilityGrid::new(date!(2023-07-24), date!(2023-07-25), time!(09:00), time!(11:00), 45),
+            AvailabilityGrid::new(date!(2023-07-24), date!(2023-07-25), time!(09:00), time!(11:00), 0),
+        ];
+
+        for grid in invalid {
+            assert!(grid.is_err(), "{grid:?}");
+        }
+    }
+
+    #[test]
+    fn slot_starts_at_in_timezone() {
+        let grid = grid();
+        let warsaw = timezones::get_by_name("Europe/Warsaw").unwrap();
+
+        #[rustfmt::skip]
+        assert_eq!(grid.slot_starts_at(1, 3, warsaw), datetime!(2023-07-25 08:30 UTC));
+    }
+
+    #[test]
+    fn bitmap_roundtrip() {
+        let grid = grid();
+        let slots = AvailabilitySlots::parse("10000011", &grid).unwrap();
+
+        assert_eq!(slots.to_bytes(), vec![0b1000_0011]);
+        assert_eq!(
+            AvailabilitySlots::from_bytes(&slots.to_bytes(), &grid).unwrap(),
+            slots
+        );
+        assert_eq!(slots.to_string(), "10000011");
+    }
+
+    #[test]
+    fn parse_invalid_slots() {
+        let grid = grid();
+
+        assert!(AvailabilitySlots::parse("1000001", &grid).is_err());
+        assert!(AvailabilitySlots::parse("100000111", &grid).is_err());
+        assert!(AvailabilitySlots::parse("1000001x", &grid).is_err());
+    }
+
+    #[test]
+    fn heatmap_and_common_windows() {
+        let grid = grid();
+        let participants = [
+            AvailabilitySlots::parse("01111101", &grid).unwrap(),
+            AvailabilitySlots::parse("01101111", &grid).unwrap(),
+        ];
+
+        assert_eq!(
+            heatmap(&grid, &participants),
+            vec![vec![0, 2, 2, 1], vec![2, 2, 1, 2]]
+        );
+        assert_eq!(
+            common_windows(&grid, &participants),
+            vec![
+                SlotRange {
+                    day: 0,
+                    first_slot: 1,
+                    slot_count: 2
+                },
+                SlotRange {
+                    day: 1,
+                    first_slot: 0,
+                    slot_count: 2
+                },
+                SlotRange {
+                    day: 1,
+                    first_slot: 3,
+                    slot_count: 1
+                },
+            ]
+        );
+    }
+}
@@ -1,27 +1,120 @@
-use anyhow::{anyhow, Result};
-use time::{ext::NumericalDuration, OffsetDateTime};
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Context, Result};
+use time::{ext::NumericalDuration, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+use time_tz::{timezones, OffsetResult, PrimitiveDateTimeExt, TimeZone, Tz};
 use uuid::Uuid;
 
+use super::availability::{AvailabilityGrid, AvailabilitySlots};
+use super::holidays::HolidayCalendars;
+use super::validation::{self, check_text, TextRules};
+use crate::api::common::{DateOption, HolidayPolicy, MeetingSettings, Vote};
+use crate::api::export::{MeetingExport, EXPORT_FORMAT_VERSION};
+
+/// Timezone of meetings that did not specify one.
+pub(crate) const DEFAULT_TIMEZONE: &str = "Etc/UTC";
+
+/// Looks up timezone `name` in the bundled timezone database and returns
+/// its canonical IANA name. Aliases (e.g. `UTC`) and Windows timezone names
+/// are resolved to the IANA timezone they refer to.
+pub(crate) fn validate_timezone(name: &str) -> Result<String> {
+    match timezones::get_by_name(name) {
+        Some(timezone) => Ok(timezone.name().to_owned()),
+        None => Err(anyhow!("unknown timezone `{name}`").context("failed to validate timezone")),
+    }
+}
+
+/// Normalizes text of `what` and checks it against `rules`. Text coming
+/// from requests is already normalized by [`validation::Validate`], but
+/// other ways values reach the database, e.g. imports, are not.
+fn validate_text(what: &str, value: &str, rules: TextRules) -> Result<String> {
+    let value = validation::normalize_text(value, rules);
+    check_text(&value, rules).map_err(|message| {
+        anyhow!("{what} {message}").context(format!("failed to validate {what}"))
+    })?;
+    Ok(value)
+}
+
+/// Returns instant at which wall-clock time `local` occurs in timezone `tz`.
+/// Ambiguous times resolve to the earlier instant and times skipped by
+/// daylight saving time transitions use the offset in effect after the gap.
+pub(crate) fn assume_timezone(local: PrimitiveDateTime, tz: &Tz) -> OffsetDateTime {
+    match local.assume_timezone(tz) {
+        OffsetResult::Some(instant) | OffsetResult::Ambiguous(instant, _) => instant,
+        OffsetResult::None => local.assume_timezone_utc(tz),
+    }
+}
+
+/// Whether participants can no longer vote, either because creator closed
+/// voting at `voting_closed_at` or because deadline `voting_closes_at`
+/// has passed.
+pub(crate) fn is_voting_closed(
+    voting_closed_at: Option<OffsetDateTime>,
+    voting_closes_at: Option<OffsetDateTime>,
+) -> bool {
+    voting_closed_at.is_some()
+        || voting_closes_at.is_some_and(|closes_at| closes_at <= OffsetDateTime::now_utc())
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct User {
     pub(crate) id: Uuid,
     pub(crate) secret_token: Uuid,
     pub(crate) name: String,
+    /// Timezone in which dates are displayed to this user. If it is not
+    /// set, meeting's timezone is used.
+    pub(crate) timezone: Option<String>,
+    /// Whether meeting can only take place on dates this user can attend
+    pub(crate) required: bool,
+    /// Account linking this user with the same person's users in other
+    /// meetings
+    pub(crate) account_id: Option<Uuid>,
+    /// Date and time at which user joined the meeting
+    pub(crate) joined_at: OffsetDateTime,
 }
 
 impl User {
-    pub(crate) fn new(name: String) -> Result<Self> {
-        if name.is_empty() {
-            return Err(anyhow!("user name is empty").context("failed to validate name"));
-        }
+    pub(crate) fn new(name: String, timezone: Option<String>) -> Result<Self> {
+        Self::with_id(Uuid::new_v4(), name, timezone)
+    }
+
+    /// Creates user with already known `id` and a freshly generated secret token.
+    pub(crate) fn with_id(id: Uuid, name: String, timezone: Option<String>) -> Result<Self> {
+        let name = validate_text("user name", &name, validation::NAME)?;
+        let timezone = timezone.as_deref().map(validate_timezone).transpose()?;
 
-        let id = Uuid::new_v4();
         let secret_token = Uuid::new_v4();
 
         Ok(Self {
             id,
             secret_token,
             name,
+            timezone,
+            required: false,
+            account_id: None,
+            joined_at: OffsetDateTime::now_utc(),
+        })
+    }
+}
+
+/// Persistent identity of a person taking part in many meetings.
+#[derive(Debug, Clone)]
+pub(crate) struct Account {
+    pub(crate) id: Uuid,
+    pub(crate) secret_token: Uuid,
+    pub(crate) name: String,
+    pub(crate) created_at: OffsetDateTime,
+}
+
+impl Account {
+    pub(crate) fn new(name: String) -> Result<Self> {
+        let name = validate_text("account name", &name, validation::NAME)?;
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            secret_token: Uuid::new_v4(),
+            name,
+            created_at: OffsetDateTime::now_utc(),
         })
     }
 }
@@ -34,19 +127,32 @@ pub(crate) struct Meeting {
     pub(crate) created_at: OffsetDateTime,
     pub(crate) expires_at: OffsetDateTime,
     pub(crate) user_id: Uuid,
+    /// IANA name of the timezone in which meeting takes place
+    pub(crate) timezone: String,
+    /// Grid of availability meeting. Meetings without grid are polls,
+    /// in which participants vote on proposed dates.
+    pub(crate) availability_grid: Option<AvailabilityGrid>,
+    pub(crate) settings: MeetingSettings,
+    /// Deadline after which participants can no longer vote
+    pub(crate) voting_closes_at: Option<OffsetDateTime>,
+    pub(crate) voting_closed_at: Option<OffsetDateTime>,
+    pub(crate) final_date_id: Option<Uuid>,
 }
 
 impl Meeting {
-    pub(crate) fn new(name: String, description: Option<String>, user_id: Uuid) -> Result<Self> {
-        if name.is_empty() {
-            return Err(anyhow!("meeting name is empty").context("failed to validate name"));
-        }
-        if let Some(ref description) = description {
-            if description.is_empty() {
-                return Err(anyhow!("description is set to empty string")
-                    .context("failed to validate description"));
-            }
-        }
+    pub(crate) fn new(
+        name: String,
+        description: Option<String>,
+        timezone: Option<String>,
+        availability_grid: Option<AvailabilityGrid>,
+        settings: MeetingSettings,
+        user_id: Uuid,
+        holidays: &HolidayCalendars,
+    ) -> Result<Self> {
+        let (name, description) =
+            Self::validate_name_and_description(&name, description.as_deref())?;
+        let timezone = validate_timezone(timezone.as_deref().unwrap_or(DEFAULT_TIMEZONE))?;
+        Self::validate_settings(&settings, holidays)?;
 
         let id = Uuid::new_v4();
         let created_at = OffsetDateTime::now_utc();
@@ -60,8 +166,113 @@ impl Meeting {
             created_at,
             expires_at,
             user_id,
+            timezone,
+            availability_grid,
+            settings,
+            voting_closes_at: None,
+            voting_closed_at: None,
+            final_date_id: None,
         })
     }
+
+    /// Sets deadline after which participants can no longer vote.
+    pub(crate) fn with_voting_closes_at(
+        mut self,
+        voting_closes_at: Option<OffsetDateTime>,
+    ) -> Result<Self> {
+        Self::validate_voting_closes_at(voting_closes_at, self.created_at, self.expires_at)?;
+        self.voting_closes_at = voting_closes_at;
+        Ok(self)
+    }
+
+    fn validate_voting_closes_at(
+        voting_closes_at: Option<OffsetDateTime>,
+        created_at: OffsetDateTime,
+        expires_at: OffsetDateTime,
+    ) -> Result<()> {
+        match voting_closes_at {
+            Some(closes_at) if closes_at < created_at => {
+                Err(anyhow!("voting closes before meeting was created")
+                    .context("failed to validate voting deadline"))
+            }
+            Some(closes_at) if closes_at > expires_at => {
+                Err(anyhow!("voting closes after meeting expires")
+                    .context("failed to validate voting deadline"))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_settings(settings: &MeetingSettings, holidays: &HolidayCalendars) -> Result<()> {
+        if let Some(ref calendar) = settings.holiday_calendar {
+            if !holidays.has_calendar(calendar) {
+                return Err(anyhow!("unknown holiday calendar `{calendar}`")
+                    .context("failed to validate settings"));
+            }
+        }
+        match (settings.min_yes_votes, settings.max_capacity) {
+            (Some(0), _) | (_, Some(0)) => {
+                return Err(anyhow!("quorum and capacity must be positive")
+                    .context("failed to validate settings"));
+            }
+            (Some(min_yes_votes), Some(max_capacity)) if max_capacity < min_yes_votes => {
+                return Err(anyhow!(
+                    "capacity of {max_capacity} is lower than quorum of {min_yes_votes}"
+                )
+                .context("failed to validate settings"));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn validate_name_and_description(
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<(String, Option<String>)> {
+        let name = Self::validate_name(name)?;
+        let description = description.map(Self::validate_description).transpose()?;
+
+        Ok((name, description))
+    }
+
+    fn validate_name(name: &str) -> Result<String> {
+        validate_text("meeting name", name, validation::NAME)
+    }
+
+    fn validate_description(description: &str) -> Result<String> {
+        validate_text("description", description, validation::DESCRIPTION)
+    }
+}
+
+/// Partial update of meeting's name and description.
+#[derive(Debug, Clone)]
+pub(crate) struct MeetingDetailsUpdate {
+    /// New name, or `None` to keep the current one
+    pub(crate) name: Option<String>,
+    /// New description, or `None` to keep the current one. `Some(None)`
+    /// clears the description.
+    pub(crate) description: Option<Option<String>>,
+}
+
+impl MeetingDetailsUpdate {
+    pub(crate) fn new(name: Option<String>, description: Option<Option<String>>) -> Result<Self> {
+        if name.is_none() && description.is_none() {
+            bail!("update changes neither name nor description");
+        }
+        let name = name.as_deref().map(Meeting::validate_name).transpose()?;
+        let description = description
+            .map(|description| {
+                description
+                    .as_deref()
+                    .map(Meeting::validate_description)
+                    .transpose()
+            })
+            .transpose()?;
+
+        Ok(Self { name, description })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -75,9 +286,7 @@ pub(crate) struct MeetingComment {
 
 impl MeetingComment {
     pub(crate) fn new(user_id: Uuid, meeting_id: Uuid, message: String) -> Result<Self> {
-        if message.is_empty() {
-            return Err(anyhow!("comment message is empty").context("failed to validate comment"));
-        }
+        let message = Self::validate_message(&message)?;
 
         let id = Uuid::new_v4();
         let posted_at = OffsetDateTime::now_utc();
@@ -90,4 +299,385 @@ impl MeetingComment {
             posted_at,
         })
     }
+
+    fn validate_message(message: &str) -> Result<String> {
+        validate_text("comment message", message, validation::COMMENT)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProposedDate {
+    pub(crate) id: Uuid,
+    pub(crate) meeting_id: Uuid,
+    pub(crate) option: DateOption,
+    /// Participant that proposed the date. `None` for dates proposed
+    /// before proposals were tracked.
+    pub(crate) proposed_by: Option<Uuid>,
+    /// Participants can vote only on approved dates
+    pub(crate) approved: bool,
+}
+
+impl ProposedDate {
+    /// Longest allowed time slot.
+    const MAX_DURATION_MINUTES: u16 = 24 * 60;
+
+    pub(crate) fn new(
+        meeting_id: Uuid,
+        option: DateOption,
+        proposed_by: Uuid,
+        approved: bool,
+    ) -> Result<Self> {
+        Self::with_id(
+            Uuid::new_v4(),
+            meeting_id,
+            option,
+            Some(proposed_by),
+            approved,
+        )
+    }
+
+    fn with_id(
+        id: Uuid,
+        meeting_id: Uuid,
+        option: DateOption,
+        proposed_by: Option<Uuid>,
+        approved: bool,
+    ) -> Result<Self> {
+        let option = Self::validate_option(option)?;
+
+        Ok(Self {
+            id,
+            meeting_id,
+            option,
+            proposed_by,
+            approved,
+        })
+    }
+
+    /// Checks if meeting with `settings`, taking place in timezone `tz`,
+    /// rejects this date because it falls on a holiday or weekend.
+    pub(crate) fn is_rejected(
+        &self,
+        settings: &MeetingSettings,
+        tz: &Tz,
+        holidays: &HolidayCalendars,
+    ) -> bool {
+        settings.holiday_policy == HolidayPolicy::Reject
+            && holidays.marks(settings, self.option, tz).is_marked()
+    }
+
+    /// Validates time slot duration and normalizes its start to UTC,
+    /// so that the same instant is always represented the same way.
+    fn validate_option(option: DateOption) -> Result<DateOption> {
+        match option {
+            DateOption::AllDay { .. } => Ok(option),
+            DateOption::TimeSlot {
+                starts_at,
+                duration_minutes,
+            } => {
+                if duration_minutes == 0 {
+                    return Err(anyhow!("time slot duration is zero")
+                        .context("failed to validate proposed date"));
+                }
+                if duration_minutes > Self::MAX_DURATION_MINUTES {
+                    return Err(anyhow!(
+                        "time slot is longer than {} minutes",
+                        Self::MAX_DURATION_MINUTES
+                    )
+                    .context("failed to validate proposed date"));
+                }
+
+                Ok(DateOption::TimeSlot {
+                    starts_at: starts_at.to_offset(UtcOffset::UTC),
+                    duration_minutes,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ParticipantAvailability {
+    pub(crate) meeting_id: Uuid,
+    pub(crate) user_id: Uuid,
+    pub(crate) slots: AvailabilitySlots,
+}
+
+impl ParticipantAvailability {
+    pub(crate) fn new(
+        meeting_id: Uuid,
+        user_id: Uuid,
+        slots: &str,
+        grid: &AvailabilityGrid,
+    ) -> Result<Self> {
+        Ok(Self {
+            meeting_id,
+            user_id,
+            slots: AvailabilitySlots::parse(slots, grid)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ParticipantVote {
+    pub(crate) proposed_date_id: Uuid,
+    pub(crate) user_id: Uuid,
+    pub(crate) vote: Vote,
+    pub(crate) comment: Option<String>,
+}
+
+impl ParticipantVote {
+    pub(crate) fn new(
+        proposed_date_id: Uuid,
+        user_id: Uuid,
+        vote: Vote,
+        comment: Option<String>,
+    ) -> Result<Self> {
+        let comment = Self::validate_comment(comment.as_deref())?;
+
+        Ok(Self {
+            proposed_date_id,
+            user_id,
+            vote,
+            comment,
+        })
+    }
+
+    fn validate_comment(comment: Option<&str>) -> Result<Option<String>> {
+        comment
+            .map(|comment| validate_text("vote comment", comment, validation::VOTE_COMMENT))
+            .transpose()
+    }
+}
+
+/// Meeting recreated from [`MeetingExport`] document, with original ids
+/// and freshly generated secret tokens for every participant.
+#[derive(Debug, Clone)]
+pub(crate) struct MeetingImport {
+    pub(crate) meeting: Meeting,
+    pub(crate) participants: Vec<User>,
+    pub(crate) proposed_dates: Vec<ProposedDate>,
+    pub(crate) votes: Vec<ParticipantVote>,
+    pub(crate) comments: Vec<MeetingComment>,
+    pub(crate) availability: Vec<ParticipantAvailability>,
+}
+
+impl MeetingImport {
+    pub(crate) fn new(export: MeetingExport, holidays: &HolidayCalendars) -> Result<Self> {
+        let MeetingExport {
+            format_version,
+            meeting,
+            participants,
+            proposed_dates,
+            votes,
+            comments,
+            availability,
+        } = export;
+
+        if format_version != EXPORT_FORMAT_VERSION {
+            bail!("unsupported export format version {format_version}");
+        }
+
+        let (name, description) =
+            Meeting::validate_name_and_description(&meeting.name, meeting.description.as_deref())?;
+        if meeting.expires_at < meeting.created_at {
+            bail!("meeting expires before it was created");
+        }
+        if meeting
+            .voting_closed_at
+            .is_some_and(|closed_at| closed_at < meeting.created_at)
+        {
+            bail!("voting was closed before meeting was created");
+        }
+        Meeting::validate_voting_closes_at(
+            meeting.voting_closes_at,
+            meeting.created_at,
+            meeting.expires_at,
+        )?;
+        let timezone = validate_timezone(&meeting.timezone)?;
+        Meeting::validate_settings(&meeting.settings, holidays)?;
+        let availability_grid = meeting
+            .availability_grid
+            .map(|grid| {
+                AvailabilityGrid::new(
+                    grid.first_day,
+                    grid.last_day,
+                    grid.day_starts_at,
+                    grid.day_ends_at,
+                    grid.slot_minutes,
+                )
+            })
+            .transpose()?;
+        let meeting = Meeting {
+            id: meeting.id,
+            name,
+            description,
+            created_at: meeting.created_at,
+            expires_at: meeting.expires_at,
+            user_id: meeting.created_by,
+            timezone,
+            availability_grid,
+            settings: meeting.settings,
+            voting_closes_at: meeting.voting_closes_at,
+            voting_closed_at: meeting.voting_closed_at,
+            final_date_id: meeting.final_date_id,
+        };
+
+        let mut participant_ids = HashSet::with_capacity(participants.len());
+        let participants = participants
+            .into_iter()
+            .map(|participant| {
+                if !participant_ids.insert(participant.id) {
+                    bail!("participant `{}` is listed more than once", participant.id);
+                }
+                User::with_id(participant.id, participant.name, participant.timezone)
+                    .map(|user| User {
+                        required: participant.required,
+                        // Exports made before join times were recorded
+                        joined_at: participant.joined_at.unwrap_or(meeting.created_at),
+                        ..user
+                    })
+                    .with_context(|| format!("invalid participant `{}`", participant.id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !participant_ids.contains(&meeting.user_id) {
+            bail!("meeting creator `{}` is not a participant", meeting.user_id);
+        }
+        if meeting.settings.unique_participant_names {
+            let mut names = HashSet::with_capacity(participants.len());
+            for participant in &participants {
+                if !names.insert(validation::display_name_key(&participant.name)) {
+                    bail!(
+                        "participant name `{}` is used more than once",
+                        participant.name
+                    );
+                }
+            }
+        }
+
+        let mut date_ids = HashSet::with_capacity(proposed_dates.len());
+        let mut options = HashSet::with_capacity(proposed_dates.len());
+        let proposed_dates = proposed_dates
+            .into_iter()
+            .map(|proposed_date| {
+                if !date_ids.insert(proposed_date.id) {
+                    bail!(
+                        "proposed date `{}` is listed more than once",
+                        proposed_date.id
+                    );
+                }
+                if let Some(proposed_by) = proposed_date.proposed_by {
+                    if !participant_ids.contains(&proposed_by) {
+                        bail!("date proposed by unknown participant `{proposed_by}`");
+                    }
+                }
+                let proposed_date = ProposedDate::with_id(
+                    proposed_date.id,
+                    meeting.id,
+                    proposed_date.option,
+                    proposed_date.proposed_by,
+                    proposed_date.approved,
+                )?;
+                if !options.insert(proposed_date.option) {
+                    bail!("{:?} is proposed more than once", proposed_date.option);
+                }
+                Ok(proposed_date)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(final_date_id) = meeting.final_date_id {
+            if !proposed_dates
+                .iter()
+                .any(|date| date.id == final_date_id && date.approved)
+            {
+                bail!("final date `{final_date_id}` is not an approved proposed date");
+            }
+        }
+
+        let mut cast_votes = HashSet::with_capacity(votes.len());
+        let votes = votes
+            .into_iter()
+            .map(|vote| {
+                if !participant_ids.contains(&vote.participant_id) {
+                    bail!("vote of unknown participant `{}`", vote.participant_id);
+                }
+                if !date_ids.contains(&vote.date_id) {
+                    bail!("vote on unknown proposed date `{}`", vote.date_id);
+                }
+                let comment = ParticipantVote::validate_comment(vote.comment.as_deref())?;
+                if !cast_votes.insert((vote.participant_id, vote.date_id)) {
+                    bail!(
+                        "participant `{}` voted more than once on date `{}`",
+                        vote.participant_id,
+                        vote.date_id
+                    );
+                }
+                Ok(ParticipantVote {
+                    proposed_date_id: vote.date_id,
+                    user_id: vote.participant_id,
+                    vote: vote.vote,
+                    comment,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut comment_ids = HashSet::with_capacity(comments.len());
+        let comments = comments
+            .into_iter()
+            .map(|comment| {
+                if !comment_ids.insert(comment.id) {
+                    bail!("comment `{}` is listed more than once", comment.id);
+                }
+                if !participant_ids.contains(&comment.written_by) {
+                    bail!(
+                        "comment written by unknown participant `{}`",
+                        comment.written_by
+                    );
+                }
+                let message = MeetingComment::validate_message(&comment.message)?;
+                Ok(MeetingComment {
+                    id: comment.id,
+                    user_id: comment.written_by,
+                    meeting_id: meeting.id,
+                    message,
+                    posted_at: comment.posted_at,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut available_participants = HashSet::with_capacity(availability.len());
+        let availability = availability
+            .into_iter()
+            .map(|availability| {
+                let Some(ref grid) = meeting.availability_grid else {
+                    bail!("availability submitted to meeting without availability grid");
+                };
+                if !participant_ids.contains(&availability.participant_id) {
+                    bail!(
+                        "availability of unknown participant `{}`",
+                        availability.participant_id
+                    );
+                }
+                if !available_participants.insert(availability.participant_id) {
+                    bail!(
+                        "availability of participant `{}` is listed more than once",
+                        availability.participant_id
+                    );
+                }
+                Ok(ParticipantAvailability {
+                    meeting_id: meeting.id,
+                    user_id: availability.participant_id,
+                    slots: AvailabilitySlots::parse(&availability.slots, grid)?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            meeting,
+            participants,
+            proposed_dates,
+            votes,
+            comments,
+            availability,
+        })
+    }
 }
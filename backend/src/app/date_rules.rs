@@ -0,0 +1,381 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context, Result};
+use time::{Date, Duration, PrimitiveDateTime, Time, Weekday};
+use time_tz::Tz;
+
+use super::business_logic::assume_timezone;
+use crate::api::common::DateOption;
+use crate::api::input::DateRuleData;
+
+/// Rule describing a set of dates to propose at once, e.g. "every weekday
+/// for the next two weeks".
+///
+/// Rule matches every day from `first_day` to `last_day` (inclusive) that
+/// falls on one of `weekdays` (every day, if empty), is generated by
+/// `recurrence` (if set) and is not one of `excluded_dates`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DateRule {
+    pub(crate) first_day: Date,
+    pub(crate) last_day: Date,
+    pub(crate) weekdays: Vec<Weekday>,
+    pub(crate) excluded_dates: Vec<Date>,
+    pub(crate) recurrence: Option<Recurrence>,
+    /// If set, time slot at given wall-clock time is proposed on every
+    /// matching day instead of the whole day.
+    pub(crate) time_slot: Option<DailyTimeSlot>,
+}
+
+/// Time slot starting at the same wall-clock time every day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DailyTimeSlot {
+    pub(crate) starts_at: Time,
+    pub(crate) duration_minutes: u16,
+}
+
+impl DateRule {
+    pub(crate) fn new(data: DateRuleData) -> Result<Self> {
+        let DateRuleData {
+            first_day,
+            last_day,
+            weekdays,
+            excluded_dates,
+            recurrence,
+            time_slot,
+        } = data;
+
+        Ok(Self {
+            first_day,
+            last_day,
+            weekdays: weekdays.into_iter().map(Into::into).collect(),
+            excluded_dates,
+            recurrence: recurrence.as_deref().map(str::parse).transpose()?,
+            time_slot: time_slot.map(|slot| DailyTimeSlot {
+                starts_at: slot.starts_at,
+                duration_minutes: slot.duration_minutes,
+            }),
+        })
+    }
+
+    /// Returns matching days in chronological order. Fails if rule matches
+    /// more than `max_dates` days.
+    pub(crate) fn expand(&self, max_dates: usize) -> Result<Vec<Date>> {
+        if self.last_day < self.first_day {
+            return Err(anyhow!("last day is before first day").context("failed to expand rule"));
+        }
+
+        let excluded_dates = self.excluded_dates.iter().collect::<HashSet<_>>();
+        let mut occurrences = 0;
+        let mut dates = Vec::new();
+        let mut day = self.first_day;
+        while day <= self.last_day {
+            let recurs = match self.recurrence {
+                Some(ref recurrence) => {
+                    if recurrence.count.is_some_and(|count| occurrences >= count) {
+                        break;
+                    }
+                    recurrence.matches(self.first_day, day)
+                }
+                None => true,
+            };
+            if recurs {
+                occurrences += 1;
+                if (self.weekdays.is_empty() || self.weekdays.contains(&day.weekday()))
+                    && !excluded_dates.contains(&day)
+                {
+                    if dates.len() == max_dates {
+                        return Err(anyhow!("rule matches more than {max_dates} dates")
+                            .context("failed to expand rule"));
+                    }
+                    dates.push(day);
+                }
+            }
+
+            day = match day.next_day() {
+                Some(day) => day,
+                None => break,
+            };
+        }
+
+        Ok(dates)
+    }
+
+    /// Expands rule into options ready to be proposed. Time slots are laid
+    /// out in timezone `tz`.
+    pub(crate) fn expand_options(&self, max_dates: usize, tz: &Tz) -> Result<Vec<DateOption>> {
+        let dates = self.expand(max_dates)?;
+        let options = dates
+            .into_iter()
+            .map(|date| match self.time_slot {
+                None => DateOption::AllDay { date },
+                Some(slot) => DateOption::TimeSlot {
+                    starts_at: assume_timezone(PrimitiveDateTime::new(date, slot.starts_at), tz),
+                    duration_minutes: slot.duration_minutes,
+                },
+            })
+            .collect();
+
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Subset of iCalendar RRULE (RFC 5545) recurrences, e.g.
+/// `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=6`.
+///
+/// Supported parts are `FREQ` (`DAILY`, `WEEKLY` or `MONTHLY`), `INTERVAL`,
+/// `COUNT`, `BYDAY` (weekly only) and `BYMONTHDAY` (monthly only). Weeks
+/// start on Monday and recurrence starts at the first day of the rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Recurrence {
+    pub(crate) frequency: Frequency,
+    pub(crate) interval: u32,
+    pub(crate) count: Option<usize>,
+    pub(crate) by_day: Vec<Weekday>,
+    pub(crate) by_month_day: Vec<u8>,
+}
+
+impl Recurrence {
+    /// Checks if recurrence starting at `start` generates `day`.
+    fn matches(&self, start: Date, day: Date) -> bool {
+        let interval = i64::from(self.interval);
+        match self.frequency {
+            Frequency::Daily => (day - start).whole_days() % interval == 0,
+            Frequency::Weekly => {
+                let week_start =
+                    start - Duration::days(start.weekday().number_days_from_monday().into());
+                let week = (day - week_start).whole_days() / 7;
+                let weekday_matches = if self.by_day.is_empty() {
+                    day.weekday() == start.weekday()
+                } else {
+                    self.by_day.contains(&day.weekday())
+                };
+                week % interval == 0 && weekday_matches
+            }
+            Frequency::Monthly => {
+                let month_index =
+                    |date: Date| i64::from(date.year()) * 12 + i64::from(date.month() as u8);
+                let months = month_index(day) - month_index(start);
+                let day_matches = if self.by_month_day.is_empty() {
+                    day.day() == start.day()
+                } else {
+                    self.by_month_day.contains(&day.day())
+                };
+                months % interval == 0 && day_matches
+            }
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = || {
+            let mut frequency = None;
+            let mut interval = 1;
+            let mut count = None;
+            let mut by_day = Vec::new();
+            let mut by_month_day = Vec::new();
+
+            for part in s.trim().trim_start_matches("RRULE:").split(';') {
+                let (name, value) = part
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("invalid part `{part}`"))?;
+                match name {
+                    "FREQ" => {
+                        frequency = Some(match value {
+                            "DAILY" => Frequency::Daily,
+                            "WEEKLY" => Frequency::Weekly,
+                            "MONTHLY" => Frequency::Monthly,
+                            _ => bail!("unsupported frequency `{value}`"),
+                        })
+                    }
+                    "INTERVAL" => {
+                        interval = value
+                            .parse()
+                            .with_context(|| format!("invalid interval `{value}`"))?;
+                        if interval == 0 {
+                            bail!("interval is zero");
+                        }
+                    }
+                    "COUNT" => {
+                        count = Some(
+                            value
+                                .parse()
+                                .with_context(|| format!("invalid count `{value}`"))?,
+                        )
+                    }
+                    "BYDAY" => {
+                        by_day = value.split(',').map(parse_weekday).collect::<Result<_>>()?;
+                    }
+                    "BYMONTHDAY" => {
+                        by_month_day = value
+                            .split(',')
+                            .map(|day| match day.parse() {
+                                Ok(day @ 1..=31) => Ok(day),
+                                _ => Err(anyhow!("invalid day of month `{day}`")),
+                            })
+                            .collect::<Result<_>>()?;
+                    }
+                    _ => bail!("unsupported part `{name}`"),
+                }
+            }
+
+            let frequency = frequency.ok_or_else(|| anyhow!("missing FREQ"))?;
+            if !by_day.is_empty() && frequency != Frequency::Weekly {
+                bail!("BYDAY is supported only with weekly frequency");
+            }
+            if !by_month_day.is_empty() && frequency != Frequency::Monthly {
+                bail!("BYMONTHDAY is supported only with monthly frequency");
+            }
+
+            Ok(Self {
+                frequency,
+                interval,
+                count,
+                by_day,
+                by_month_day,
+            })
+        };
+
+        parse().with_context(|| format!("failed to parse recurrence `{s}`"))
+    }
+}
+
+fn parse_weekday(day: &str) -> Result<Weekday> {
+    match day {
+        "MO" => Ok(Weekday::Monday),
+        "TU" => Ok(Weekday::Tuesday),
+        "WE" => Ok(Weekday::Wednesday),
+        "TH" => Ok(Weekday::Thursday),
+        "FR" => Ok(Weekday::Friday),
+        "SA" => Ok(Weekday::Saturday),
+        "SU" => Ok(Weekday::Sunday),
+        _ => Err(anyhow!("invalid weekday `{day}`")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime, time};
+    use time_tz::timezones;
+
+    fn rule(first_day: Date, last_day: Date) -> DateRule {
+        DateRule {
+            first_day,
+            last_day,
+            weekdays: Vec::new(),
+            excluded_dates: Vec::new(),
+            recurrence: None,
+            time_slot: None,
+        }
+    }
+
+    #[test]
+    fn weekdays_with_exclusions() {
+        #[rustfmt::skip]
+        let rule = DateRule {
+            weekdays: vec![Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Thursday, Weekday::Friday],
+            excluded_dates: vec![date!(2023-08-15)],
+            ..rule(date!(2023-08-11), date!(2023-08-17))
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            rule.expand(100).unwrap(),
+            vec![date!(2023-08-11), date!(2023-08-14), date!(2023-08-16), date!(2023-08-17)]
+        );
+    }
+
+    #[test]
+    fn too_many_dates() {
+        #[rustfmt::skip]
+        let rule = rule(date!(2023-08-01), date!(2023-08-31));
+
+        assert_eq!(rule.expand(31).unwrap().len(), 31);
+        assert!(rule.expand(30).is_err());
+    }
+
+    #[test]
+    fn invalid_range() {
+        #[rustfmt::skip]
+        let rule = rule(date!(2023-08-02), date!(2023-08-01));
+
+        assert!(rule.expand(100).is_err());
+    }
+
+    #[test]
+    fn weekly_recurrence() {
+        #[rustfmt::skip]
+        let rule = DateRule {
+            recurrence: Some("FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=3".parse().unwrap()),
+            ..rule(date!(2023-07-31), date!(2023-09-30))
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            rule.expand(100).unwrap(),
+            vec![date!(2023-07-31), date!(2023-08-02), date!(2023-08-14)]
+        );
+    }
+
+    #[test]
+    fn monthly_recurrence() {
+        #[rustfmt::skip]
+        let rule = DateRule {
+            recurrence: Some("RRULE:FREQ=MONTHLY;BYMONTHDAY=1,15".parse().unwrap()),
+            ..rule(date!(2023-07-10), date!(2023-09-10))
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            rule.expand(100).unwrap(),
+            vec![date!(2023-07-15), date!(2023-08-01), date!(2023-08-15), date!(2023-09-01)]
+        );
+    }
+
+    #[test]
+    fn invalid_recurrence() {
+        let invalid = [
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=WEEKLY;BYDAY=XX",
+        ];
+
+        for recurrence in invalid {
+            assert!(recurrence.parse::<Recurrence>().is_err(), "{recurrence}");
+        }
+    }
+
+    #[test]
+    fn time_slots_in_timezone() {
+        #[rustfmt::skip]
+        let rule = DateRule {
+            time_slot: Some(DailyTimeSlot { starts_at: time!(09:00), duration_minutes: 60 }),
+            ..rule(date!(2023-10-28), date!(2023-10-29))
+        };
+        let warsaw = timezones::get_by_name("Europe/Warsaw").unwrap();
+
+        #[rustfmt::skip]
+        assert_eq!(
+            rule.expand_options(100, warsaw).unwrap(),
+            vec![
+                DateOption::TimeSlot { starts_at: datetime!(2023-10-28 07:00 UTC), duration_minutes: 60 },
+                DateOption::TimeSlot { starts_at: datetime!(2023-10-29 08:00 UTC), duration_minutes: 60 },
+            ]
+        );
+    }
+}
@@ -1,68 +1,131 @@
 use anyhow::{anyhow, Context};
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
+use subtle::ConstantTimeEq;
+use time_tz::{timezones, Tz};
 use tracing::info;
 use uuid::Uuid;
 
-use super::{business_logic, AppState};
-use crate::api::input::{CreateMeetingData, JoinMeetingData, PostCommentData};
-use crate::api::output::{CreatedMeeting, JoinMeetingResponse, Meeting};
+use super::validation::{Validate, ValidationErrors};
+use super::{availability, business_logic, date_rules, results, AppState};
+use crate::api::common::{version_entity_tag, MeetingMode};
+use crate::api::export::MeetingExport;
+use crate::api::input::{
+    AccountCredentials, AccountToken, AddProposedDateData, CastVoteData, CreateAccountData,
+    CreateMeetingData, FinalizeMeetingData, GenerateProposedDatesData, GetMeetingQuery, IfMatch,
+    ImportMeetingData, JoinMeetingData, LinkAccountData, PostCommentData, SetAvailabilityData,
+    SetParticipantRequiredData, UpdateMeetingData, UserCredentials,
+};
+use crate::api::output::{
+    AccountMeetings, AddedProposedDate, Availability, CreatedAccount, CreatedMeeting,
+    FinalizedMeeting, GeneratedProposedDates, ImportedMeeting, ImportedParticipant, InvalidFields,
+    JoinMeetingResponse, Meeting, MeetingHistory, MeetingInfo, Results,
+};
 use crate::app::middleware;
-use crate::database;
+use crate::database::{self, models};
 
 #[axum_macros::debug_handler]
 #[tracing::instrument(skip(app_state))]
 pub(crate) async fn get_meeting_by_id(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<Meeting>, StatusCode> {
-    info!(meeting_id=?id, "Getting meeting info");
+    Query(query): Query<GetMeetingQuery>,
+) -> Result<(HeaderMap, Json<Meeting>), StatusCode> {
+    info!(meeting_id=?id, viewer=?query.user_id, "Getting meeting info");
 
-    let meeting_info = database::get_meeting_info(id, &app_state.database_pool)
+    let models::MeetingSnapshot {
+        info: meeting_info,
+        comments: meeting_comments,
+        participants_dates_votes,
+    } = app_state
+        .storage
+        .get_meeting_snapshot(id)
         .await
         .map_err(internal_error)?
         .ok_or_else(|| anyhow!("No meeting with provided id"))
         .map_err(not_found_error)?;
 
-    let meeting_comments = database::get_meeting_comments(id, &app_state.database_pool)
-        .await
-        .map_err(internal_error)?;
+    let authenticated_viewer = authenticate_viewer(&query, &app_state).await?;
 
-    let participants_proposed_dates_votes =
-        database::get_meeting_participants_proposed_dates_votes(id, &app_state.database_pool)
-            .await
-            .map_err(internal_error)?;
+    let mut meeting = Meeting::new(
+        meeting_info,
+        meeting_comments,
+        participants_dates_votes,
+        query.user_id,
+        &app_state.holidays,
+    )
+    .map_err(internal_error)?;
+    if meeting.meeting_info.is_voting_closed() {
+        show_auto_final_date(&mut meeting);
+    }
+    // Even the creator does not learn who cast anonymous votes
+    if meeting.meeting_info.settings.anonymous_votes {
+        meeting.anonymize_votes(authenticated_viewer);
+    }
+    if meeting.meeting_info.settings.hide_results
+        && !meeting.meeting_info.is_voting_closed()
+        && authenticated_viewer != Some(meeting.meeting_info.created_by)
+    {
+        meeting.hide_votes(authenticated_viewer);
+    }
 
-    Ok(Json(
-        Meeting::new(
-            meeting_info,
-            meeting_comments,
-            participants_proposed_dates_votes,
-        )
-        .map_err(internal_error)?,
-    ))
+    let mut headers = HeaderMap::new();
+    let etag = HeaderValue::from_str(&version_entity_tag(meeting.meeting_info.version))
+        .context("entity tag is not a valid header value")
+        .map_err(internal_error)?;
+    headers.insert(header::ETAG, etag);
+    Ok((headers, Json(meeting)))
 }
 
 #[axum_macros::debug_handler]
 #[tracing::instrument(skip(app_state))]
 pub(crate) async fn create_meeting(
     State(app_state): State<AppState>,
-    Json(data): Json<CreateMeetingData>,
-) -> Result<(StatusCode, Json<CreatedMeeting>), StatusCode> {
+    Json(mut data): Json<CreateMeetingData>,
+) -> Result<(StatusCode, Json<CreatedMeeting>), InputError> {
     info!(meeting_data=?data, "Creating new meeting");
 
-    let user = business_logic::User::new(data.user_name)
+    data.validate()?;
+
+    let mut user = business_logic::User::new(data.user_name, data.user_timezone)
         .context("failed to create user")
         .map_err(bad_request)?;
-    let meeting =
-        business_logic::Meeting::new(data.meeting_name, data.meeting_description, user.id)
-            .context("failed to create meeting")
-            .map_err(bad_request)?;
+    if let Some(ref account) = data.account {
+        authenticate_account(account, &app_state).await?;
+        user.account_id = Some(account.account_id);
+    }
+    let availability_grid = data
+        .availability_grid
+        .map(|grid| {
+            availability::AvailabilityGrid::new(
+                grid.first_day,
+                grid.last_day,
+                grid.day_starts_at,
+                grid.day_ends_at,
+                grid.slot_minutes,
+            )
+        })
+        .transpose()
+        .map_err(bad_request)?;
+    let meeting = business_logic::Meeting::new(
+        data.meeting_name,
+        data.meeting_description,
+        data.meeting_timezone,
+        availability_grid,
+        data.settings,
+        user.id,
+        &app_state.holidays,
+    )
+    .and_then(|meeting| meeting.with_voting_closes_at(data.voting_closes_at))
+    .context("failed to create meeting")
+    .map_err(bad_request)?;
 
-    database::create_new_meeting(&user, &meeting, &app_state.database_pool)
+    app_state
+        .storage
+        .create_new_meeting(&user, &meeting)
         .await
         .context("failed to insert new meeting into database")
         .map_err(internal_error)?;
@@ -76,23 +139,70 @@ pub(crate) async fn create_meeting(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn update_meeting(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    IfMatch(version): IfMatch,
+    Json(mut data): Json<UpdateMeetingData>,
+) -> Result<StatusCode, InputError> {
+    info!(?meeting_id, update_data=?data, "Updating meeting details");
+
+    data.validate()?;
+
+    let UpdateMeetingData {
+        user_id,
+        user_token,
+        name,
+        description,
+    } = data;
+
+    let credentials = UserCredentials {
+        user_id,
+        user_token,
+    };
+    authenticate_meeting_creator(meeting_id, &credentials, &app_state).await?;
+    let update =
+        business_logic::MeetingDetailsUpdate::new(name, description).map_err(bad_request)?;
+    app_state
+        .storage
+        .update_meeting_details(meeting_id, user_id, &update, version)
+        .await
+        .map_err(versioned_update_error)?;
+
+    info!(?update, "Meeting details were updated");
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[axum_macros::debug_handler]
 #[tracing::instrument(skip(app_state))]
 pub(crate) async fn join_meeting(
     State(app_state): State<AppState>,
     Path(meeting_id): Path<Uuid>,
-    Json(data): Json<JoinMeetingData>,
-) -> Result<(StatusCode, Json<JoinMeetingResponse>), StatusCode> {
+    Json(mut data): Json<JoinMeetingData>,
+) -> Result<(StatusCode, Json<JoinMeetingResponse>), InputError> {
     info!(?meeting_id, join_meeting_data=?data, "Creating new meeting participant");
 
-    let user = business_logic::User::new(data.name).map_err(bad_request)?;
+    data.validate()?;
+
+    let mut user = business_logic::User::new(data.name, data.timezone).map_err(bad_request)?;
+    if let Some(ref account) = data.account {
+        authenticate_account(account, &app_state).await?;
+        user.account_id = Some(account.account_id);
+    }
 
-    if let Err(error) = database::join_meeting(&user, meeting_id, &app_state.database_pool).await {
+    if let Err(error) = app_state.storage.join_meeting(&user, meeting_id).await {
         match error {
             database::JoinMeetingError::NonexistentMeeting(_) => {
-                return Err(bad_request(error.into()))
+                return Err(bad_request(error.into()).into())
+            }
+            database::JoinMeetingError::DuplicateName(_)
+            | database::JoinMeetingError::AlreadyJoined => {
+                info!(error = ?error, "Conflict");
+                return Err(StatusCode::CONFLICT.into());
             }
-            database::JoinMeetingError::Database(err) => return Err(internal_error(err)),
+            database::JoinMeetingError::Database(err) => return Err(internal_error(err).into()),
         }
     }
 
@@ -104,55 +214,949 @@ pub(crate) async fn join_meeting(
     Ok((StatusCode::CREATED, Json(response)))
 }
 
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn create_account(
+    State(app_state): State<AppState>,
+    Json(mut data): Json<CreateAccountData>,
+) -> Result<(StatusCode, Json<CreatedAccount>), InputError> {
+    info!(account_data=?data, "Creating new account");
+
+    data.validate()?;
+    let account = business_logic::Account::new(data.name).map_err(bad_request)?;
+    app_state
+        .storage
+        .create_account(&account)
+        .await
+        .map_err(internal_error)?;
+
+    let response = CreatedAccount {
+        id: account.id,
+        secret_token: account.secret_token,
+    };
+    info!(account_id=?account.id, "Created new account");
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn get_account_meetings(
+    State(app_state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Query(query): Query<AccountToken>,
+) -> Result<Json<AccountMeetings>, StatusCode> {
+    info!(?account_id, "Getting meetings of account");
+
+    let credentials = AccountCredentials {
+        account_id,
+        account_token: query.account_token,
+    };
+    authenticate_account(&credentials, &app_state).await?;
+    let meetings = app_state
+        .storage
+        .get_account_meetings(account_id)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(AccountMeetings {
+        meetings: meetings.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn link_account(
+    State(app_state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Json(data): Json<LinkAccountData>,
+) -> Result<StatusCode, StatusCode> {
+    info!(?account_id, meeting_id=?data.meeting_id, user_id=?data.user_id, "Linking participant to account");
+
+    let credentials = AccountCredentials {
+        account_id,
+        account_token: data.account_token,
+    };
+    authenticate_account(&credentials, &app_state).await?;
+    authenticate(data.user_id, data.user_token, &app_state).await?;
+    let is_participant = app_state
+        .storage
+        .is_meeting_participant(data.meeting_id, data.user_id)
+        .await
+        .map_err(internal_error)?;
+    if !is_participant {
+        info!(user_id=?data.user_id, "User is not a participant of this meeting");
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let linked = app_state
+        .storage
+        .link_account(account_id, data.meeting_id, data.user_id)
+        .await
+        .map_err(internal_error)?;
+    if !linked {
+        info!("Participant or account is already linked");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    info!("Participant was linked to account");
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[axum_macros::debug_handler]
 #[tracing::instrument(skip(app_state))]
 pub(crate) async fn post_comment(
     State(app_state): State<AppState>,
     Path(meeting_id): Path<Uuid>,
-    Json(data): Json<PostCommentData>,
-) -> Result<StatusCode, StatusCode> {
+    Json(mut data): Json<PostCommentData>,
+) -> Result<StatusCode, InputError> {
     info!(?meeting_id, comment_data=?data, "Posting new comment to meeting");
 
+    data.validate()?;
+
     let PostCommentData {
         user_id,
         user_token,
         message,
     } = data;
 
-    if !database::meeting_exists(meeting_id, &app_state.database_pool)
+    if !app_state
+        .storage
+        .meeting_exists(meeting_id)
         .await
         .map_err(internal_error)?
     {
         info!(?meeting_id, "Meeting with given id does not exist");
-        return Err(StatusCode::NOT_FOUND);
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+
+    authenticate(user_id, user_token, &app_state).await?;
+
+    let meeting_comment =
+        business_logic::MeetingComment::new(user_id, meeting_id, message).map_err(bad_request)?;
+    app_state
+        .storage
+        .post_comment(&meeting_comment)
+        .await
+        .map_err(internal_error)?;
+
+    info!(?meeting_comment, "Meeting comment was added to database");
+    Ok(StatusCode::CREATED)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn add_proposed_date(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<AddProposedDateData>,
+) -> Result<(StatusCode, Json<AddedProposedDate>), StatusCode> {
+    info!(?meeting_id, proposed_date_data=?data, "Adding proposed date to meeting");
+
+    let AddProposedDateData {
+        user_id,
+        user_token,
+        option,
+    } = data;
+
+    let meeting_info: MeetingInfo = app_state
+        .storage
+        .get_meeting_info(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?
+        .into();
+
+    authenticate(user_id, user_token, &app_state).await?;
+    let approved = if meeting_info.created_by == user_id {
+        true
+    } else {
+        if !meeting_info.settings.participants_can_propose {
+            info!(?user_id, "Only meeting creator can propose dates");
+            return Err(StatusCode::FORBIDDEN);
+        }
+        let is_participant = app_state
+            .storage
+            .is_meeting_participant(meeting_id, user_id)
+            .await
+            .map_err(internal_error)?;
+        if !is_participant {
+            info!(?user_id, "User is not a participant of this meeting");
+            return Err(StatusCode::FORBIDDEN);
+        }
+        !meeting_info.settings.proposals_require_approval
+    };
+    if meeting_info.mode == MeetingMode::Availability {
+        info!("Dates can not be proposed in availability meeting");
+        return Err(StatusCode::CONFLICT);
+    }
+    ensure_voting_open(&meeting_info)?;
+
+    let proposed_date = business_logic::ProposedDate::new(meeting_id, option, user_id, approved)
+        .map_err(bad_request)?;
+    let tz = meeting_timezone(&meeting_info.timezone)?;
+    if proposed_date.is_rejected(&meeting_info.settings, tz, &app_state.holidays) {
+        return Err(bad_request(anyhow!(
+            "meeting does not accept dates on holidays or weekends"
+        )));
+    }
+    if let Err(error) = app_state
+        .storage
+        .add_proposed_date(&proposed_date, version)
+        .await
+    {
+        match error {
+            database::AddProposedDateError::AlreadyProposed => {
+                info!(?error, "Conflict");
+                return Err(StatusCode::CONFLICT);
+            }
+            database::AddProposedDateError::StaleVersion => return Err(precondition_failed()),
+            database::AddProposedDateError::Database(err) => return Err(internal_error(err)),
+        }
     }
 
-    if let Err(error) =
-        middleware::validate_user_credentials(user_id, user_token, &app_state.database_pool).await
+    let response = AddedProposedDate {
+        id: proposed_date.id,
+    };
+    info!(?response, "Proposed date was added to database");
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn generate_proposed_dates(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<GenerateProposedDatesData>,
+) -> Result<(StatusCode, Json<GeneratedProposedDates>), StatusCode> {
+    info!(?meeting_id, rule_data=?data, "Generating proposed dates from rule");
+
+    let GenerateProposedDatesData {
+        user_id,
+        user_token,
+        rule,
+    } = data;
+
+    let meeting_info: MeetingInfo = app_state
+        .storage
+        .get_meeting_info(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?
+        .into();
+
+    authenticate(user_id, user_token, &app_state).await?;
+    if meeting_info.created_by != user_id {
+        info!(?user_id, "Only meeting creator can propose dates");
+        return Err(StatusCode::FORBIDDEN);
+    }
+    if meeting_info.mode == MeetingMode::Availability {
+        info!("Dates can not be proposed in availability meeting");
+        return Err(StatusCode::CONFLICT);
+    }
+    ensure_voting_open(&meeting_info)?;
+
+    let tz = meeting_timezone(&meeting_info.timezone)?;
+    let proposed_dates = date_rules::DateRule::new(rule)
+        .and_then(|rule| rule.expand_options(app_state.config.max_generated_dates, tz))
+        .and_then(|options| {
+            options
+                .into_iter()
+                .map(|option| business_logic::ProposedDate::new(meeting_id, option, user_id, true))
+                .filter(|date| {
+                    // Dates rejected by holiday settings are skipped, so that
+                    // rules like "every day" remain usable
+                    !date.as_ref().is_ok_and(|date| {
+                        date.is_rejected(&meeting_info.settings, tz, &app_state.holidays)
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .map_err(bad_request)?;
+    if proposed_dates.is_empty() {
+        return Err(bad_request(anyhow!("rule does not match any date")));
+    }
+
+    if let Err(error) = app_state
+        .storage
+        .add_proposed_dates(meeting_id, &proposed_dates, version)
+        .await
     {
         match error {
-            middleware::CredentialValidationError::NonexistentUser => {
-                info!(?user_id, "Unauthorized");
-                return Err(StatusCode::UNAUTHORIZED);
+            database::AddProposedDateError::AlreadyProposed => {
+                info!(?error, "Conflict");
+                return Err(StatusCode::CONFLICT);
+            }
+            database::AddProposedDateError::StaleVersion => return Err(precondition_failed()),
+            database::AddProposedDateError::Database(err) => return Err(internal_error(err)),
+        }
+    }
+
+    let response = GeneratedProposedDates::new(&proposed_dates, &meeting_info, &app_state.holidays)
+        .map_err(internal_error)?;
+    info!(count = proposed_dates.len(), "Generated proposed dates");
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn approve_proposed_date(
+    State(app_state): State<AppState>,
+    Path((meeting_id, date_id)): Path<(Uuid, Uuid)>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<UserCredentials>,
+) -> Result<StatusCode, StatusCode> {
+    info!(?meeting_id, ?date_id, user_id=?data.user_id, "Approving proposed date");
+
+    let meeting_info = authenticate_meeting_creator(meeting_id, &data, &app_state).await?;
+    ensure_voting_open(&meeting_info)?;
+    let approved = app_state
+        .storage
+        .approve_proposed_date(meeting_id, date_id, data.user_id, version)
+        .await
+        .map_err(versioned_update_error)?;
+    if !approved {
+        info!("No proposed date with provided id in this meeting");
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    info!("Proposed date was approved");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn reject_proposed_date(
+    State(app_state): State<AppState>,
+    Path((meeting_id, date_id)): Path<(Uuid, Uuid)>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<UserCredentials>,
+) -> Result<StatusCode, StatusCode> {
+    info!(?meeting_id, ?date_id, user_id=?data.user_id, "Rejecting proposed date");
+
+    let meeting_info = authenticate_meeting_creator(meeting_id, &data, &app_state).await?;
+    ensure_voting_open(&meeting_info)?;
+    let status = app_state
+        .storage
+        .get_proposed_date_status(meeting_id, date_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No proposed date with provided id in this meeting"))
+        .map_err(not_found_error)?;
+    if status.approved {
+        info!("Approved dates can not be rejected");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let deleted = app_state
+        .storage
+        .delete_pending_proposed_date(meeting_id, date_id, data.user_id, version)
+        .await
+        .map_err(versioned_update_error)?;
+    if !deleted {
+        info!("Proposed date was approved or deleted concurrently");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    info!("Proposed date was rejected");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn cast_vote(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    Json(mut data): Json<CastVoteData>,
+) -> Result<StatusCode, InputError> {
+    info!(?meeting_id, user_id=?data.user_id, date_id=?data.date_id, "Casting vote");
+
+    data.validate()?;
+
+    let CastVoteData {
+        user_id,
+        user_token,
+        date_id,
+        vote,
+        comment,
+    } = data;
+
+    let meeting_info: MeetingInfo = app_state
+        .storage
+        .get_meeting_info(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?
+        .into();
+
+    authenticate(user_id, user_token, &app_state).await?;
+    let is_participant = app_state
+        .storage
+        .is_meeting_participant(meeting_id, user_id)
+        .await
+        .map_err(internal_error)?;
+    if !is_participant {
+        info!(?user_id, "User is not a participant of this meeting");
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    if meeting_info.mode == MeetingMode::Availability {
+        info!("Votes can not be cast in availability meeting");
+        return Err(StatusCode::CONFLICT.into());
+    }
+    ensure_voting_open(&meeting_info)?;
+
+    let status = app_state
+        .storage
+        .get_proposed_date_status(meeting_id, date_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No proposed date with provided id in this meeting"))
+        .map_err(not_found_error)?;
+    if !status.approved {
+        info!("Proposed date awaits creator's approval");
+        return Err(StatusCode::CONFLICT.into());
+    }
+
+    let vote = business_logic::ParticipantVote::new(date_id, user_id, vote, comment)
+        .map_err(bad_request)?;
+    if let Err(error) = app_state.storage.cast_vote(meeting_id, &vote).await {
+        match error {
+            database::CastVoteError::VotingClosed => {
+                info!("Voting was closed concurrently");
+                return Err(StatusCode::CONFLICT.into());
             }
-            middleware::CredentialValidationError::InvalidSecretToken => {
-                info!(?user_id, "Forbidden");
+            database::CastVoteError::Database(err) => return Err(internal_error(err).into()),
+        }
+    }
+
+    info!("Vote was cast");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn get_results(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    Query(query): Query<GetMeetingQuery>,
+) -> Result<Json<Results>, StatusCode> {
+    info!(?meeting_id, viewer=?query.user_id, "Getting meeting results");
+
+    let meeting_info: MeetingInfo = app_state
+        .storage
+        .get_meeting_info(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?
+        .into();
+    if meeting_info.mode == MeetingMode::Availability {
+        info!("Availability meeting has no voting results");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let authenticated_viewer = authenticate_viewer(&query, &app_state).await?;
+    if meeting_info.settings.hide_results
+        && !meeting_info.is_voting_closed()
+        && authenticated_viewer != Some(meeting_info.created_by)
+    {
+        info!("Results are hidden until voting is closed");
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let meeting_votes = app_state
+        .storage
+        .get_meeting_votes(meeting_id)
+        .await
+        .map_err(internal_error)?;
+    let tallies = results::tally_meeting_votes(meeting_votes);
+
+    Ok(Json(Results::new(
+        tallies,
+        &(&meeting_info.settings).into(),
+        meeting_info.settings.anonymous_votes,
+    )))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn set_participant_required(
+    State(app_state): State<AppState>,
+    Path((meeting_id, participant_id)): Path<(Uuid, Uuid)>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<SetParticipantRequiredData>,
+) -> Result<StatusCode, StatusCode> {
+    info!(?meeting_id, ?participant_id, user_id=?data.user_id, required=data.required, "Setting whether participant is required");
+
+    let credentials = UserCredentials {
+        user_id: data.user_id,
+        user_token: data.user_token,
+    };
+    let meeting_info = authenticate_meeting_creator(meeting_id, &credentials, &app_state).await?;
+    ensure_voting_open(&meeting_info)?;
+    let updated = app_state
+        .storage
+        .set_participant_required(
+            meeting_id,
+            participant_id,
+            data.user_id,
+            data.required,
+            version,
+        )
+        .await
+        .map_err(versioned_update_error)?;
+    if !updated {
+        info!("No participant with provided id in this meeting");
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    info!("Participant was updated");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn close_voting(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<UserCredentials>,
+) -> Result<StatusCode, StatusCode> {
+    info!(?meeting_id, user_id=?data.user_id, "Closing voting");
+
+    let mut meeting_info = authenticate_meeting_creator(meeting_id, &data, &app_state).await?;
+    ensure_voting_open(&meeting_info)?;
+    let closed = app_state
+        .storage
+        .close_voting(meeting_id, data.user_id, version)
+        .await
+        .map_err(versioned_update_error)?;
+    if !closed {
+        info!("Voting is already closed");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    info!("Voting was closed");
+    auto_finalize(meeting_id, &mut meeting_info, &app_state).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn finalize_meeting(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    IfMatch(version): IfMatch,
+    Json(data): Json<FinalizeMeetingData>,
+) -> Result<Json<FinalizedMeeting>, StatusCode> {
+    info!(?meeting_id, user_id=?data.user_id, date_id=?data.date_id, "Finalizing meeting");
+
+    let credentials = UserCredentials {
+        user_id: data.user_id,
+        user_token: data.user_token,
+    };
+    let mut meeting_info =
+        authenticate_meeting_creator(meeting_id, &credentials, &app_state).await?;
+    if meeting_info.mode == MeetingMode::Availability {
+        info!("Availability meeting can not be finalized");
+        return Err(StatusCode::CONFLICT);
+    }
+    // Participants already see the date chosen by automatic finalization
+    // once the deadline has passed, so it takes precedence
+    if meeting_info.is_voting_closed() {
+        auto_finalize(meeting_id, &mut meeting_info, &app_state).await?;
+    }
+    if meeting_info.final_date_id.is_some() {
+        info!("Meeting is already finalized");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let meeting_votes = app_state
+        .storage
+        .get_meeting_votes(meeting_id)
+        .await
+        .map_err(internal_error)?;
+    let tallies = results::tally_meeting_votes(meeting_votes);
+    let tally = tallies
+        .iter()
+        .find(|tally| tally.date_id == data.date_id)
+        .ok_or_else(|| anyhow!("No approved proposed date with provided id"))
+        .map_err(not_found_error)?;
+    let warnings = tally.problems(&(&meeting_info.settings).into());
+    if !warnings.is_empty() && !data.force {
+        info!(?warnings, "Date is not viable");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let finalized = app_state
+        .storage
+        .finalize_meeting(meeting_id, data.date_id, Some(data.user_id), Some(version))
+        .await
+        .map_err(versioned_update_error)?;
+    if !finalized {
+        info!("Meeting is already finalized");
+        return Err(StatusCode::CONFLICT);
+    }
+
+    info!(?warnings, "Meeting was finalized");
+    Ok(Json(FinalizedMeeting {
+        final_date_id: data.date_id,
+        warnings,
+    }))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn get_meeting_history(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    Query(credentials): Query<UserCredentials>,
+) -> Result<Json<MeetingHistory>, StatusCode> {
+    info!(?meeting_id, user_id=?credentials.user_id, "Getting meeting history");
+
+    let meeting_info = authenticate_meeting_creator(meeting_id, &credentials, &app_state).await?;
+    let history = app_state
+        .storage
+        .get_meeting_history(meeting_id)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(MeetingHistory::new(
+        history,
+        meeting_info.settings.anonymous_votes,
+    )))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn get_availability(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+) -> Result<Json<Availability>, StatusCode> {
+    info!(?meeting_id, "Getting meeting availability");
+
+    let models::MeetingAvailability {
+        timezone,
+        grid,
+        participants,
+    } = app_state
+        .storage
+        .get_meeting_availability(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?;
+    let Some(grid) = grid else {
+        info!("Meeting has no availability grid");
+        return Err(StatusCode::CONFLICT);
+    };
+
+    Ok(Json(
+        Availability::new(timezone, grid, participants).map_err(internal_error)?,
+    ))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn set_availability(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    Json(data): Json<SetAvailabilityData>,
+) -> Result<StatusCode, StatusCode> {
+    info!(?meeting_id, availability_data=?data, "Setting participant availability");
+
+    let SetAvailabilityData {
+        user_id,
+        user_token,
+        slots,
+    } = data;
+
+    let meeting_info: MeetingInfo = app_state
+        .storage
+        .get_meeting_info(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?
+        .into();
+
+    authenticate(user_id, user_token, &app_state).await?;
+    ensure_voting_open(&meeting_info)?;
+
+    let Some(grid) = app_state
+        .storage
+        .get_availability_grid(meeting_id)
+        .await
+        .map_err(internal_error)?
+    else {
+        info!("Meeting has no availability grid");
+        return Err(StatusCode::CONFLICT);
+    };
+    let grid = availability::AvailabilityGrid::try_from(grid).map_err(internal_error)?;
+
+    let availability =
+        business_logic::ParticipantAvailability::new(meeting_id, user_id, &slots, &grid)
+            .map_err(bad_request)?;
+    if let Err(error) = app_state.storage.set_availability(&availability).await {
+        match error {
+            database::SetAvailabilityError::NotParticipant => {
+                info!(?error, "Forbidden");
                 return Err(StatusCode::FORBIDDEN);
             }
-            middleware::CredentialValidationError::DatabaseError(err) => {
-                return Err(internal_error(err));
+            database::SetAvailabilityError::Database(err) => return Err(internal_error(err)),
+        }
+    }
+
+    info!("Participant availability was saved");
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip(app_state))]
+pub(crate) async fn export_meeting(
+    State(app_state): State<AppState>,
+    Path(meeting_id): Path<Uuid>,
+    Query(credentials): Query<UserCredentials>,
+) -> Result<Json<MeetingExport>, StatusCode> {
+    info!(?meeting_id, user_id=?credentials.user_id, "Exporting meeting");
+
+    authenticate(credentials.user_id, credentials.user_token, &app_state).await?;
+
+    let export = app_state
+        .storage
+        .get_meeting_export(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?;
+
+    if export.meeting.created_by != credentials.user_id {
+        info!(user_id=?credentials.user_id, "Only meeting creator can export meeting");
+        return Err(StatusCode::FORBIDDEN);
+    }
+    // Exported votes name their voters, which not even the creator may learn
+    if export.meeting.settings.anonymous_votes {
+        info!("Meeting with anonymous votes can not be exported");
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    info!("Exported meeting");
+    Ok(Json(export.try_into().map_err(internal_error)?))
+}
+
+#[axum_macros::debug_handler]
+#[tracing::instrument(skip_all)]
+pub(crate) async fn import_meeting(
+    State(app_state): State<AppState>,
+    Json(data): Json<ImportMeetingData>,
+) -> Result<(StatusCode, Json<ImportedMeeting>), StatusCode> {
+    info!(meeting_id=?data.meeting.meeting.id, "Importing meeting");
+
+    match app_state.config.admin_token {
+        // Constant-time comparison does not reveal how much of a guess matched
+        Some(admin_token)
+            if bool::from(admin_token.as_bytes().ct_eq(data.admin_token.as_bytes())) => {}
+        Some(_) => {
+            info!("Invalid admin token");
+            return Err(StatusCode::FORBIDDEN);
+        }
+        None => {
+            info!("Administrative endpoints are disabled");
+            return Err(StatusCode::FORBIDDEN);
+        }
+    }
+
+    let import = business_logic::MeetingImport::new(data.meeting, &app_state.holidays)
+        .context("failed to validate imported meeting")
+        .map_err(bad_request)?;
+
+    if let Err(error) = app_state.storage.import_meeting(&import).await {
+        match error {
+            database::ImportMeetingError::Conflict(_) => {
+                info!(?error, "Conflict");
+                return Err(StatusCode::CONFLICT);
             }
+            database::ImportMeetingError::Database(err) => return Err(internal_error(err)),
         }
     }
 
-    let meeting_comment =
-        business_logic::MeetingComment::new(user_id, meeting_id, message).map_err(bad_request)?;
-    database::post_comment(&meeting_comment, &app_state.database_pool)
+    let response = ImportedMeeting {
+        meeting_id: import.meeting.id,
+        participants: import
+            .participants
+            .iter()
+            .map(|user| ImportedParticipant {
+                id: user.id,
+                secret_token: user.secret_token,
+            })
+            .collect(),
+    };
+    info!(meeting_id=?response.meeting_id, "Imported meeting");
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Looks up meeting's timezone in the bundled timezone database.
+fn meeting_timezone(timezone: &str) -> Result<&'static Tz, StatusCode> {
+    timezones::get_by_name(timezone)
+        .ok_or_else(|| anyhow!("unknown meeting timezone `{timezone}`"))
+        .map_err(internal_error)
+}
+
+/// Validates user credentials, mapping failures to response status codes.
+async fn authenticate(
+    user_id: Uuid,
+    user_token: Uuid,
+    app_state: &AppState,
+) -> Result<(), StatusCode> {
+    match middleware::validate_user_credentials(user_id, user_token, &*app_state.storage).await {
+        Ok(()) => Ok(()),
+        Err(middleware::CredentialValidationError::NonexistentUser) => {
+            info!(?user_id, "Unauthorized");
+            Err(StatusCode::UNAUTHORIZED)
+        }
+        Err(middleware::CredentialValidationError::InvalidSecretToken) => {
+            info!(?user_id, "Forbidden");
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(middleware::CredentialValidationError::DatabaseError(err)) => Err(internal_error(err)),
+    }
+}
+
+async fn authenticate_account(
+    credentials: &AccountCredentials,
+    app_state: &AppState,
+) -> Result<(), StatusCode> {
+    let account_id = credentials.account_id;
+    match middleware::validate_account_credentials(
+        account_id,
+        credentials.account_token,
+        &*app_state.storage,
+    )
+    .await
+    {
+        Ok(()) => Ok(()),
+        Err(middleware::CredentialValidationError::NonexistentUser) => {
+            info!(?account_id, "Unauthorized");
+            Err(StatusCode::UNAUTHORIZED)
+        }
+        Err(middleware::CredentialValidationError::InvalidSecretToken) => {
+            info!(?account_id, "Forbidden");
+            Err(StatusCode::FORBIDDEN)
+        }
+        Err(middleware::CredentialValidationError::DatabaseError(err)) => Err(internal_error(err)),
+    }
+}
+
+/// Authenticates viewer of the meeting if they provided their secret token.
+/// Viewer is only trusted with hidden votes if they proved their identity.
+async fn authenticate_viewer(
+    query: &GetMeetingQuery,
+    app_state: &AppState,
+) -> Result<Option<Uuid>, StatusCode> {
+    match (query.user_id, query.user_token) {
+        (Some(user_id), Some(user_token)) => {
+            authenticate(user_id, user_token, app_state).await?;
+            Ok(Some(user_id))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Authenticates user and checks that they created meeting with `meeting_id`.
+async fn authenticate_meeting_creator(
+    meeting_id: Uuid,
+    credentials: &UserCredentials,
+    app_state: &AppState,
+) -> Result<MeetingInfo, StatusCode> {
+    let meeting_info: MeetingInfo = app_state
+        .storage
+        .get_meeting_info(meeting_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| anyhow!("No meeting with provided id"))
+        .map_err(not_found_error)?
+        .into();
+
+    authenticate(credentials.user_id, credentials.user_token, app_state).await?;
+    if meeting_info.created_by != credentials.user_id {
+        info!(user_id=?credentials.user_id, "User is not the meeting creator");
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(meeting_info)
+}
+
+/// Sets final date of meeting with automatic finalization to its
+/// best-scoring date. Must only be called once voting is closed.
+async fn auto_finalize(
+    meeting_id: Uuid,
+    meeting_info: &mut MeetingInfo,
+    app_state: &AppState,
+) -> Result<(), StatusCode> {
+    if !meeting_info.settings.auto_finalize || meeting_info.final_date_id.is_some() {
+        return Ok(());
+    }
+
+    let meeting_votes = app_state
+        .storage
+        .get_meeting_votes(meeting_id)
         .await
         .map_err(internal_error)?;
+    let tallies = results::tally_meeting_votes(meeting_votes);
+    let quorum = (&meeting_info.settings).into();
+    let Some(best_date) = results::best_date(&tallies, &quorum) else {
+        info!("No date can be chosen as final date");
+        return Ok(());
+    };
 
-    info!(?meeting_comment, "Meeting comment was added to database");
-    Ok(StatusCode::CREATED)
+    let finalized = app_state
+        .storage
+        .finalize_meeting(meeting_id, best_date, None, None)
+        .await
+        .map_err(versioned_update_error)?;
+    if finalized {
+        info!(final_date_id=?best_date, "Meeting was finalized");
+        meeting_info.final_date_id = Some(best_date);
+        meeting_info.version += 1;
+    } else {
+        // Meeting was finalized concurrently, possibly by its creator
+        let current_info = app_state
+            .storage
+            .get_meeting_info(meeting_id)
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(|| anyhow!("No meeting with provided id"))
+            .map_err(not_found_error)?;
+        meeting_info.final_date_id = current_info.final_date_id;
+        meeting_info.version = current_info.version;
+    }
+    Ok(())
+}
+
+/// Shows the date automatic finalization would choose on a meeting whose
+/// voting is closed but which was not finalized yet. Nothing is stored, so
+/// reading the meeting does not change it or its version.
+fn show_auto_final_date(meeting: &mut Meeting) {
+    let meeting_info = &meeting.meeting_info;
+    if !meeting_info.settings.auto_finalize || meeting_info.final_date_id.is_some() {
+        return;
+    }
+    let tallies = results::tally_meeting(meeting);
+    let quorum = (&meeting.meeting_info.settings).into();
+    meeting.meeting_info.final_date_id = results::best_date(&tallies, &quorum);
+}
+
+/// Rejects changes of votes, availability and proposed dates once voting
+/// is closed.
+fn ensure_voting_open(meeting_info: &MeetingInfo) -> Result<(), StatusCode> {
+    if meeting_info.is_voting_closed() {
+        info!("Voting is closed");
+        return Err(StatusCode::CONFLICT);
+    }
+    Ok(())
 }
 
 fn internal_error(err: anyhow::Error) -> StatusCode {
@@ -165,7 +1169,480 @@ fn not_found_error(err: anyhow::Error) -> StatusCode {
     StatusCode::NOT_FOUND
 }
 
+fn precondition_failed() -> StatusCode {
+    info!("Meeting version is stale");
+    StatusCode::PRECONDITION_FAILED
+}
+
+fn versioned_update_error(err: database::VersionedUpdateError) -> StatusCode {
+    match err {
+        database::VersionedUpdateError::StaleVersion => precondition_failed(),
+        database::VersionedUpdateError::Database(err) => internal_error(err),
+    }
+}
+
 fn bad_request(err: anyhow::Error) -> StatusCode {
     info!(error = ?err, "Bad request");
     StatusCode::BAD_REQUEST
 }
+
+/// Error of handlers whose request body has validated text fields.
+#[derive(Debug)]
+pub(crate) enum InputError {
+    Status(StatusCode),
+    InvalidFields(ValidationErrors),
+}
+
+impl From<StatusCode> for InputError {
+    fn from(status: StatusCode) -> Self {
+        Self::Status(status)
+    }
+}
+
+impl From<ValidationErrors> for InputError {
+    fn from(errors: ValidationErrors) -> Self {
+        info!(%errors, "Invalid fields");
+        Self::InvalidFields(errors)
+    }
+}
+
+impl IntoResponse for InputError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::Status(status) => status.into_response(),
+            Self::InvalidFields(errors) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(InvalidFields::from(errors)),
+            )
+                .into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::Arc;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::api::common::Vote;
+    use crate::app::holidays::HolidayCalendars;
+    use crate::config::{Config, StorageConfig};
+    #[cfg(feature = "sqlite")]
+    use crate::database::SqliteStorage;
+    use crate::database::{MemoryStorage, Storage};
+
+    fn app_state(storage: Arc<dyn Storage>) -> AppState {
+        let config = Config {
+            storage: StorageConfig::Memory,
+            server_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            server_port: 0,
+            admin_token: None,
+            max_generated_dates: 100,
+            holidays_file: None,
+        };
+        AppState {
+            config: Arc::new(config),
+            storage,
+            holidays: Arc::new(HolidayCalendars::new(None).unwrap()),
+        }
+    }
+
+    /// States of the app with each available storage.
+    async fn app_states() -> Vec<AppState> {
+        let storages: Vec<Arc<dyn Storage>> = vec![
+            Arc::new(MemoryStorage::default()),
+            #[cfg(feature = "sqlite")]
+            Arc::new(SqliteStorage::open("sqlite::memory:").await.unwrap()),
+        ];
+        storages.into_iter().map(app_state).collect()
+    }
+
+    fn body<T: serde::de::DeserializeOwned>(value: serde_json::Value) -> Json<T> {
+        Json(serde_json::from_value(value).unwrap())
+    }
+
+    async fn create(app_state: &AppState, settings: serde_json::Value) -> CreatedMeeting {
+        let data = json!({
+            "meeting_name": " Team  sync ",
+            "user_name": "Alice",
+            "settings": settings,
+        });
+        let (status, Json(created)) = create_meeting(State(app_state.clone()), body(data))
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        created
+    }
+
+    async fn get(app_state: &AppState, meeting_id: Uuid) -> (HeaderMap, Meeting) {
+        let query = GetMeetingQuery {
+            user_id: None,
+            user_token: None,
+        };
+        let (headers, Json(meeting)) =
+            get_meeting_by_id(State(app_state.clone()), Path(meeting_id), Query(query))
+                .await
+                .unwrap();
+        (headers, meeting)
+    }
+
+    #[tokio::test]
+    async fn created_meeting_is_returned_with_version() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({})).await;
+
+            let (headers, meeting) = get(&app_state, created.meeting_id).await;
+            assert_eq!(meeting.meeting_info.name, "Team sync");
+            assert_eq!(meeting.meeting_info.created_by, created.user_id);
+            assert_eq!(
+                headers[header::ETAG],
+                version_entity_tag(meeting.meeting_info.version)
+            );
+
+            let missing = get_meeting_by_id(
+                State(app_state),
+                Path(Uuid::new_v4()),
+                Query(GetMeetingQuery {
+                    user_id: None,
+                    user_token: None,
+                }),
+            )
+            .await;
+            assert_eq!(missing.unwrap_err(), StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[tokio::test]
+    async fn stale_version_is_rejected() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({})).await;
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+            let version = meeting.meeting_info.version;
+            let update = json!({
+                "user_id": created.user_id,
+                "user_token": created.user_secret_token,
+                "name": "Retro",
+            });
+
+            let status = update_meeting(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                IfMatch(version),
+                body(update.clone()),
+            )
+            .await
+            .unwrap();
+            assert_eq!(status, StatusCode::NO_CONTENT);
+            let stale = update_meeting(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                IfMatch(version),
+                body(update),
+            )
+            .await;
+            assert!(matches!(
+                stale,
+                Err(InputError::Status(StatusCode::PRECONDITION_FAILED))
+            ));
+
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+            assert_eq!(meeting.meeting_info.name, "Retro");
+            assert_eq!(meeting.meeting_info.version, version + 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn duplicate_names_conflict() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({"unique_participant_names": true})).await;
+
+            let join = |name: &str| {
+                join_meeting(
+                    State(app_state.clone()),
+                    Path(created.meeting_id),
+                    body(json!({ "name": name })),
+                )
+            };
+            assert!(join("Bob").await.is_ok());
+            assert!(matches!(
+                join(" BOB ").await,
+                Err(InputError::Status(StatusCode::CONFLICT))
+            ));
+            assert!(matches!(
+                join("alice").await,
+                Err(InputError::Status(StatusCode::CONFLICT))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn participants_dates_and_votes_are_returned() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({})).await;
+            let other = create(&app_state, json!({})).await;
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+
+            let version = meeting.meeting_info.version;
+            let mut date_ids = Vec::new();
+            for (version, date) in (version..).zip(["2030-01-02", "2030-01-01"]) {
+                let data = json!({
+                    "user_id": created.user_id,
+                    "user_token": created.user_secret_token,
+                    "date": date,
+                });
+                let (_, Json(added)) = add_proposed_date(
+                    State(app_state.clone()),
+                    Path(created.meeting_id),
+                    IfMatch(version),
+                    body(data),
+                )
+                .await
+                .unwrap();
+                date_ids.push(added.id);
+            }
+            let (_, Json(bob)) = join_meeting(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                body(json!({ "name": "Bob" })),
+            )
+            .await
+            .unwrap();
+            let vote = json!({
+                "user_id": bob.id,
+                "user_token": bob.secret_token,
+                "date_id": date_ids[0],
+                "vote": "yes",
+            });
+            let status = cast_vote(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                body(vote),
+            )
+            .await
+            .unwrap();
+            assert_eq!(status, StatusCode::NO_CONTENT);
+
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+            let mut participants: Vec<_> = meeting.participants.iter().map(|p| p.id).collect();
+            participants.sort_unstable();
+            let mut expected = vec![created.user_id, bob.id];
+            expected.sort_unstable();
+            assert_eq!(participants, expected);
+            let dates: Vec<_> = meeting.proposed_dates.iter().map(|d| d.id).collect();
+            assert_eq!(dates, [date_ids[1], date_ids[0]]);
+            assert_eq!(meeting.votes.len(), 1);
+            assert_eq!(meeting.votes[0].participant_id, bob.id);
+            assert_eq!(meeting.votes[0].date_id, date_ids[0]);
+
+            let (_, meeting) = get(&app_state, other.meeting_id).await;
+            assert_eq!(meeting.participants.len(), 1);
+            assert!(meeting.proposed_dates.is_empty());
+            assert!(meeting.votes.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn participants_dates_and_votes_are_ordered() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({})).await;
+            let mut users = vec![(created.user_id, created.user_secret_token)];
+            for name in ["Bob", "Carol", "Dave"] {
+                let (_, Json(user)) = join_meeting(
+                    State(app_state.clone()),
+                    Path(created.meeting_id),
+                    body(json!({ "name": name })),
+                )
+                .await
+                .unwrap();
+                users.push((user.id, user.secret_token));
+            }
+
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+            let options = [
+                json!({"starts_at": "2030-01-01T10:00:00Z", "duration_minutes": 60}),
+                json!({"date": "2030-01-03"}),
+                json!({"starts_at": "2030-01-01T10:00:00Z", "duration_minutes": 30}),
+                json!({"date": "2030-01-02"}),
+                json!({"date": "2030-01-01"}),
+                // Still on January 1 in meeting's timezone
+                json!({"starts_at": "2030-01-02T01:00:00+02:00", "duration_minutes": 30}),
+            ];
+            let mut date_ids = Vec::new();
+            for (version, mut option) in (meeting.meeting_info.version..).zip(options) {
+                option["user_id"] = json!(created.user_id);
+                option["user_token"] = json!(created.user_secret_token);
+                let (_, Json(added)) = add_proposed_date(
+                    State(app_state.clone()),
+                    Path(created.meeting_id),
+                    IfMatch(version),
+                    body(option),
+                )
+                .await
+                .unwrap();
+                date_ids.push(added.id);
+            }
+            for &(user_id, user_token) in users.iter().rev() {
+                for &date_id in &date_ids {
+                    let vote = json!({
+                        "user_id": user_id,
+                        "user_token": user_token,
+                        "date_id": date_id,
+                        "vote": "yes",
+                    });
+                    cast_vote(
+                        State(app_state.clone()),
+                        Path(created.meeting_id),
+                        body(vote),
+                    )
+                    .await
+                    .unwrap();
+                }
+            }
+
+            let participants: Vec<_> = users.iter().map(|&(user_id, _)| user_id).collect();
+            let dates = [
+                date_ids[4],
+                date_ids[2],
+                date_ids[0],
+                date_ids[5],
+                date_ids[3],
+                date_ids[1],
+            ];
+            let votes: Vec<_> = participants
+                .iter()
+                .flat_map(|&user_id| dates.map(|date_id| (user_id, date_id)))
+                .collect();
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+            let ids: Vec<_> = meeting.participants.iter().map(|p| p.id).collect();
+            assert_eq!(ids, participants);
+            let ids: Vec<_> = meeting.proposed_dates.iter().map(|d| d.id).collect();
+            assert_eq!(ids, dates);
+            let ids: Vec<_> = meeting
+                .votes
+                .iter()
+                .map(|v| (v.participant_id, v.date_id))
+                .collect();
+            assert_eq!(ids, votes);
+        }
+    }
+
+    #[tokio::test]
+    async fn storage_rejects_votes_once_voting_is_closed() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({})).await;
+            let (_, meeting) = get(&app_state, created.meeting_id).await;
+            let version = meeting.meeting_info.version;
+            let credentials = json!({
+                "user_id": created.user_id,
+                "user_token": created.user_secret_token,
+            });
+            let mut data = credentials.clone();
+            data["date"] = json!("2030-01-01");
+            let (_, Json(added)) = add_proposed_date(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                IfMatch(version),
+                body(data),
+            )
+            .await
+            .unwrap();
+            let status = close_voting(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                IfMatch(version + 1),
+                body(credentials),
+            )
+            .await
+            .unwrap();
+            assert_eq!(status, StatusCode::NO_CONTENT);
+
+            // Vote checked by the handler before voting was closed
+            let vote =
+                business_logic::ParticipantVote::new(added.id, created.user_id, Vote::Yes, None)
+                    .unwrap();
+            let result = app_state.storage.cast_vote(created.meeting_id, &vote).await;
+            assert!(matches!(result, Err(database::CastVoteError::VotingClosed)));
+        }
+    }
+
+    #[tokio::test]
+    async fn history_is_recorded() {
+        for app_state in app_states().await {
+            let created = create(&app_state, json!({})).await;
+            let data = json!({
+                "user_id": created.user_id,
+                "user_token": created.user_secret_token,
+                "message": "Hello",
+            });
+            post_comment(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                body(data),
+            )
+            .await
+            .unwrap();
+
+            let credentials = UserCredentials {
+                user_id: created.user_id,
+                user_token: created.user_secret_token,
+            };
+            let Json(history) = get_meeting_history(
+                State(app_state.clone()),
+                Path(created.meeting_id),
+                Query(credentials),
+            )
+            .await
+            .unwrap();
+            let export = app_state
+                .storage
+                .get_meeting_export(created.meeting_id)
+                .await
+                .unwrap()
+                .unwrap();
+            let comment_id = export.comments[0].id;
+            let entries: Vec<_> = history
+                .entries
+                .iter()
+                .map(|entry| {
+                    json!({
+                        "actor_id": entry.actor_id,
+                        "action": entry.action,
+                        "proposed_date_id": entry.proposed_date_id,
+                        "old_value": entry.old_value,
+                        "new_value": entry.new_value,
+                    })
+                })
+                .collect();
+            assert_eq!(
+                entries,
+                [json!({
+                    "actor_id": created.user_id,
+                    "action": "comment_posted",
+                    "proposed_date_id": null,
+                    "old_value": null,
+                    "new_value": {"id": comment_id, "message": "Hello"},
+                })]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_fields_are_reported() {
+        for app_state in app_states().await {
+            let data = json!({
+                "meeting_name": "  ",
+                "user_name": "x".repeat(101),
+            });
+
+            let result = create_meeting(State(app_state), body(data)).await;
+            let Err(InputError::InvalidFields(errors)) = result else {
+                panic!("expected invalid fields, got {result:?}");
+            };
+            let fields: Vec<_> = errors.0.iter().map(|error| error.field).collect();
+            assert_eq!(fields, ["meeting_name", "user_name"]);
+        }
+    }
+}
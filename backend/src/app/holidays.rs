@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use time::{Date, Duration, Month, Weekday};
+use time_tz::Tz;
+
+use crate::api::common::{DateOption, MeetingSettings};
+
+/// Source of public holidays. Every provider serves one or more named
+/// calendars, e.g. holidays of a single country.
+pub(crate) trait HolidayProvider: fmt::Debug + Send + Sync {
+    /// Checks if provider serves calendar with given name.
+    fn has_calendar(&self, calendar: &str) -> bool;
+
+    /// Returns name of the holiday on `date` in `calendar`, if there is one.
+    fn holiday(&self, calendar: &str, date: Date) -> Option<String>;
+}
+
+/// All holiday providers known to the server. Calendars of providers
+/// registered earlier take precedence.
+#[derive(Debug, Default)]
+pub(crate) struct HolidayCalendars {
+    providers: Vec<Box<dyn HolidayProvider>>,
+}
+
+impl HolidayCalendars {
+    /// Creates calendars with bundled dataset and, if `custom_holidays_file`
+    /// is set, custom calendars loaded from that file.
+    pub(crate) fn new(custom_holidays_file: Option<&Path>) -> Result<Self> {
+        let mut calendars = Self::default();
+        if let Some(path) = custom_holidays_file {
+            calendars.register(FileHolidays::load(path)?);
+        }
+        calendars.register(BundledHolidays);
+        Ok(calendars)
+    }
+
+    pub(crate) fn register(&mut self, provider: impl HolidayProvider + 'static) {
+        self.providers.push(Box::new(provider));
+    }
+
+    pub(crate) fn has_calendar(&self, calendar: &str) -> bool {
+        self.providers.iter().any(|p| p.has_calendar(calendar))
+    }
+
+    pub(crate) fn holiday(&self, calendar: &str, date: Date) -> Option<String> {
+        self.providers
+            .iter()
+            .find(|p| p.has_calendar(calendar))
+            .and_then(|p| p.holiday(calendar, date))
+    }
+
+    /// Returns marks of `option` according to meeting `settings`. Time slots
+    /// are checked against the day on which they start in timezone `tz`.
+    pub(crate) fn marks(
+        &self,
+        settings: &MeetingSettings,
+        option: DateOption,
+        tz: &Tz,
+    ) -> DateMarks {
+        let date = option.day(tz);
+
+        DateMarks {
+            holiday: settings
+                .holiday_calendar
+                .as_deref()
+                .and_then(|calendar| self.holiday(calendar, date)),
+            weekend: settings.exclude_weekends
+                && matches!(date.weekday(), Weekday::Saturday | Weekday::Sunday),
+        }
+    }
+}
+
+/// Reasons for which meeting may not want given date.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct DateMarks {
+    /// Name of the holiday in meeting's holiday calendar
+    pub(crate) holiday: Option<String>,
+    /// Date falls on a weekend and meeting excludes weekends
+    pub(crate) weekend: bool,
+}
+
+impl DateMarks {
+    pub(crate) fn is_marked(&self) -> bool {
+        self.holiday.is_some() || self.weekend
+    }
+}
+
+/// How holiday of a bundled calendar is determined in a given year.
+#[derive(Debug, Clone, Copy)]
+enum HolidayRule {
+    /// The same day every year
+    Fixed(Month, u8),
+    /// Given number of days after Easter Sunday
+    Easter(i64),
+    /// N-th weekday of a month
+    NthWeekday(Month, Weekday, u8),
+    /// Last weekday of a month
+    LastWeekday(Month, Weekday),
+}
+
+impl HolidayRule {
+    fn matches(self, date: Date) -> bool {
+        match self {
+            Self::Fixed(month, day) => date.month() == month && date.day() == day,
+            Self::Easter(offset) => easter_sunday(date.year())
+                .is_some_and(|easter| easter + Duration::days(offset) == date),
+            Self::NthWeekday(month, weekday, n) => {
+                date.month() == month && date.weekday() == weekday && (date.day() - 1) / 7 + 1 == n
+            }
+            Self::LastWeekday(month, weekday) => {
+                date.month() == month
+                    && date.weekday() == weekday
+                    && (date + Duration::weeks(1)).month() != month
+            }
+        }
+    }
+}
+
+/// Returns date of Easter Sunday in Gregorian calendar
+/// (anonymous Gregorian algorithm).
+fn easter_sunday(year: i32) -> Option<Date> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    let month = Month::try_from(month as u8).ok()?;
+    Date::from_calendar_date(year, month, day as u8).ok()
+}
+
+/// Public holidays of a few countries bundled with the server. Calendars
+/// are named after ISO 3166 country codes. Only nationwide holidays are
+/// included and days observed in lieu of holidays falling on weekends are not.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BundledHolidays;
+
+impl BundledHolidays {
+    fn rules(calendar: &str) -> Option<&'static [(HolidayRule, &'static str)]> {
+        use HolidayRule::*;
+        use Month::*;
+
+        match calendar {
+            "PL" => Some(&[
+                (Fixed(January, 1), "New Year's Day"),
+                (Fixed(January, 6), "Epiphany"),
+                (Easter(0), "Easter Sunday"),
+                (Easter(1), "Easter Monday"),
+                (Fixed(May, 1), "Labour Day"),
+                (Fixed(May, 3), "Constitution Day"),
+                (Easter(49), "Pentecost"),
+                (Easter(60), "Corpus Christi"),
+                (Fixed(August, 15), "Assumption Day"),
+                (Fixed(November, 1), "All Saints' Day"),
+                (Fixed(November, 11), "Independence Day"),
+                (Fixed(December, 25), "Christmas Day"),
+                (Fixed(December, 26), "Second Day of Christmas"),
+            ]),
+            "DE" => Some(&[
+                (Fixed(January, 1), "New Year's Day"),
+                (Easter(-2), "Good Friday"),
+                (Easter(1), "Easter Monday"),
+                (Fixed(May, 1), "Labour Day"),
+                (Easter(39), "Ascension Day"),
+                (Easter(50), "Whit Monday"),
+                (Fixed(October, 3), "German Unity Day"),
+                (Fixed(December, 25), "Christmas Day"),
+                (Fixed(December, 26), "Second Day of Christmas"),
+            ]),
+            "GB" => Some(&[
+                (Fixed(January, 1), "New Year's Day"),
+                (Easter(-2), "Good Friday"),
+                (Easter(1), "Easter Monday"),
+                (
+                    NthWeekday(May, Weekday::Monday, 1),
+                    "Early May Bank Holiday",
+                ),
+                (LastWeekday(May, Weekday::Monday), "Spring Bank Holiday"),
+                (LastWeekday(August, Weekday::Monday), "Summer Bank Holiday"),
+                (Fixed(December, 25), "Christmas Day"),
+                (Fixed(December, 26), "Boxing Day"),
+            ]),
+            "US" => Some(&[
+                (Fixed(January, 1), "New Year's Day"),
+                (
+                    NthWeekday(January, Weekday::Monday, 3),
+                    "Martin Luther King Jr. Day",
+                ),
+                (
+                    NthWeekday(February, Weekday::Monday, 3),
+                    "Washington's Birthday",
+                ),
+                (LastWeekday(May, Weekday::Monday), "Memorial Day"),
+                (Fixed(June, 19), "Juneteenth"),
+                (Fixed(July, 4), "Independence Day"),
+                (NthWeekday(September, Weekday::Monday, 1), "Labor Day"),
+                (NthWeekday(October, Weekday::Monday, 2), "Columbus Day"),
+                (Fixed(November, 11), "Veterans Day"),
+                (
+                    NthWeekday(November, Weekday::Thursday, 4),
+                    "Thanksgiving Day",
+                ),
+                (Fixed(December, 25), "Christmas Day"),
+            ]),
+            _ => None,
+        }
+    }
+}
+
+impl HolidayProvider for BundledHolidays {
+    fn has_calendar(&self, calendar: &str) -> bool {
+        Self::rules(calendar).is_some()
+    }
+
+    fn holiday(&self, calendar: &str, date: Date) -> Option<String> {
+        Self::rules(calendar)?
+            .iter()
+            .find(|(rule, _)| rule.matches(date))
+            .map(|(_, name)| (*name).to_owned())
+    }
+}
+
+/// Custom calendars loaded from a JSON file mapping calendar names to lists
+/// of holidays, e.g. `{"ACME": [{"date": "2023-12-27", "name": "Office closed"}]}`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FileHolidays {
+    calendars: HashMap<String, HashMap<Date, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileHoliday {
+    #[serde(with = "crate::api::serde_rfc_3339::date")]
+    date: Date,
+    name: String,
+}
+
+impl FileHolidays {
+    pub(crate) fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read holidays file {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("failed to parse holidays file {}", path.display()))
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let calendars = serde_json::from_str::<HashMap<String, Vec<FileHoliday>>>(content)?
+            .into_iter()
+            .map(|(calendar, holidays)| {
+                let holidays = holidays.into_iter().map(|h| (h.date, h.name)).collect();
+                (calendar, holidays)
+            })
+            .collect();
+        Ok(Self { calendars })
+    }
+}
+
+impl HolidayProvider for FileHolidays {
+    fn has_calendar(&self, calendar: &str) -> bool {
+        self.calendars.contains_key(calendar)
+    }
+
+    fn holiday(&self, calendar: &str, date: Date) -> Option<String> {
+        self.calendars.get(calendar)?.get(&date).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+    use time_tz::timezones;
+
+    #[test]
+    fn easter() {
+        #[rustfmt::skip]
+        let expected = [date!(2023-04-09), date!(2024-03-31), date!(2025-04-20), date!(2038-04-25)];
+
+        for easter in expected {
+            assert_eq!(easter_sunday(easter.year()), Some(easter));
+        }
+    }
+
+    #[test]
+    fn bundled_calendars() {
+        let bundled = BundledHolidays;
+
+        #[rustfmt::skip]
+        let expected = [
+            ("PL", date!(2023-06-08), Some("Corpus Christi")),
+            ("PL", date!(2023-05-03), Some("Constitution Day")),
+            ("DE", date!(2023-04-07), Some("Good Friday")),
+            ("GB", date!(2023-05-29), Some("Spring Bank Holiday")),
+            ("GB", date!(2023-05-22), None),
+            ("US", date!(2023-11-23), Some("Thanksgiving Day")),
+            ("US", date!(2023-11-16), None),
+            ("US", date!(2023-01-16), Some("Martin Luther King Jr. Day")),
+        ];
+
+        for (calendar, date, holiday) in expected {
+            assert_eq!(
+                bundled.holiday(calendar, date).as_deref(),
+                holiday,
+                "{calendar} {date}"
+            );
+        }
+        assert!(!bundled.has_calendar("XX"));
+    }
+
+    #[test]
+    fn file_calendars_take_precedence() {
+        let file = FileHolidays::parse(
+            r#"{"ACME": [{"date": "2023-12-27", "name": "Office closed"}], "PL": []}"#,
+        )
+        .unwrap();
+        let mut calendars = HolidayCalendars::default();
+        calendars.register(file);
+        calendars.register(BundledHolidays);
+
+        #[rustfmt::skip]
+        assert_eq!(calendars.holiday("ACME", date!(2023-12-27)).as_deref(), Some("Office closed"));
+        #[rustfmt::skip]
+        assert_eq!(calendars.holiday("PL", date!(2023-12-25)), None);
+        assert!(calendars.has_calendar("US"));
+    }
+
+    #[test]
+    fn marks_use_local_date() {
+        let calendars = HolidayCalendars::new(None).unwrap();
+        let settings = MeetingSettings {
+            holiday_calendar: Some("PL".to_owned()),
+            exclude_weekends: true,
+            ..Default::default()
+        };
+        let warsaw = timezones::get_by_name("Europe/Warsaw").unwrap();
+
+        // Friday 23:30 UTC is already Saturday, Independence Day, in Warsaw
+        let option = DateOption::TimeSlot {
+            starts_at: datetime!(2023-11-10 23:30 UTC),
+            duration_minutes: 60,
+        };
+        assert_eq!(
+            calendars.marks(&settings, option, warsaw),
+            DateMarks {
+                holiday: Some("Independence Day".to_owned()),
+                weekend: true,
+            }
+        );
+        #[rustfmt::skip]
+        let option = DateOption::AllDay { date: date!(2023-11-10) };
+        assert!(!calendars.marks(&settings, option, warsaw).is_marked());
+    }
+}
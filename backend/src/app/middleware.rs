@@ -1,9 +1,8 @@
 use anyhow::{self, Result};
-use sqlx::PgPool;
 use tracing::debug;
 use uuid::Uuid;
 
-use crate::database;
+use crate::database::Storage;
 
 /// Error indicating that user validation failed
 #[derive(Debug, thiserror::Error)]
@@ -22,15 +21,15 @@ pub(crate) enum CredentialValidationError {
 // TODO: make this proper tower middleware
 
 /// Validates passed user credentials.
-#[tracing::instrument(skip(pool))]
+#[tracing::instrument(skip(storage))]
 pub(crate) async fn validate_user_credentials(
     user_id: Uuid,
     user_secret_token: Uuid,
-    pool: &PgPool,
+    storage: &dyn Storage,
 ) -> Result<(), CredentialValidationError> {
     debug!(?user_id, ?user_secret_token, "Validating user credentials");
 
-    match database::get_user_secret_token(user_id, pool).await? {
+    match storage.get_user_secret_token(user_id).await? {
         Some(token) => {
             if user_secret_token == token {
                 debug!("User credentials are valid");
@@ -46,3 +45,28 @@ pub(crate) async fn validate_user_credentials(
         }
     }
 }
+
+/// Validates passed account credentials.
+#[tracing::instrument(skip(storage))]
+pub(crate) async fn validate_account_credentials(
+    account_id: Uuid,
+    account_token: Uuid,
+    storage: &dyn Storage,
+) -> Result<(), CredentialValidationError> {
+    debug!(?account_id, "Validating account credentials");
+
+    match storage.get_account_secret_token(account_id).await? {
+        Some(token) if token == account_token => {
+            debug!("Account credentials are valid");
+            Ok(())
+        }
+        Some(_) => {
+            debug!("Invalid account secret token");
+            Err(CredentialValidationError::InvalidSecretToken)
+        }
+        None => {
+            debug!(?account_id, "Account with given id does not exist");
+            Err(CredentialValidationError::NonexistentUser)
+        }
+    }
+}
@@ -1,47 +1,105 @@
+pub(crate) mod availability;
 pub(crate) mod business_logic;
+pub(crate) mod date_rules;
 pub(crate) mod handlers;
+pub(crate) mod holidays;
 pub(crate) mod middleware;
+pub(crate) mod results;
+pub(crate) mod validation;
 
 use std::{sync::Arc, time::Duration};
 
 use anyhow::Context;
 use axum::{
-    routing::{get, post},
+    routing::{get, post, put},
     Router, Server,
 };
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
-use crate::config::Config;
+use self::holidays::HolidayCalendars;
+use crate::config::{Config, PostgresConfig, StorageConfig};
+use crate::database::{MemoryStorage, PostgresStorage, Storage};
 
 #[derive(Debug, Clone)]
 pub(crate) struct AppState {
-    #[allow(dead_code)]
     pub(crate) config: Arc<Config>,
-    pub(crate) database_pool: PgPool,
+    pub(crate) storage: Arc<dyn Storage>,
+    pub(crate) holidays: Arc<HolidayCalendars>,
 }
 
 pub async fn run_server(
     config: Arc<Config>,
     cancellation_token: CancellationToken,
 ) -> Result<(), anyhow::Error> {
-    let database_pool = match db_pool_connect(Arc::clone(&config), cancellation_token.clone()).await
-    {
-        Some(pool) => pool,
-        None => return Ok(()),
+    let holidays = HolidayCalendars::new(config.holidays_file.as_deref())
+        .context("failed to load holiday calendars")?;
+
+    let storage: Arc<dyn Storage> = match config.storage {
+        StorageConfig::Postgres(ref postgres) => {
+            match db_pool_connect(postgres, cancellation_token.clone()).await {
+                Some(pool) => Arc::new(PostgresStorage::new(pool)),
+                None => return Ok(()),
+            }
+        }
+        #[cfg(feature = "sqlite")]
+        StorageConfig::Sqlite(ref url) => {
+            Arc::new(crate::database::SqliteStorage::open(url).await?)
+        }
+        StorageConfig::Memory => {
+            warn!("Using in-memory storage. All data will be lost when the server stops");
+            Arc::new(MemoryStorage::default())
+        }
     };
 
     let app_state = AppState {
         config: Arc::clone(&config),
-        database_pool,
+        storage,
+        holidays: Arc::new(holidays),
     };
 
     let app = Router::new()
+        .route("/account", post(handlers::create_account))
+        .route(
+            "/account/:uuid/meetings",
+            get(handlers::get_account_meetings).post(handlers::link_account),
+        )
         .route("/meeting", post(handlers::create_meeting))
-        .route("/meeting/:uuid", get(handlers::get_meeting_by_id))
+        .route(
+            "/meeting/:uuid",
+            get(handlers::get_meeting_by_id).patch(handlers::update_meeting),
+        )
         .route("/meeting/:uuid/join", post(handlers::join_meeting))
         .route("/meeting/:uuid/comment", post(handlers::post_comment))
+        .route("/meeting/:uuid/date", post(handlers::add_proposed_date))
+        .route(
+            "/meeting/:uuid/date/generate",
+            post(handlers::generate_proposed_dates),
+        )
+        .route(
+            "/meeting/:uuid/date/:date_id/approve",
+            post(handlers::approve_proposed_date),
+        )
+        .route(
+            "/meeting/:uuid/date/:date_id/reject",
+            post(handlers::reject_proposed_date),
+        )
+        .route("/meeting/:uuid/vote", post(handlers::cast_vote))
+        .route("/meeting/:uuid/close", post(handlers::close_voting))
+        .route("/meeting/:uuid/finalize", post(handlers::finalize_meeting))
+        .route("/meeting/:uuid/results", get(handlers::get_results))
+        .route("/meeting/:uuid/history", get(handlers::get_meeting_history))
+        .route(
+            "/meeting/:uuid/participant/:participant_id",
+            put(handlers::set_participant_required),
+        )
+        .route(
+            "/meeting/:uuid/availability",
+            get(handlers::get_availability).put(handlers::set_availability),
+        )
+        .route("/meeting/:uuid/export", get(handlers::export_meeting))
+        .route("/admin/import", post(handlers::import_meeting))
         .with_state(app_state);
 
     let address = config.server_socket_addr();
@@ -61,10 +119,10 @@ pub async fn run_server(
 }
 
 async fn db_pool_connect(
-    config: Arc<Config>,
+    config: &PostgresConfig,
     cancellation_token: CancellationToken,
 ) -> Option<PgPool> {
-    let uri = config.postgres_uri();
+    let uri = config.uri();
     let timeout = Duration::from_secs(5);
     info!("Connecting to database");
     loop {
@@ -0,0 +1,349 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::api::common::{MeetingSettings, Vote};
+use crate::api::output::Meeting;
+use crate::database::models;
+
+/// Vote of a participant on a proposed date.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CastVote {
+    pub(crate) date_id: Uuid,
+    pub(crate) participant_id: Uuid,
+    pub(crate) vote: Vote,
+}
+
+/// Attendance constraints a date must satisfy to be viable.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Quorum {
+    /// Minimal number of `yes` votes
+    pub(crate) min_yes_votes: Option<u16>,
+    /// Maximal number of `yes` votes
+    pub(crate) max_capacity: Option<u16>,
+}
+
+impl From<&MeetingSettings> for Quorum {
+    fn from(settings: &MeetingSettings) -> Self {
+        Self {
+            min_yes_votes: settings.min_yes_votes,
+            max_capacity: settings.max_capacity,
+        }
+    }
+}
+
+/// Votes cast on a single proposed date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DateTally {
+    pub(crate) date_id: Uuid,
+    pub(crate) yes: u32,
+    pub(crate) maybe: u32,
+    pub(crate) no: u32,
+    /// Required participants that voted `no` on this date
+    pub(crate) blocked_by: Vec<Uuid>,
+}
+
+impl DateTally {
+    pub(crate) fn new(date_id: Uuid) -> Self {
+        Self {
+            date_id,
+            yes: 0,
+            maybe: 0,
+            no: 0,
+            blocked_by: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, vote: CastVote, required: bool) {
+        match vote.vote {
+            Vote::Yes => self.yes += 1,
+            Vote::Maybe => self.maybe += 1,
+            Vote::No => {
+                self.no += 1;
+                if required {
+                    self.blocked_by.push(vote.participant_id);
+                }
+            }
+        }
+    }
+
+    /// Whether some required participant can not attend on this date.
+    pub(crate) fn is_blocked(&self) -> bool {
+        !self.blocked_by.is_empty()
+    }
+
+    /// Reasons why this date is not viable under `quorum`. Empty if the
+    /// date is viable.
+    pub(crate) fn problems(&self, quorum: &Quorum) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.is_blocked() {
+            problems.push("a required participant can not attend".to_owned());
+        }
+        if let Some(min_yes_votes) = quorum.min_yes_votes {
+            if self.yes < u32::from(min_yes_votes) {
+                problems.push(format!(
+                    "only {} of required {min_yes_votes} participants voted yes",
+                    self.yes
+                ));
+            }
+        }
+        if let Some(max_capacity) = quorum.max_capacity {
+            if self.yes > u32::from(max_capacity) {
+                problems.push(format!(
+                    "{} participants voted yes, exceeding capacity of {max_capacity}",
+                    self.yes
+                ));
+            }
+        }
+        problems
+    }
+
+    /// Whether this date satisfies `quorum` and is not blocked.
+    pub(crate) fn is_viable(&self, quorum: &Quorum) -> bool {
+        self.problems(quorum).is_empty()
+    }
+
+    /// Score of the date. Every `yes` is worth two points and every
+    /// `maybe` one point.
+    pub(crate) fn score(&self) -> u32 {
+        2 * self.yes + self.maybe
+    }
+}
+
+/// Tallies `votes` on `dates`, keeping order of `dates`. Votes on dates
+/// not listed in `dates` are ignored.
+pub(crate) fn tally(
+    dates: &[Uuid],
+    votes: impl IntoIterator<Item = CastVote>,
+    required_participants: &HashSet<Uuid>,
+) -> Vec<DateTally> {
+    let mut tallies = dates
+        .iter()
+        .copied()
+        .map(DateTally::new)
+        .collect::<Vec<_>>();
+    for vote in votes {
+        if let Some(tally) = tallies.iter_mut().find(|t| t.date_id == vote.date_id) {
+            tally.add(vote, required_participants.contains(&vote.participant_id));
+        }
+    }
+    for tally in &mut tallies {
+        tally.blocked_by.sort_unstable();
+    }
+    tallies
+}
+
+/// Tallies votes on approved dates of a meeting.
+pub(crate) fn tally_meeting_votes(meeting_votes: models::MeetingVotes) -> Vec<DateTally> {
+    let models::MeetingVotes {
+        dates,
+        votes,
+        required_participants,
+    } = meeting_votes;
+    let votes = votes.into_iter().map(|vote| CastVote {
+        date_id: vote.proposed_date_id,
+        participant_id: vote.user_id,
+        vote: vote.vote.into(),
+    });
+    tally(&dates, votes, &required_participants.into_iter().collect())
+}
+
+/// Tallies votes of an assembled meeting on its approved dates, in the same
+/// way as [`tally_meeting_votes`] does for votes read from storage.
+pub(crate) fn tally_meeting(meeting: &Meeting) -> Vec<DateTally> {
+    let dates = meeting
+        .proposed_dates
+        .iter()
+        .filter(|date| date.approved)
+        .map(|date| date.id)
+        .collect::<Vec<_>>();
+    let required_participants = meeting
+        .participants
+        .iter()
+        .filter(|participant| participant.required)
+        .map(|participant| participant.id)
+        .collect();
+    let votes = meeting.votes.iter().map(|vote| CastVote {
+        date_id: vote.date_id,
+        participant_id: vote.participant_id,
+        vote: vote.vote,
+    });
+    tally(&dates, votes, &required_participants)
+}
+
+/// Returns id of the viable date with the highest score. Ties are resolved
+/// in favour of the date tallied first. Returns `None` if no viable date
+/// received a positive vote.
+pub(crate) fn best_date(tallies: &[DateTally], quorum: &Quorum) -> Option<Uuid> {
+    tallies
+        .iter()
+        .filter(|tally| tally.is_viable(quorum) && tally.score() > 0)
+        .fold(None, |best: Option<&DateTally>, tally| match best {
+            Some(best) if best.score() >= tally.score() => Some(best),
+            _ => Some(tally),
+        })
+        .map(|tally| tally.date_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn votes<const N: usize>(votes: [(Uuid, Uuid, Vote); N]) -> Vec<CastVote> {
+        votes
+            .into_iter()
+            .map(|(date_id, participant_id, vote)| CastVote {
+                date_id,
+                participant_id,
+                vote,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tally_votes() {
+        let dates = [Uuid::new_v4(), Uuid::new_v4()];
+        let [alice, bob] = [Uuid::new_v4(), Uuid::new_v4()];
+        let votes = votes([
+            (dates[0], alice, Vote::Yes),
+            (dates[0], bob, Vote::No),
+            (dates[1], alice, Vote::Maybe),
+            (Uuid::new_v4(), alice, Vote::Yes),
+        ]);
+
+        let tallies = tally(&dates, votes, &HashSet::new());
+
+        assert_eq!(
+            tallies,
+            [
+                DateTally {
+                    date_id: dates[0],
+                    yes: 1,
+                    maybe: 0,
+                    no: 1,
+                    blocked_by: Vec::new(),
+                },
+                DateTally {
+                    date_id: dates[1],
+                    yes: 0,
+                    maybe: 1,
+                    no: 0,
+                    blocked_by: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn best_date_has_highest_score() {
+        let dates = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let [alice, bob, carol] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let votes = votes([
+            (dates[0], alice, Vote::Maybe),
+            (dates[0], bob, Vote::Maybe),
+            (dates[0], carol, Vote::Maybe),
+            (dates[1], alice, Vote::Yes),
+            (dates[1], bob, Vote::Yes),
+            (dates[2], alice, Vote::Yes),
+            (dates[2], bob, Vote::No),
+        ]);
+
+        let tallies = tally(&dates, votes, &HashSet::new());
+
+        assert_eq!(best_date(&tallies, &Quorum::default()), Some(dates[1]));
+    }
+
+    #[test]
+    fn best_date_ties_prefer_first_date() {
+        let dates = [Uuid::new_v4(), Uuid::new_v4()];
+        let alice = Uuid::new_v4();
+        let votes = votes([(dates[1], alice, Vote::Yes), (dates[0], alice, Vote::Yes)]);
+
+        let tallies = tally(&dates, votes, &HashSet::new());
+
+        assert_eq!(best_date(&tallies, &Quorum::default()), Some(dates[0]));
+    }
+
+    #[test]
+    fn no_best_date_without_positive_votes() {
+        let dates = [Uuid::new_v4()];
+        let alice = Uuid::new_v4();
+
+        let tallies = tally(&dates, [], &HashSet::new());
+        assert_eq!(best_date(&tallies, &Quorum::default()), None);
+
+        let tallies = tally(
+            &dates,
+            votes([(dates[0], alice, Vote::No)]),
+            &HashSet::new(),
+        );
+        assert_eq!(best_date(&tallies, &Quorum::default()), None);
+    }
+
+    #[test]
+    fn required_participant_blocks_date() {
+        let dates = [Uuid::new_v4(), Uuid::new_v4()];
+        let [alice, bob, carol] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let votes = votes([
+            (dates[0], alice, Vote::Yes),
+            (dates[0], bob, Vote::Yes),
+            (dates[0], carol, Vote::No),
+            (dates[1], alice, Vote::Yes),
+            (dates[1], bob, Vote::No),
+            (dates[1], carol, Vote::Maybe),
+        ]);
+
+        let tallies = tally(&dates, votes, &HashSet::from([carol]));
+
+        assert_eq!(tallies[0].blocked_by, [carol]);
+        assert!(tallies[0].is_blocked());
+        assert!(!tallies[1].is_blocked());
+        assert_eq!(best_date(&tallies, &Quorum::default()), Some(dates[1]));
+    }
+
+    #[test]
+    fn quorum_and_capacity() {
+        let dates = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let [alice, bob, carol] = [Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+        let votes = votes([
+            (dates[0], alice, Vote::Yes),
+            (dates[0], bob, Vote::Yes),
+            (dates[0], carol, Vote::Yes),
+            (dates[1], alice, Vote::Yes),
+            (dates[1], bob, Vote::Yes),
+            (dates[2], alice, Vote::Yes),
+            (dates[2], bob, Vote::Maybe),
+        ]);
+        let quorum = Quorum {
+            min_yes_votes: Some(2),
+            max_capacity: Some(2),
+        };
+
+        let tallies = tally(&dates, votes, &HashSet::new());
+
+        assert!(!tallies[0].is_viable(&quorum));
+        assert_eq!(tallies[0].problems(&quorum).len(), 1);
+        assert!(tallies[1].is_viable(&quorum));
+        assert!(!tallies[2].is_viable(&quorum));
+        assert_eq!(best_date(&tallies, &quorum), Some(dates[1]));
+        assert_eq!(best_date(&tallies, &Quorum::default()), Some(dates[0]));
+    }
+
+    #[test]
+    fn no_best_date_without_viable_dates() {
+        let dates = [Uuid::new_v4()];
+        let alice = Uuid::new_v4();
+        let quorum = Quorum {
+            min_yes_votes: Some(2),
+            max_capacity: None,
+        };
+
+        let tallies = tally(
+            &dates,
+            votes([(dates[0], alice, Vote::Yes)]),
+            &HashSet::new(),
+        );
+
+        assert_eq!(best_date(&tallies, &quorum), None);
+    }
+}
@@ -0,0 +1,269 @@
+//! Validation of free-form text sent by users. Limits mirror lengths of
+//! `VARCHAR` columns in the database schema. Both Postgres and this module
+//! count lengths in Unicode scalar values, so a value accepted here always
+//! fits into its column.
+
+use std::fmt;
+
+use unicode_normalization::UnicodeNormalization;
+
+use crate::api::input::{
+    CastVoteData, CreateAccountData, CreateMeetingData, JoinMeetingData, PostCommentData,
+    UpdateMeetingData,
+};
+
+/// Rules applied to one kind of text field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TextRules {
+    /// Longest allowed value in characters
+    pub(crate) max_length: usize,
+    /// Whether line breaks and tabs are allowed
+    pub(crate) multiline: bool,
+}
+
+/// Names of users and meetings
+pub(crate) const NAME: TextRules = TextRules {
+    max_length: 100,
+    multiline: false,
+};
+
+/// Descriptions of meetings
+pub(crate) const DESCRIPTION: TextRules = TextRules {
+    max_length: 1000,
+    multiline: true,
+};
+
+/// Messages of meeting comments
+pub(crate) const COMMENT: TextRules = TextRules {
+    max_length: 1000,
+    multiline: true,
+};
+
+/// Comments attached to votes
+pub(crate) const VOTE_COMMENT: TextRules = TextRules {
+    max_length: 200,
+    multiline: false,
+};
+
+/// Trims surrounding whitespace and converts text to Unicode Normalization
+/// Form C. Runs of whitespace in single-line fields are collapsed to one
+/// space and line breaks in multi-line fields are converted to `\n`.
+pub(crate) fn normalize_text(value: &str, rules: TextRules) -> String {
+    let value = if rules.multiline {
+        value.trim().replace("\r\n", "\n").replace('\r', "\n")
+    } else {
+        value.split_whitespace().collect::<Vec<_>>().join(" ")
+    };
+    value.nfc().collect()
+}
+
+/// Key under which display names are compared. Names that differ only in
+/// letter case, Unicode composition or whitespace have equal keys.
+pub(crate) fn display_name_key(name: &str) -> String {
+    normalize_text(name, NAME).to_lowercase().nfc().collect()
+}
+
+/// Checks already normalized `value` and returns description of the first
+/// problem found.
+pub(crate) fn check_text(value: &str, rules: TextRules) -> Result<(), String> {
+    if value.is_empty() {
+        return Err("must not be empty".to_owned());
+    }
+    let allowed = |c: char| rules.multiline && (c == '\n' || c == '\t');
+    if value.chars().any(|c| c.is_control() && !allowed(c)) {
+        return Err("must not contain control characters".to_owned());
+    }
+    let length = value.chars().count();
+    if length > rules.max_length {
+        return Err(format!(
+            "must be at most {} characters long, got {length}",
+            rules.max_length
+        ));
+    }
+
+    Ok(())
+}
+
+/// Problem with a single field of request body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FieldError {
+    pub(crate) field: &'static str,
+    pub(crate) message: String,
+}
+
+/// Every problem found in a request body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ValidationErrors(pub(crate) Vec<FieldError>);
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "`{}` {}", error.field, error.message)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// Normalizes text fields in place and collects problems with all of them,
+/// so that clients learn about every invalid field at once.
+#[derive(Debug, Default)]
+pub(crate) struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub(crate) fn text(&mut self, field: &'static str, value: &mut String, rules: TextRules) {
+        *value = normalize_text(value, rules);
+        if let Err(message) = check_text(value, rules) {
+            self.errors.push(FieldError { field, message });
+        }
+    }
+
+    pub(crate) fn optional_text(
+        &mut self,
+        field: &'static str,
+        value: &mut Option<String>,
+        rules: TextRules,
+    ) {
+        if let Some(value) = value {
+            self.text(field, value, rules);
+        }
+    }
+
+    pub(crate) fn finish(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationErrors(self.errors))
+        }
+    }
+}
+
+/// Request body with text fields that are validated before it is handled.
+pub(crate) trait Validate {
+    /// Normalizes text fields and checks them against their limits.
+    fn validate(&mut self) -> Result<(), ValidationErrors>;
+}
+
+impl Validate for CreateMeetingData {
+    fn validate(&mut self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::default();
+        validator.text("meeting_name", &mut self.meeting_name, NAME);
+        validator.optional_text(
+            "meeting_description",
+            &mut self.meeting_description,
+            DESCRIPTION,
+        );
+        validator.text("user_name", &mut self.user_name, NAME);
+        validator.finish()
+    }
+}
+
+impl Validate for CreateAccountData {
+    fn validate(&mut self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::default();
+        validator.text("name", &mut self.name, NAME);
+        validator.finish()
+    }
+}
+
+impl Validate for JoinMeetingData {
+    fn validate(&mut self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::default();
+        validator.text("name", &mut self.name, NAME);
+        validator.finish()
+    }
+}
+
+impl Validate for PostCommentData {
+    fn validate(&mut self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::default();
+        validator.text("message", &mut self.message, COMMENT);
+        validator.finish()
+    }
+}
+
+impl Validate for CastVoteData {
+    fn validate(&mut self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::default();
+        validator.optional_text("comment", &mut self.comment, VOTE_COMMENT);
+        validator.finish()
+    }
+}
+
+impl Validate for UpdateMeetingData {
+    fn validate(&mut self) -> Result<(), ValidationErrors> {
+        let mut validator = Validator::default();
+        validator.optional_text("name", &mut self.name, NAME);
+        if let Some(ref mut description) = self.description {
+            validator.optional_text("description", description, DESCRIPTION);
+        }
+        validator.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization() {
+        assert_eq!(normalize_text("  Team sync \n", NAME), "Team sync");
+        assert_eq!(normalize_text("Team \t  sync", NAME), "Team sync");
+        assert_eq!(normalize_text("Zo\u{65}\u{308}", NAME), "Zo\u{eb}");
+        assert_eq!(
+            normalize_text(" first\r\nsecond\rthird ", DESCRIPTION),
+            "first\nsecond\nthird"
+        );
+    }
+
+    #[test]
+    fn length_is_counted_in_characters() {
+        let name = "ż".repeat(100);
+        assert_eq!(name.len(), 200);
+        assert_eq!(check_text(&name, NAME), Ok(()));
+        assert!(check_text(&"ż".repeat(101), NAME).is_err());
+        assert_eq!(check_text(&"a".repeat(1000), COMMENT), Ok(()));
+        assert!(check_text(&"a".repeat(1001), COMMENT).is_err());
+    }
+
+    #[test]
+    fn control_characters() {
+        assert!(check_text("a\u{0}b", NAME).is_err());
+        assert!(check_text("a\tb", VOTE_COMMENT).is_err());
+        assert_eq!(check_text("a\n\tb", DESCRIPTION), Ok(()));
+        assert!(check_text("a\u{7}b", DESCRIPTION).is_err());
+    }
+
+    #[test]
+    fn display_names() {
+        assert_eq!(display_name_key(" Anna "), display_name_key("anna"));
+        assert_eq!(
+            display_name_key("Zo\u{eb}"),
+            display_name_key("ZO\u{45}\u{308}")
+        );
+        assert_ne!(display_name_key("Anna"), display_name_key("Anna B"));
+    }
+
+    #[test]
+    fn every_invalid_field_is_reported() {
+        let mut name = "   ".to_owned();
+        let mut description = Some("x".repeat(1001));
+        let mut user_name = " Alice ".to_owned();
+        let mut validator = Validator::default();
+        validator.text("meeting_name", &mut name, NAME);
+        validator.optional_text("meeting_description", &mut description, DESCRIPTION);
+        validator.text("user_name", &mut user_name, NAME);
+
+        let errors = validator.finish().unwrap_err().0;
+        let fields: Vec<_> = errors.iter().map(|error| error.field).collect();
+        assert_eq!(fields, ["meeting_name", "meeting_description"]);
+        assert_eq!(user_name, "Alice");
+    }
+}
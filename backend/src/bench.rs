@@ -0,0 +1,113 @@
+//! Fixtures for benchmarks in `benches/`. Compiled only with `bench` feature,
+//! as benchmarks can not reach crate-private items otherwise.
+
+use std::sync::Arc;
+
+use time::{Date, Duration, Month, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::api::output::Meeting;
+use crate::app::holidays::HolidayCalendars;
+use crate::database::models;
+
+/// Rows of a poll in which every participant voted on every proposed date.
+#[derive(Debug, Clone)]
+pub struct LargeMeeting {
+    meeting_info: models::MeetingInfo,
+    participants_dates_votes: models::ParticipantsDatesVotes,
+    holidays: Arc<HolidayCalendars>,
+}
+
+impl LargeMeeting {
+    pub fn new(participants: usize, dates: usize) -> Self {
+        let participants = (0..participants)
+            .map(|index| models::Participant {
+                id: Uuid::new_v4(),
+                name: format!("Participant {index}"),
+                timezone: None,
+                required: index == 0,
+                joined_at: OffsetDateTime::UNIX_EPOCH + Duration::seconds(index as i64),
+            })
+            .collect::<Vec<_>>();
+        let first_day = Date::from_calendar_date(2030, Month::January, 1).unwrap();
+        let proposed_dates = (0..dates)
+            .map(|index| models::ProposedDate {
+                id: Uuid::new_v4(),
+                date: Some(first_day + Duration::days(index as i64)),
+                starts_at: None,
+                duration_minutes: None,
+                proposed_by: None,
+                approved: true,
+            })
+            .collect::<Vec<_>>();
+        // Storage does not order votes, so they are generated in reverse.
+        let votes = proposed_dates
+            .iter()
+            .rev()
+            .flat_map(|date| {
+                participants.iter().enumerate().map(|(index, participant)| {
+                    models::ParticipantVote {
+                        proposed_date_id: date.id,
+                        user_id: participant.id,
+                        vote: match index % 3 {
+                            0 => models::Vote::Ok,
+                            1 => models::Vote::Maybe,
+                            _ => models::Vote::No,
+                        },
+                        comment: (index % 10 == 0).then(|| format!("Comment {index}")),
+                    }
+                })
+            })
+            .collect();
+
+        let meeting_info = models::MeetingInfo {
+            name: "Large meeting".to_owned(),
+            description: None,
+            created_by: participants.first().map_or_else(Uuid::new_v4, |p| p.id),
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            timezone: "Etc/UTC".to_owned(),
+            has_availability_grid: false,
+            settings: models::MeetingSettings {
+                holiday_calendar: None,
+                holiday_policy: models::HolidayPolicy::Flag,
+                exclude_weekends: false,
+                participants_can_propose: false,
+                proposals_require_approval: false,
+                hide_results: false,
+                anonymous_votes: false,
+                auto_finalize: false,
+                min_yes_votes: None,
+                max_capacity: None,
+                unique_participant_names: false,
+            },
+            voting_closes_at: None,
+            voting_closed_at: None,
+            final_date_id: None,
+            version: 1,
+        };
+
+        Self {
+            meeting_info,
+            participants_dates_votes: models::ParticipantsDatesVotes {
+                participants,
+                proposed_dates,
+                votes,
+            },
+            holidays: Arc::new(HolidayCalendars::new(None).unwrap()),
+        }
+    }
+
+    /// Assembles meeting as returned by the API and returns number of its
+    /// votes.
+    pub fn assemble(self) -> usize {
+        let meeting = Meeting::new(
+            self.meeting_info,
+            Vec::new(),
+            self.participants_dates_votes,
+            None,
+            &self.holidays,
+        )
+        .unwrap();
+        meeting.votes.len()
+    }
+}
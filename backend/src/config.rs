@@ -1,34 +1,69 @@
 use std::env::var;
 use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use tracing::info;
+use uuid::Uuid;
+
+/// Used when `MAX_GENERATED_DATES` is not set.
+const DEFAULT_MAX_GENERATED_DATES: usize = 100;
+
+/// Where meetings are stored, selected by `STORAGE` env variable.
+#[derive(Debug, Clone)]
+pub(crate) enum StorageConfig {
+    /// Postgres database, used when `STORAGE` is not set or is `postgres`
+    Postgres(PostgresConfig),
+    /// SQLite database at URL given by `SQLITE_URL`, e.g.
+    /// `sqlite://meetings.db`. Used when `STORAGE` is `sqlite`.
+    #[cfg(feature = "sqlite")]
+    Sqlite(String),
+    /// Memory of the server, used when `STORAGE` is `memory`. Data is lost
+    /// when the server stops.
+    Memory,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresConfig {
+    pub(crate) user: String,
+    pub(crate) password: String,
+    pub(crate) url: String,
+    pub(crate) port: u16,
+    pub(crate) db: String,
+}
 
 #[derive(Debug, Clone)]
 pub struct Config {
-    pub(crate) postgres_user: String,
-    pub(crate) postgres_password: String,
-    pub(crate) postgres_url: String,
-    pub(crate) postgres_port: u16,
-    pub(crate) postgres_db: String,
+    pub(crate) storage: StorageConfig,
     pub(crate) server_addr: IpAddr,
     pub(crate) server_port: u16,
+    /// Token authorizing administrative operations. When it is not set
+    /// administrative endpoints are disabled.
+    pub(crate) admin_token: Option<Uuid>,
+    /// Maximal number of dates that a single rule may generate
+    pub(crate) max_generated_dates: usize,
+    /// JSON file with custom holiday calendars
+    pub(crate) holidays_file: Option<PathBuf>,
 }
 
 impl Config {
     pub fn from_env() -> Result<Arc<Self>> {
         info!("Loading configuration from env");
 
-        let postgres_user = var("POSTGRES_USER").context("missing env variable POSTGRES_USER")?;
-        let postgres_password =
-            var("POSTGRES_PASSWORD").context("missing env variable POSTGRES_PASSWORD")?;
-        let postgres_url = var("POSTGRES_URL").context("missing env variable POSTGRES_URL")?;
-        let postgres_port = var("POSTGRES_PORT")
-            .context("missing env variable POSTGRES_PORT")?
-            .parse()
-            .context("failed to parse POSTGRES_PORT as u16")?;
-        let postgres_db = var("POSTGRES_DB").context("missing env variable POSTGRES_DB")?;
+        let storage = match var("STORAGE").as_deref() {
+            Err(_) | Ok("postgres") => StorageConfig::Postgres(PostgresConfig::from_env()?),
+            #[cfg(feature = "sqlite")]
+            Ok("sqlite") => {
+                StorageConfig::Sqlite(var("SQLITE_URL").context("missing env variable SQLITE_URL")?)
+            }
+            #[cfg(not(feature = "sqlite"))]
+            Ok("sqlite") => bail!("STORAGE `sqlite` requires server built with `sqlite` feature"),
+            Ok("memory") => StorageConfig::Memory,
+            Ok(storage) => {
+                bail!("unknown STORAGE `{storage}`, expected `postgres`, `sqlite` or `memory`")
+            }
+        };
         let server_addr = var("SERVER_ADDR")
             .context("missing env variable SERVER_ADDR")?
             .parse()
@@ -37,32 +72,61 @@ impl Config {
             .context("missing env variable SERVER_PORT")?
             .parse()
             .context("failed to parse SERVER_PORT as u16")?;
+        let admin_token = var("ADMIN_TOKEN")
+            .ok()
+            .map(|token| token.parse())
+            .transpose()
+            .context("failed to parse ADMIN_TOKEN as UUID")?;
+        let max_generated_dates = var("MAX_GENERATED_DATES")
+            .ok()
+            .map(|max| max.parse())
+            .transpose()
+            .context("failed to parse MAX_GENERATED_DATES as usize")?
+            .unwrap_or(DEFAULT_MAX_GENERATED_DATES);
+        let holidays_file = var("HOLIDAYS_FILE").ok().map(PathBuf::from);
 
         let config = Config {
-            postgres_user,
-            postgres_password,
-            postgres_url,
-            postgres_port,
-            postgres_db,
+            storage,
             server_addr,
             server_port,
+            admin_token,
+            max_generated_dates,
+            holidays_file,
         };
 
         Ok(Arc::new(config))
     }
 
-    pub(crate) fn postgres_uri(&self) -> String {
+    pub(crate) fn server_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(self.server_addr, self.server_port)
+    }
+}
+
+impl PostgresConfig {
+    fn from_env() -> Result<Self> {
+        let user = var("POSTGRES_USER").context("missing env variable POSTGRES_USER")?;
+        let password =
+            var("POSTGRES_PASSWORD").context("missing env variable POSTGRES_PASSWORD")?;
+        let url = var("POSTGRES_URL").context("missing env variable POSTGRES_URL")?;
+        let port = var("POSTGRES_PORT")
+            .context("missing env variable POSTGRES_PORT")?
+            .parse()
+            .context("failed to parse POSTGRES_PORT as u16")?;
+        let db = var("POSTGRES_DB").context("missing env variable POSTGRES_DB")?;
+
+        Ok(PostgresConfig {
+            user,
+            password,
+            url,
+            port,
+            db,
+        })
+    }
+
+    pub(crate) fn uri(&self) -> String {
         format!(
             "postgresql://{}:{}@{}:{}/{}",
-            self.postgres_user,
-            self.postgres_password,
-            self.postgres_url,
-            self.postgres_port,
-            self.postgres_db
+            self.user, self.password, self.url, self.port, self.db
         )
     }
-
-    pub(crate) fn server_socket_addr(&self) -> SocketAddr {
-        SocketAddr::new(self.server_addr, self.server_port)
-    }
 }
@@ -0,0 +1,970 @@
+//! Storage keeping all data in memory of the server. Data is lost when the
+//! server stops, so it is only meant for tests and local development.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{anyhow, Context, Result};
+use axum::async_trait;
+use serde_json::json;
+use time::OffsetDateTime;
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{
+    details_value, models, proposed_date_value, required_value, sort_proposed_dates, vote_value,
+    AddProposedDateError, CastVoteError, ImportMeetingError, JoinMeetingError,
+    SetAvailabilityError, Storage, VersionedUpdateError,
+};
+use crate::api::common::{DateOption, MeetingSettings};
+use crate::app::availability;
+use crate::app::business_logic;
+use crate::app::validation::display_name_key;
+
+/// Storage keeping all data in memory. Every method locks the whole store,
+/// which makes it atomic and serializes concurrent requests.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryStorage {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    meetings: HashMap<Uuid, StoredMeeting>,
+    /// Meeting of every participant
+    users: HashMap<Uuid, Uuid>,
+    accounts: HashMap<Uuid, business_logic::Account>,
+    /// Id of the next history entry, shared by all meetings
+    next_history_id: i64,
+}
+
+#[derive(Debug)]
+struct StoredMeeting {
+    meeting: business_logic::Meeting,
+    version: i64,
+    /// Participants in the order they joined
+    participants: Vec<business_logic::User>,
+    proposed_dates: Vec<business_logic::ProposedDate>,
+    votes: Vec<business_logic::ParticipantVote>,
+    comments: Vec<business_logic::MeetingComment>,
+    /// Bitmaps of free slots by participant
+    availability: HashMap<Uuid, Vec<u8>>,
+    history: Vec<models::HistoryEntry>,
+}
+
+impl MemoryStorage {
+    fn state(&self) -> Result<MutexGuard<'_, State>> {
+        self.state
+            .lock()
+            .map_err(|_| anyhow!("in-memory storage is poisoned"))
+    }
+}
+
+impl State {
+    /// Inserts meeting together with its participants. The meeting has
+    /// neither proposed dates nor votes.
+    fn insert_meeting(
+        &mut self,
+        meeting: &business_logic::Meeting,
+        participants: Vec<business_logic::User>,
+    ) -> &mut StoredMeeting {
+        for participant in &participants {
+            self.users.insert(participant.id, meeting.id);
+        }
+        let stored = StoredMeeting {
+            meeting: meeting.clone(),
+            version: 1,
+            participants,
+            proposed_dates: Vec::new(),
+            votes: Vec::new(),
+            comments: Vec::new(),
+            availability: HashMap::new(),
+            history: Vec::new(),
+        };
+        self.meetings
+            .entry(meeting.id)
+            .insert_entry(stored)
+            .into_mut()
+    }
+
+    /// Increments version of meeting. If `expected_version` is given, fails
+    /// unless it is the current version.
+    fn bump_meeting_version(
+        &mut self,
+        meeting_id: Uuid,
+        expected_version: Option<i64>,
+    ) -> Result<&mut StoredMeeting, VersionedUpdateError> {
+        let meeting = self
+            .meetings
+            .get_mut(&meeting_id)
+            .ok_or(VersionedUpdateError::StaleVersion)?;
+        if expected_version.is_some_and(|expected| expected != meeting.version) {
+            debug!(?expected_version, "Meeting version is stale");
+            return Err(VersionedUpdateError::StaleVersion);
+        }
+        meeting.version += 1;
+        Ok(meeting)
+    }
+
+    fn record_history(
+        &mut self,
+        meeting_id: Uuid,
+        actor_id: Option<Uuid>,
+        action: models::HistoryAction,
+        proposed_date_id: Option<Uuid>,
+        old_value: Option<serde_json::Value>,
+        new_value: Option<serde_json::Value>,
+    ) {
+        self.next_history_id += 1;
+        let entry = models::HistoryEntry {
+            id: self.next_history_id,
+            actor_id,
+            recorded_at: OffsetDateTime::now_utc(),
+            action,
+            proposed_date_id,
+            old_value,
+            new_value,
+        };
+        if let Some(meeting) = self.meetings.get_mut(&meeting_id) {
+            meeting.history.push(entry);
+        }
+    }
+}
+
+impl StoredMeeting {
+    fn participant(&self, user_id: Uuid) -> Option<&business_logic::User> {
+        self.participants
+            .iter()
+            .find(|participant| participant.id == user_id)
+    }
+
+    fn proposed_date(&self, date_id: Uuid) -> Option<&business_logic::ProposedDate> {
+        self.proposed_dates.iter().find(|date| date.id == date_id)
+    }
+
+    fn is_proposed(&self, option: DateOption) -> bool {
+        self.proposed_dates.iter().any(|date| date.option == option)
+    }
+
+    /// Participants in order in which they joined, ties are broken by id.
+    fn sorted_participants(&self) -> Vec<&business_logic::User> {
+        let mut participants: Vec<_> = self.participants.iter().collect();
+        participants.sort_by_key(|participant| (participant.joined_at, participant.id));
+        participants
+    }
+
+    /// Proposed dates in chronological order in meeting's timezone.
+    fn sorted_proposed_dates(&self) -> Result<Vec<&business_logic::ProposedDate>> {
+        let mut dates: Vec<_> = self.proposed_dates.iter().collect();
+        sort_proposed_dates(&mut dates, &self.meeting.timezone, |date| Some(date.option))?;
+        Ok(dates)
+    }
+
+    fn info(&self) -> models::MeetingInfo {
+        let meeting = &self.meeting;
+        models::MeetingInfo {
+            name: meeting.name.clone(),
+            description: meeting.description.clone(),
+            created_by: meeting.user_id,
+            created_at: meeting.created_at,
+            timezone: meeting.timezone.clone(),
+            has_availability_grid: meeting.availability_grid.is_some(),
+            settings: settings_model(&meeting.settings),
+            voting_closes_at: meeting.voting_closes_at,
+            voting_closed_at: meeting.voting_closed_at,
+            final_date_id: meeting.final_date_id,
+            version: self.version,
+        }
+    }
+
+    fn participants_dates_votes(&self) -> Result<models::ParticipantsDatesVotes> {
+        Ok(models::ParticipantsDatesVotes {
+            participants: self
+                .sorted_participants()
+                .into_iter()
+                .map(|participant| models::Participant {
+                    id: participant.id,
+                    name: participant.name.clone(),
+                    timezone: participant.timezone.clone(),
+                    required: participant.required,
+                    joined_at: participant.joined_at,
+                })
+                .collect(),
+            proposed_dates: self
+                .sorted_proposed_dates()?
+                .into_iter()
+                .map(|date| {
+                    let (day, starts_at, duration_minutes) = date.option.into_columns();
+                    models::ProposedDate {
+                        id: date.id,
+                        date: day,
+                        starts_at,
+                        duration_minutes,
+                        proposed_by: date.proposed_by,
+                        approved: date.approved,
+                    }
+                })
+                .collect(),
+            votes: self
+                .votes
+                .iter()
+                .map(|vote| models::ParticipantVote {
+                    proposed_date_id: vote.proposed_date_id,
+                    user_id: vote.user_id,
+                    vote: vote.vote.into(),
+                    comment: vote.comment.clone(),
+                })
+                .collect(),
+        })
+    }
+
+    fn grid(&self) -> Option<models::AvailabilityGrid> {
+        self.meeting.availability_grid.map(grid_model)
+    }
+
+    fn participant_availability(&self) -> Vec<models::ParticipantAvailability> {
+        self.availability
+            .iter()
+            .map(|(user_id, slots)| models::ParticipantAvailability {
+                user_id: *user_id,
+                slots: slots.clone(),
+            })
+            .collect()
+    }
+}
+
+fn settings_model(settings: &MeetingSettings) -> models::MeetingSettings {
+    models::MeetingSettings {
+        holiday_calendar: settings.holiday_calendar.clone(),
+        holiday_policy: settings.holiday_policy.into(),
+        exclude_weekends: settings.exclude_weekends,
+        participants_can_propose: settings.participants_can_propose,
+        proposals_require_approval: settings.proposals_require_approval,
+        hide_results: settings.hide_results,
+        anonymous_votes: settings.anonymous_votes,
+        auto_finalize: settings.auto_finalize,
+        min_yes_votes: settings.min_yes_votes.map(i32::from),
+        max_capacity: settings.max_capacity.map(i32::from),
+        unique_participant_names: settings.unique_participant_names,
+    }
+}
+
+fn grid_model(grid: availability::AvailabilityGrid) -> models::AvailabilityGrid {
+    models::AvailabilityGrid {
+        first_day: grid.first_day,
+        last_day: grid.last_day,
+        day_starts_at: grid.day_starts_at,
+        day_ends_at: grid.day_ends_at,
+        slot_minutes: grid.slot_minutes.into(),
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    async fn get_meeting_info(&self, id: Uuid) -> Result<Option<models::MeetingInfo>> {
+        let state = self.state()?;
+        Ok(state.meetings.get(&id).map(StoredMeeting::info))
+    }
+
+    async fn get_meeting_snapshot(&self, id: Uuid) -> Result<Option<models::MeetingSnapshot>> {
+        let state = self.state()?;
+        let Some(meeting) = state.meetings.get(&id) else {
+            return Ok(None);
+        };
+        let mut comments: Vec<_> = meeting
+            .comments
+            .iter()
+            .map(|comment| models::MeetingComment {
+                message: comment.message.clone(),
+                written_by: comment.user_id,
+                posted_at: comment.posted_at,
+            })
+            .collect();
+        comments.sort_by_key(|comment| Reverse(comment.posted_at));
+        Ok(Some(models::MeetingSnapshot {
+            info: meeting.info(),
+            comments,
+            participants_dates_votes: meeting.participants_dates_votes()?,
+        }))
+    }
+
+    async fn create_new_meeting(
+        &self,
+        user: &business_logic::User,
+        meeting: &business_logic::Meeting,
+    ) -> Result<()> {
+        let mut state = self.state()?;
+        if state.meetings.contains_key(&meeting.id) || state.users.contains_key(&user.id) {
+            return Err(anyhow!("meeting or its creator already exists"));
+        }
+        state.insert_meeting(meeting, vec![user.clone()]);
+        Ok(())
+    }
+
+    async fn join_meeting(
+        &self,
+        user: &business_logic::User,
+        meeting_id: Uuid,
+    ) -> Result<(), JoinMeetingError> {
+        let mut state = self.state()?;
+        let Some(meeting) = state.meetings.get_mut(&meeting_id) else {
+            return Err(JoinMeetingError::NonexistentMeeting(meeting_id));
+        };
+        if meeting.meeting.settings.unique_participant_names {
+            let key = display_name_key(&user.name);
+            if meeting
+                .participants
+                .iter()
+                .any(|participant| display_name_key(&participant.name) == key)
+            {
+                return Err(JoinMeetingError::DuplicateName(user.name.clone()));
+            }
+        }
+        if user.account_id.is_some()
+            && meeting
+                .participants
+                .iter()
+                .any(|participant| participant.account_id == user.account_id)
+        {
+            return Err(JoinMeetingError::AlreadyJoined);
+        }
+
+        meeting.participants.push(user.clone());
+        state.users.insert(user.id, meeting_id);
+        Ok(())
+    }
+
+    async fn post_comment(&self, meeting_comment: &business_logic::MeetingComment) -> Result<()> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get_mut(&meeting_comment.meeting_id)
+            .context("meeting does not exist")?;
+        meeting.comments.push(meeting_comment.clone());
+        state.record_history(
+            meeting_comment.meeting_id,
+            Some(meeting_comment.user_id),
+            models::HistoryAction::CommentPosted,
+            None,
+            None,
+            Some(json!({ "id": meeting_comment.id, "message": meeting_comment.message })),
+        );
+        Ok(())
+    }
+
+    async fn add_proposed_date(
+        &self,
+        proposed_date: &business_logic::ProposedDate,
+        expected_version: i64,
+    ) -> Result<(), AddProposedDateError> {
+        self.add_proposed_dates(
+            proposed_date.meeting_id,
+            std::slice::from_ref(proposed_date),
+            expected_version,
+        )
+        .await
+    }
+
+    async fn add_proposed_dates(
+        &self,
+        meeting_id: Uuid,
+        proposed_dates: &[business_logic::ProposedDate],
+        expected_version: i64,
+    ) -> Result<(), AddProposedDateError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get(&meeting_id)
+            .ok_or(AddProposedDateError::StaleVersion)?;
+        if meeting.version != expected_version {
+            return Err(AddProposedDateError::StaleVersion);
+        }
+        for (i, proposed_date) in proposed_dates.iter().enumerate() {
+            let repeated = proposed_dates[..i]
+                .iter()
+                .any(|date| date.option == proposed_date.option);
+            if repeated || meeting.is_proposed(proposed_date.option) {
+                debug!(?proposed_date, "Date is already proposed");
+                return Err(AddProposedDateError::AlreadyProposed);
+            }
+        }
+
+        let meeting = state.bump_meeting_version(meeting_id, Some(expected_version))?;
+        meeting.proposed_dates.extend_from_slice(proposed_dates);
+        for proposed_date in proposed_dates {
+            state.record_history(
+                meeting_id,
+                proposed_date.proposed_by,
+                models::HistoryAction::DateProposed,
+                Some(proposed_date.id),
+                None,
+                Some(proposed_date_value(
+                    proposed_date.option,
+                    proposed_date.approved,
+                )),
+            );
+        }
+        Ok(())
+    }
+
+    async fn get_meeting_availability(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<models::MeetingAvailability>> {
+        let state = self.state()?;
+        Ok(state
+            .meetings
+            .get(&id)
+            .map(|meeting| models::MeetingAvailability {
+                timezone: meeting.meeting.timezone.clone(),
+                grid: meeting.grid(),
+                participants: meeting.participant_availability(),
+            }))
+    }
+
+    async fn get_availability_grid(
+        &self,
+        meeting_id: Uuid,
+    ) -> Result<Option<models::AvailabilityGrid>> {
+        let state = self.state()?;
+        Ok(state
+            .meetings
+            .get(&meeting_id)
+            .and_then(StoredMeeting::grid))
+    }
+
+    async fn set_availability(
+        &self,
+        availability: &business_logic::ParticipantAvailability,
+    ) -> Result<(), SetAvailabilityError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get_mut(&availability.meeting_id)
+            .filter(|meeting| meeting.participant(availability.user_id).is_some())
+            .ok_or(SetAvailabilityError::NotParticipant)?;
+        meeting
+            .availability
+            .insert(availability.user_id, availability.slots.to_bytes());
+        Ok(())
+    }
+
+    async fn get_proposed_date_status(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+    ) -> Result<Option<models::ProposedDateStatus>> {
+        let state = self.state()?;
+        Ok(state
+            .meetings
+            .get(&meeting_id)
+            .and_then(|meeting| meeting.proposed_date(date_id))
+            .map(|date| models::ProposedDateStatus {
+                approved: date.approved,
+            }))
+    }
+
+    async fn approve_proposed_date(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get(&meeting_id)
+            .ok_or(VersionedUpdateError::StaleVersion)?;
+        if meeting.version != expected_version {
+            return Err(VersionedUpdateError::StaleVersion);
+        }
+        let Some(date) = meeting.proposed_date(date_id) else {
+            return Ok(false);
+        };
+        let approved = date.approved;
+
+        let meeting = state.bump_meeting_version(meeting_id, Some(expected_version))?;
+        if !approved {
+            for date in meeting.proposed_dates.iter_mut() {
+                if date.id == date_id {
+                    date.approved = true;
+                }
+            }
+            state.record_history(
+                meeting_id,
+                Some(actor_id),
+                models::HistoryAction::DateApproved,
+                Some(date_id),
+                Some(json!({ "approved": false })),
+                Some(json!({ "approved": true })),
+            );
+        }
+        Ok(true)
+    }
+
+    async fn delete_pending_proposed_date(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get(&meeting_id)
+            .ok_or(VersionedUpdateError::StaleVersion)?;
+        if meeting.version != expected_version {
+            return Err(VersionedUpdateError::StaleVersion);
+        }
+        let Some(position) = meeting
+            .proposed_dates
+            .iter()
+            .position(|date| date.id == date_id && !date.approved)
+        else {
+            return Ok(false);
+        };
+
+        let meeting = state.bump_meeting_version(meeting_id, Some(expected_version))?;
+        let deleted = meeting.proposed_dates.remove(position);
+        meeting
+            .votes
+            .retain(|vote| vote.proposed_date_id != deleted.id);
+        state.record_history(
+            meeting_id,
+            Some(actor_id),
+            models::HistoryAction::DateRejected,
+            Some(date_id),
+            Some(proposed_date_value(deleted.option, deleted.approved)),
+            None,
+        );
+        Ok(true)
+    }
+
+    async fn cast_vote(
+        &self,
+        meeting_id: Uuid,
+        vote: &business_logic::ParticipantVote,
+    ) -> Result<(), CastVoteError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get_mut(&meeting_id)
+            .context("meeting does not exist")?;
+        if business_logic::is_voting_closed(
+            meeting.meeting.voting_closed_at,
+            meeting.meeting.voting_closes_at,
+        ) {
+            debug!("Voting is closed");
+            return Err(CastVoteError::VotingClosed);
+        }
+        let previous_vote = match meeting.votes.iter_mut().find(|previous| {
+            previous.proposed_date_id == vote.proposed_date_id && previous.user_id == vote.user_id
+        }) {
+            Some(previous) => Some(std::mem::replace(previous, vote.clone())),
+            None => {
+                meeting.votes.push(vote.clone());
+                None
+            }
+        };
+        state.record_history(
+            meeting_id,
+            Some(vote.user_id),
+            models::HistoryAction::VoteCast,
+            Some(vote.proposed_date_id),
+            previous_vote.map(|previous| vote_value(previous.vote, previous.comment)),
+            Some(vote_value(vote.vote, vote.comment.clone())),
+        );
+        Ok(())
+    }
+
+    async fn close_voting(
+        &self,
+        meeting_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get(&meeting_id)
+            .ok_or(VersionedUpdateError::StaleVersion)?;
+        if meeting.version != expected_version {
+            return Err(VersionedUpdateError::StaleVersion);
+        }
+        if meeting.meeting.voting_closed_at.is_some() {
+            return Ok(false);
+        }
+
+        let meeting = state.bump_meeting_version(meeting_id, Some(expected_version))?;
+        meeting.meeting.voting_closed_at = Some(OffsetDateTime::now_utc());
+        state.record_history(
+            meeting_id,
+            Some(actor_id),
+            models::HistoryAction::VotingClosed,
+            None,
+            None,
+            None,
+        );
+        Ok(true)
+    }
+
+    async fn get_meeting_votes(&self, meeting_id: Uuid) -> Result<models::MeetingVotes> {
+        let state = self.state()?;
+        let Some(meeting) = state.meetings.get(&meeting_id) else {
+            return Ok(models::MeetingVotes {
+                dates: Vec::new(),
+                votes: Vec::new(),
+                required_participants: Vec::new(),
+            });
+        };
+        let dates = meeting
+            .sorted_proposed_dates()?
+            .into_iter()
+            .filter(|date| date.approved)
+            .map(|date| date.id)
+            .collect();
+        let votes = meeting
+            .votes
+            .iter()
+            .filter(|vote| {
+                meeting
+                    .proposed_date(vote.proposed_date_id)
+                    .is_some_and(|date| date.approved)
+            })
+            .map(|vote| models::DateVote {
+                proposed_date_id: vote.proposed_date_id,
+                user_id: vote.user_id,
+                vote: vote.vote.into(),
+            })
+            .collect();
+        let required_participants = meeting
+            .participants
+            .iter()
+            .filter(|participant| participant.required)
+            .map(|participant| participant.id)
+            .collect();
+        Ok(models::MeetingVotes {
+            dates,
+            votes,
+            required_participants,
+        })
+    }
+
+    async fn update_meeting_details(
+        &self,
+        meeting_id: Uuid,
+        actor_id: Uuid,
+        update: &business_logic::MeetingDetailsUpdate,
+        expected_version: i64,
+    ) -> Result<(), VersionedUpdateError> {
+        let mut state = self.state()?;
+        let meeting = &mut state
+            .bump_meeting_version(meeting_id, Some(expected_version))?
+            .meeting;
+        let old_details = models::MeetingDetails {
+            name: meeting.name.clone(),
+            description: meeting.description.clone(),
+        };
+        if let Some(ref name) = update.name {
+            meeting.name = name.clone();
+        }
+        if let Some(ref description) = update.description {
+            meeting.description = description.clone();
+        }
+        let new_details = models::MeetingDetails {
+            name: meeting.name.clone(),
+            description: meeting.description.clone(),
+        };
+        state.record_history(
+            meeting_id,
+            Some(actor_id),
+            models::HistoryAction::DetailsUpdated,
+            None,
+            Some(details_value(old_details)),
+            Some(details_value(new_details)),
+        );
+        Ok(())
+    }
+
+    async fn set_participant_required(
+        &self,
+        meeting_id: Uuid,
+        participant_id: Uuid,
+        actor_id: Uuid,
+        required: bool,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get(&meeting_id)
+            .ok_or(VersionedUpdateError::StaleVersion)?;
+        if meeting.version != expected_version {
+            return Err(VersionedUpdateError::StaleVersion);
+        }
+        let Some(participant) = meeting.participant(participant_id) else {
+            return Ok(false);
+        };
+        let was_required = participant.required;
+
+        let meeting = state.bump_meeting_version(meeting_id, Some(expected_version))?;
+        for participant in meeting.participants.iter_mut() {
+            if participant.id == participant_id {
+                participant.required = required;
+            }
+        }
+        state.record_history(
+            meeting_id,
+            Some(actor_id),
+            models::HistoryAction::ParticipantRequiredChanged,
+            None,
+            Some(required_value(participant_id, was_required)),
+            Some(required_value(participant_id, required)),
+        );
+        Ok(true)
+    }
+
+    async fn finalize_meeting(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Option<Uuid>,
+        expected_version: Option<i64>,
+    ) -> Result<bool, VersionedUpdateError> {
+        let mut state = self.state()?;
+        let meeting = state
+            .meetings
+            .get(&meeting_id)
+            .ok_or(VersionedUpdateError::StaleVersion)?;
+        if expected_version.is_some_and(|expected| expected != meeting.version) {
+            return Err(VersionedUpdateError::StaleVersion);
+        }
+        if meeting.meeting.final_date_id.is_some() {
+            return Ok(false);
+        }
+
+        let meeting = &mut state
+            .bump_meeting_version(meeting_id, expected_version)?
+            .meeting;
+        meeting.final_date_id = Some(date_id);
+        if meeting.voting_closed_at.is_none() {
+            // Voting of meetings finalized after their deadline closed at the deadline
+            let now = OffsetDateTime::now_utc();
+            let closed_at = meeting
+                .voting_closes_at
+                .map_or(now, |deadline| deadline.min(now));
+            meeting.voting_closed_at = Some(closed_at);
+        }
+        state.record_history(
+            meeting_id,
+            actor_id,
+            models::HistoryAction::MeetingFinalized,
+            Some(date_id),
+            None,
+            None,
+        );
+        Ok(true)
+    }
+
+    async fn is_meeting_participant(&self, meeting_id: Uuid, user_id: Uuid) -> Result<bool> {
+        let state = self.state()?;
+        Ok(state.users.get(&user_id) == Some(&meeting_id))
+    }
+
+    async fn get_meeting_history(&self, meeting_id: Uuid) -> Result<Vec<models::HistoryEntry>> {
+        let state = self.state()?;
+        Ok(state
+            .meetings
+            .get(&meeting_id)
+            .map(|meeting| meeting.history.clone())
+            .unwrap_or_default())
+    }
+
+    async fn get_user_secret_token(&self, user_id: Uuid) -> Result<Option<Uuid>> {
+        let state = self.state()?;
+        let token = state
+            .users
+            .get(&user_id)
+            .and_then(|meeting_id| state.meetings.get(meeting_id))
+            .and_then(|meeting| meeting.participant(user_id))
+            .map(|participant| participant.secret_token);
+        Ok(token)
+    }
+
+    async fn create_account(&self, account: &business_logic::Account) -> Result<()> {
+        let mut state = self.state()?;
+        if state.accounts.contains_key(&account.id) {
+            return Err(anyhow!("account `{}` already exists", account.id));
+        }
+        state.accounts.insert(account.id, account.clone());
+        Ok(())
+    }
+
+    async fn get_account_secret_token(&self, account_id: Uuid) -> Result<Option<Uuid>> {
+        let state = self.state()?;
+        Ok(state
+            .accounts
+            .get(&account_id)
+            .map(|account| account.secret_token))
+    }
+
+    async fn get_account_meetings(&self, account_id: Uuid) -> Result<Vec<models::AccountMeeting>> {
+        let state = self.state()?;
+        let mut meetings: Vec<_> = state
+            .meetings
+            .values()
+            .flat_map(|meeting| {
+                meeting
+                    .participants
+                    .iter()
+                    .filter(|participant| participant.account_id == Some(account_id))
+                    .map(|participant| models::AccountMeeting {
+                        meeting_id: meeting.meeting.id,
+                        name: meeting.meeting.name.clone(),
+                        created_at: meeting.meeting.created_at,
+                        created_by: meeting.meeting.user_id,
+                        user_id: participant.id,
+                        user_secret_token: participant.secret_token,
+                    })
+            })
+            .collect();
+        meetings.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then(a.meeting_id.cmp(&b.meeting_id))
+        });
+        Ok(meetings)
+    }
+
+    async fn link_account(
+        &self,
+        account_id: Uuid,
+        meeting_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        let mut state = self.state()?;
+        let Some(meeting) = state.meetings.get_mut(&meeting_id) else {
+            return Ok(false);
+        };
+        let linked_elsewhere = meeting.participants.iter().any(|participant| {
+            participant.account_id == Some(account_id) && participant.id != user_id
+        });
+        let Some(participant) = meeting
+            .participants
+            .iter_mut()
+            .find(|participant| participant.id == user_id)
+        else {
+            return Ok(false);
+        };
+        if linked_elsewhere || participant.account_id.is_some_and(|id| id != account_id) {
+            return Ok(false);
+        }
+        participant.account_id = Some(account_id);
+        Ok(true)
+    }
+
+    async fn meeting_exists(&self, meeting_id: Uuid) -> Result<bool> {
+        let state = self.state()?;
+        Ok(state.meetings.contains_key(&meeting_id))
+    }
+
+    async fn get_meeting_export(&self, id: Uuid) -> Result<Option<models::MeetingExport>> {
+        let state = self.state()?;
+        let Some(stored) = state.meetings.get(&id) else {
+            return Ok(None);
+        };
+        let meeting = &stored.meeting;
+        let mut comments: Vec<_> = stored
+            .comments
+            .iter()
+            .map(|comment| models::ExportedComment {
+                id: comment.id,
+                user_id: comment.user_id,
+                message: comment.message.clone(),
+                posted_at: comment.posted_at,
+            })
+            .collect();
+        comments.sort_by_key(|comment| comment.posted_at);
+        let models::ParticipantsDatesVotes {
+            participants,
+            proposed_dates,
+            votes,
+        } = stored.participants_dates_votes()?;
+
+        let export = models::MeetingExport {
+            meeting: models::ExportedMeeting {
+                id: meeting.id,
+                name: meeting.name.clone(),
+                description: meeting.description.clone(),
+                created_by: meeting.user_id,
+                created_at: meeting.created_at,
+                expires_at: meeting.expires_at,
+                timezone: meeting.timezone.clone(),
+                settings: settings_model(&meeting.settings),
+                voting_closes_at: meeting.voting_closes_at,
+                voting_closed_at: meeting.voting_closed_at,
+                final_date_id: meeting.final_date_id,
+            },
+            participants,
+            proposed_dates,
+            votes,
+            comments,
+            availability_grid: stored.grid(),
+            availability: stored.participant_availability(),
+        };
+        Ok(Some(export))
+    }
+
+    async fn import_meeting(
+        &self,
+        import: &business_logic::MeetingImport,
+    ) -> Result<(), ImportMeetingError> {
+        let business_logic::MeetingImport {
+            meeting,
+            participants,
+            proposed_dates,
+            votes,
+            comments,
+            availability,
+        } = import;
+
+        let mut state = self.state()?;
+        let mut conflicts = Vec::new();
+        if state.meetings.contains_key(&meeting.id) {
+            conflicts.push(format!("meeting `{}` already exists", meeting.id));
+        }
+        for participant in participants {
+            if state.users.contains_key(&participant.id) {
+                conflicts.push(format!("participant `{}` already exists", participant.id));
+            }
+        }
+        for stored in state.meetings.values() {
+            for date in proposed_dates {
+                if stored.proposed_date(date.id).is_some() {
+                    conflicts.push(format!("proposed date `{}` already exists", date.id));
+                }
+            }
+            for comment in comments {
+                if stored.comments.iter().any(|stored| stored.id == comment.id) {
+                    conflicts.push(format!("comment `{}` already exists", comment.id));
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(ImportMeetingError::Conflict(conflicts.join(", ")));
+        }
+
+        let stored = state.insert_meeting(meeting, participants.to_vec());
+        stored.proposed_dates = proposed_dates.clone();
+        stored.votes = votes.clone();
+        stored.comments = comments.clone();
+        stored.availability = availability
+            .iter()
+            .map(|availability| (availability.user_id, availability.slots.to_bytes()))
+            .collect();
+        Ok(())
+    }
+}
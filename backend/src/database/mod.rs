@@ -1,186 +1,193 @@
+//! Storage of meetings. Handlers access data only through [`Storage`],
+//! which is implemented for Postgres, for SQLite when `sqlite` feature is
+//! enabled and for an in-memory store.
+
+pub(crate) mod memory;
 pub(crate) mod models;
+mod postgres;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+use std::fmt;
 
-use anyhow::{self, Context, Result};
-use futures::future::TryFutureExt;
-use sqlx::PgPool;
-use tracing::{debug, trace, warn};
+use anyhow::{anyhow, Result};
+use axum::async_trait;
+use serde_json::{json, Value as JsonValue};
+use time_tz::timezones;
 use uuid::Uuid;
 
+use crate::api::common::{DateOption, Vote};
 use crate::app::business_logic;
 
-#[tracing::instrument(skip(pool))]
-pub(crate) async fn get_meeting_info(
-    id: Uuid,
-    pool: &PgPool,
-) -> Result<Option<models::MeetingInfo>> {
-    let query = r#"
-SELECT
-    meeting.name, meeting.description, users.id AS created_by, meeting.created_at
-FROM
-    meeting
-INNER JOIN users
-    ON meeting.user_id = users.id
-WHERE
-    meeting.id = $1
-"#;
-
-    debug!(?id, "Queering meeting from database");
-    let meeting = sqlx::query_as(query)
-        .bind(id)
-        .fetch_optional(pool)
-        .await
-        .context("Failed to query meeting from database")?;
-    debug!(?meeting, "Received meeting from database");
-    Ok(meeting)
-}
+pub(crate) use self::memory::MemoryStorage;
+pub(crate) use self::postgres::PostgresStorage;
+#[cfg(feature = "sqlite")]
+pub(crate) use self::sqlite::SqliteStorage;
 
-#[tracing::instrument(skip(pool))]
-pub(crate) async fn get_meeting_comments(
-    id: Uuid,
-    pool: &PgPool,
-) -> Result<Vec<models::MeetingComment>> {
-    let query = r#"
-SELECT
-    users.id AS written_by, meeting_comment.message, meeting_comment.posted_at
-FROM
-    meeting_comment
-INNER JOIN users
-    ON meeting_comment.user_id = users.id
-WHERE
-    meeting_comment.meeting_id = $1
-ORDER BY
-    meeting_comment.posted_at DESC
-"#;
-
-    debug!(?id, "Queering meeting comments from database");
-    let comments = sqlx::query_as(query)
-        .bind(id)
-        .fetch_all(pool)
-        .await
-        .context("Failed to query meeting comments from database")?;
-    debug!(?comments, "Received comments from database");
-    Ok(comments)
-}
+/// Data access used by handlers. Every method is atomic: it either applies
+/// all of its changes or none of them.
+#[async_trait]
+pub(crate) trait Storage: fmt::Debug + Send + Sync {
+    async fn get_meeting_info(&self, id: Uuid) -> Result<Option<models::MeetingInfo>>;
 
-#[tracing::instrument(skip(pool))]
-pub(crate) async fn get_meeting_participants_proposed_dates_votes(
-    id: Uuid,
-    pool: &PgPool,
-) -> Result<Vec<models::ParticipantsProposedDatesVotes>> {
-    let query = r#"
-SELECT
-    users.id AS user_id,
-    users.name,
-    proposed_date.id AS date_id,
-    proposed_date.date,
-    proposed_date_user_votes.vote,
-    proposed_date_user_votes.comment
-FROM
-    meeting_participants
-INNER JOIN users
-    ON meeting_participants.user_id = users.id
-FULL OUTER JOIN proposed_date_user_votes
-    ON meeting_participants.user_id = proposed_date_user_votes.user_id
-FULL OUTER JOIN proposed_date
-    ON proposed_date_user_votes.proposed_date_id = proposed_date.id
-WHERE
-    meeting_participants.meeting_id = $1 OR
-    proposed_date.meeting_id = $1
-"#;
-
-    debug!(
-        ?id,
-        "Queering meeting participants, proposed dates and their votes"
-    );
-
-    let data = sqlx::query_as(query).bind(id).fetch_all(pool).await?;
-    debug!(
-        ?data,
-        "Received meeting participants, proposed dates and their votes"
-    );
-    Ok(data)
-}
+    /// Returns meeting with its comments, participants, proposed dates and
+    /// votes as of a single point in time, so that changes made concurrently
+    /// are visible either in all of them or in none.
+    async fn get_meeting_snapshot(&self, id: Uuid) -> Result<Option<models::MeetingSnapshot>>;
 
-pub(crate) async fn create_new_meeting(
-    user: &business_logic::User,
-    meeting: &business_logic::Meeting,
-    pool: &PgPool,
-) -> Result<()> {
-    let insert_user_query = r#"
-INSERT INTO
-    users(id, secret_token, name)
-VALUES
-    ($1, $2, $3)
-"#;
-    let insert_meeting_query = r#"
-INSERT INTO
-    meeting(id, name, description, created_at, expires_at, user_id)
-VALUES
-    ($1, $2, $3, $4, $5, $6)
-"#;
-    let insert_meeting_participants_query = r#"
-INSERT INTO
-    meeting_participants(user_id, meeting_id)
-VALUES
-    ($1, $2)
-"#;
-
-    let insert_user = || async {
-        sqlx::query(insert_user_query)
-            .bind(user.id)
-            .bind(user.secret_token)
-            .bind(&user.name)
-            .execute(pool)
-            .await
-            .context("failed to insert into users")
-    };
-    let insert_meeting = || async {
-        sqlx::query(insert_meeting_query)
-            .bind(meeting.id)
-            .bind(&meeting.name)
-            .bind(&meeting.description)
-            .bind(meeting.created_at)
-            .bind(meeting.expires_at)
-            .bind(meeting.user_id)
-            .execute(pool)
-            .await
-            .context("failed to insert into meeting")
-    };
-    let insert_meeting_participants = || async {
-        sqlx::query(insert_meeting_participants_query)
-            .bind(user.id)
-            .bind(meeting.id)
-            .execute(pool)
-            .await
-            .context("failed to insert into meeting_participants")
-    };
-
-    debug!(?user, ?meeting, "Creating new meeting");
-    trace!("Starting transaction");
-    let transaction = pool.begin().await.context("failed to begin transaction")?;
-    if let Err(error) = insert_user()
-        .and_then(|_| async { insert_meeting().await })
-        .and_then(|_| async { insert_meeting_participants().await })
-        .await
-    {
-        match transaction.rollback().await {
-            Ok(_) => trace!(database_error = ?error, "rolled back transaction"),
-            Err(rollback_error) => {
-                warn!(database_error = ?error, ?rollback_error, "failed to rollback transaction");
-            }
-        }
+    /// Creates meeting with `user` as its creator and only participant.
+    async fn create_new_meeting(
+        &self,
+        user: &business_logic::User,
+        meeting: &business_logic::Meeting,
+    ) -> Result<()>;
 
-        debug!(database_error=?error, "Failed to create new meeting");
-        Err(error)
-    } else {
-        transaction
-            .commit()
-            .await
-            .context("failed to commit transaction")?;
-        trace!("Committed transaction");
-        debug!("Successfully created new meeting");
-        Ok(())
-    }
+    async fn join_meeting(
+        &self,
+        user: &business_logic::User,
+        meeting_id: Uuid,
+    ) -> Result<(), JoinMeetingError>;
+
+    async fn post_comment(&self, meeting_comment: &business_logic::MeetingComment) -> Result<()>;
+
+    async fn add_proposed_date(
+        &self,
+        proposed_date: &business_logic::ProposedDate,
+        expected_version: i64,
+    ) -> Result<(), AddProposedDateError>;
+
+    /// Inserts all proposed dates, or none of them if any is already proposed.
+    async fn add_proposed_dates(
+        &self,
+        meeting_id: Uuid,
+        proposed_dates: &[business_logic::ProposedDate],
+        expected_version: i64,
+    ) -> Result<(), AddProposedDateError>;
+
+    async fn get_meeting_availability(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<models::MeetingAvailability>>;
+
+    /// Returns `None` if meeting does not exist or is a poll.
+    async fn get_availability_grid(
+        &self,
+        meeting_id: Uuid,
+    ) -> Result<Option<models::AvailabilityGrid>>;
+
+    /// Inserts or replaces availability of a meeting participant.
+    async fn set_availability(
+        &self,
+        availability: &business_logic::ParticipantAvailability,
+    ) -> Result<(), SetAvailabilityError>;
+
+    /// Returns `None` if meeting has no such date.
+    async fn get_proposed_date_status(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+    ) -> Result<Option<models::ProposedDateStatus>>;
+
+    /// Returns `false` if meeting has no such date.
+    async fn approve_proposed_date(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError>;
+
+    /// Returns `false` if meeting has no such date awaiting approval.
+    async fn delete_pending_proposed_date(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError>;
+
+    /// Inserts vote, replacing participant's previous vote on the same date.
+    /// Fails if voting is closed when the vote would be stored, also when it
+    /// was closed after the caller checked it.
+    async fn cast_vote(
+        &self,
+        meeting_id: Uuid,
+        vote: &business_logic::ParticipantVote,
+    ) -> Result<(), CastVoteError>;
+
+    /// Returns `false` if voting was already closed.
+    async fn close_voting(
+        &self,
+        meeting_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError>;
+
+    /// Returns approved proposed dates in chronological order and votes
+    /// cast on them.
+    async fn get_meeting_votes(&self, meeting_id: Uuid) -> Result<models::MeetingVotes>;
+
+    async fn update_meeting_details(
+        &self,
+        meeting_id: Uuid,
+        actor_id: Uuid,
+        update: &business_logic::MeetingDetailsUpdate,
+        expected_version: i64,
+    ) -> Result<(), VersionedUpdateError>;
+
+    /// Returns `false` if meeting has no such participant.
+    async fn set_participant_required(
+        &self,
+        meeting_id: Uuid,
+        participant_id: Uuid,
+        actor_id: Uuid,
+        required: bool,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError>;
+
+    /// Sets final date of meeting and closes voting if it is still open.
+    /// Returns `false` if meeting was already finalized. Version is only
+    /// checked if `expected_version` is given. `actor_id` is `None` for
+    /// automatic finalization.
+    async fn finalize_meeting(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Option<Uuid>,
+        expected_version: Option<i64>,
+    ) -> Result<bool, VersionedUpdateError>;
+
+    async fn is_meeting_participant(&self, meeting_id: Uuid, user_id: Uuid) -> Result<bool>;
+
+    /// Returns history of meeting in the order it was recorded.
+    async fn get_meeting_history(&self, meeting_id: Uuid) -> Result<Vec<models::HistoryEntry>>;
+
+    /// Returns `None` if there is no such user.
+    async fn get_user_secret_token(&self, user_id: Uuid) -> Result<Option<Uuid>>;
+
+    async fn create_account(&self, account: &business_logic::Account) -> Result<()>;
+
+    /// Returns `None` if there is no such account.
+    async fn get_account_secret_token(&self, account_id: Uuid) -> Result<Option<Uuid>>;
+
+    /// Returns meetings created or joined by account, newest first.
+    async fn get_account_meetings(&self, account_id: Uuid) -> Result<Vec<models::AccountMeeting>>;
+
+    /// Links participant of meeting to account. Returns `false` if the
+    /// participant is linked to another account or the account already
+    /// takes part in the meeting as another participant.
+    async fn link_account(&self, account_id: Uuid, meeting_id: Uuid, user_id: Uuid)
+        -> Result<bool>;
+
+    async fn meeting_exists(&self, meeting_id: Uuid) -> Result<bool>;
+
+    async fn get_meeting_export(&self, id: Uuid) -> Result<Option<models::MeetingExport>>;
+
+    /// Inserts all rows of the imported meeting, keeping their original ids.
+    async fn import_meeting(
+        &self,
+        import: &business_logic::MeetingImport,
+    ) -> Result<(), ImportMeetingError>;
 }
 
 /// Error returned when joining a meeting as a participant fails.
@@ -192,157 +199,114 @@ pub(crate) enum JoinMeetingError {
     /// tried to join.
     #[error("meeting with id `{0}` does not exist")]
     NonexistentMeeting(Uuid),
+    /// Meeting requires unique display names and a participant with
+    /// the same name already joined.
+    #[error("participant named `{0}` already joined the meeting")]
+    DuplicateName(String),
+    /// Account of the joining user already takes part in the meeting.
+    #[error("account already takes part in the meeting")]
+    AlreadyJoined,
     /// Database operation failed.
     #[error(transparent)]
     Database(#[from] anyhow::Error),
 }
 
-#[tracing::instrument(skip(pool))]
-pub(crate) async fn join_meeting(
-    user: &business_logic::User,
-    meeting_id: Uuid,
-    pool: &PgPool,
-) -> Result<(), JoinMeetingError> {
-    let insert_user_query = r#"
-INSERT INTO
-    users(id, secret_token, name)
-VALUES
-    ($1, $2, $3)
-"#;
-    let insert_meeting_participants_query = r#"
-INSERT INTO
-    meeting_participants(user_id, meeting_id)
-VALUES
-    ($1, $2)
-"#;
-
-    let insert_user = || async {
-        sqlx::query(insert_user_query)
-            .bind(user.id)
-            .bind(user.secret_token)
-            .bind(&user.name)
-            .execute(pool)
-            .await
-            .context("failed to insert into users")
-    };
-    let insert_meeting_participants = || async {
-        sqlx::query(insert_meeting_participants_query)
-            .bind(user.id)
-            .bind(meeting_id)
-            .execute(pool)
-            .await
-            .context("failed to insert into meeting_participants")
-    };
-
-    debug!(participant_data=?user, ?meeting_id, "Creating new participant");
-    trace!("Starting transaction");
-    let transaction = pool.begin().await.context("failed to begin transaction")?;
-
-    // Check if meeting exists
-    // TODO: move this to the caller & later use as a middleware
-    match meeting_exists(meeting_id, pool).await {
-        Ok(true) => { /* Meeting exists, so we can procede with inserting user */ }
-        Ok(false) => return Err(JoinMeetingError::NonexistentMeeting(meeting_id)),
-        Err(error) => return Err(error.into()),
-    }
+/// Error returned when update of meeting that requires its current version
+/// fails.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum VersionedUpdateError {
+    /// Meeting was modified since the client read its version.
+    #[error("meeting version is stale")]
+    StaleVersion,
+    /// Database operation failed.
+    #[error(transparent)]
+    Database(#[from] anyhow::Error),
+}
 
-    if let Err(error) = insert_user()
-        .and_then(|_| async { insert_meeting_participants().await })
-        .await
-    {
-        match transaction.rollback().await {
-            Ok(_) => trace!(database_error = ?error, "rolled back transaction"),
-            Err(rollback_error) => {
-                warn!(database_error = ?error, ?rollback_error, "failed to rollback transaction");
-            }
+impl From<VersionedUpdateError> for AddProposedDateError {
+    fn from(value: VersionedUpdateError) -> Self {
+        match value {
+            VersionedUpdateError::StaleVersion => Self::StaleVersion,
+            VersionedUpdateError::Database(error) => Self::Database(error),
         }
-
-        debug!(database_error=?error, "Failed to add new meeting participant");
-        Err(error.into())
-    } else {
-        transaction
-            .commit()
-            .await
-            .context("failed to commit transaction")?;
-        trace!("Committed transaction");
-        debug!("Successfully added new participant");
-        Ok(())
     }
 }
 
-#[tracing::instrument(skip(pool))]
-pub(crate) async fn post_comment(
-    meeting_comment: &business_logic::MeetingComment,
-    pool: &PgPool,
+/// Error returned when adding a proposed date fails.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum AddProposedDateError {
+    /// The same date has already been proposed in this meeting.
+    #[error("date is already proposed")]
+    AlreadyProposed,
+    /// Meeting was modified since the client read its version.
+    #[error("meeting version is stale")]
+    StaleVersion,
+    /// Database operation failed.
+    #[error(transparent)]
+    Database(#[from] anyhow::Error),
+}
+
+/// Error returned when setting participant's availability fails.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum SetAvailabilityError {
+    /// User is not a participant of the meeting.
+    #[error("user is not a participant of the meeting")]
+    NotParticipant,
+    /// Database operation failed.
+    #[error(transparent)]
+    Database(#[from] anyhow::Error),
+}
+
+/// Error returned when casting a vote fails.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum CastVoteError {
+    /// Voting of the meeting is closed.
+    #[error("voting is closed")]
+    VotingClosed,
+    /// Database operation failed.
+    #[error(transparent)]
+    Database(#[from] anyhow::Error),
+}
+
+/// Error returned when importing a meeting fails.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ImportMeetingError {
+    /// Some of the imported rows already exist in the database.
+    ///
+    /// String data of this variant describes conflicting rows.
+    #[error("imported meeting conflicts with existing data: {0}")]
+    Conflict(String),
+    /// Database operation failed.
+    #[error(transparent)]
+    Database(#[from] anyhow::Error),
+}
+
+/// Sorts proposed dates chronologically in meeting's `timezone`, as
+/// [`DateOption::chronological_key`] orders them. Postgres sorts them in
+/// queries; SQLite has no timezone database, so other backends sort here.
+fn sort_proposed_dates<T>(
+    dates: &mut [T],
+    timezone: &str,
+    option: impl Fn(&T) -> Option<DateOption>,
 ) -> Result<()> {
-    let insert_meeting_comment_query = r#"
-INSERT INTO
-    meeting_comment(id, user_id, meeting_id, message, posted_at)
-VALUES
-    ($1, $2, $3, $4, $5)
-"#;
-
-    debug!("Inserting meeting comment into database");
-    sqlx::query(insert_meeting_comment_query)
-        .bind(meeting_comment.id)
-        .bind(meeting_comment.user_id)
-        .bind(meeting_comment.meeting_id)
-        .bind(&meeting_comment.message)
-        .bind(meeting_comment.posted_at)
-        .execute(pool)
-        .await
-        .context("failed to insert meeting into database")?;
-
-    debug!("Meeting comment inserted successfully");
+    let tz = timezones::get_by_name(timezone)
+        .ok_or_else(|| anyhow!("unknown meeting timezone `{timezone}`"))?;
+    dates.sort_by_cached_key(|date| option(date).map(|option| option.chronological_key(tz)));
     Ok(())
 }
 
-/// Returns `Some(secret_token)` of user with `user_id` from database.
-/// If user with provided `user_id` does not exist in the database this
-/// function will return `None`.
-pub(crate) async fn get_user_secret_token(user_id: Uuid, pool: &PgPool) -> Result<Option<Uuid>> {
-    let select_secret_token = r#"
-SELECT
-    secret_token
-FROM
-    users
-WHERE
-    id = $1
-"#;
-
-    debug!(?user_id, "Getting secret token of user");
-    let token = sqlx::query_as::<_, models::UserSecretToken>(select_secret_token)
-        .bind(user_id)
-        .fetch_optional(pool)
-        .await
-        .context("failed to get user's secret token")?
-        .map(models::UserSecretToken::into_token);
-
-    debug!(?token, "Received user's secret token");
-    Ok(token)
+fn details_value(details: models::MeetingDetails) -> JsonValue {
+    json!({ "name": details.name, "description": details.description })
+}
+
+fn proposed_date_value(option: DateOption, approved: bool) -> JsonValue {
+    json!({ "option": option, "approved": approved })
+}
+
+fn vote_value(vote: Vote, comment: Option<String>) -> JsonValue {
+    json!({ "vote": vote, "comment": comment })
 }
 
-/// Checks it meeting with provided ID exists. Must be executed inside
-/// transaction to avoid time-of-check-time-of-use bugs.
-pub(crate) async fn meeting_exists(meeting_id: Uuid, pool: &PgPool) -> Result<bool> {
-    let select_meeting_by_id = r#"
-SELECT
-    id
-FROM
-    meeting
-WHERE
-    meeting.id = $1
-"#;
-
-    debug!(?meeting_id, "Checking if meeting exists");
-
-    let exists = sqlx::query(select_meeting_by_id)
-        .bind(meeting_id)
-        .fetch_optional(pool)
-        .await
-        .with_context(|| format!("Failed to check if meeting with id `{meeting_id}` exists"))?
-        .is_some();
-
-    debug!(?exists, "Received status from database");
-    Ok(exists)
+fn required_value(participant_id: Uuid, required: bool) -> JsonValue {
+    json!({ "participant_id": participant_id, "required": required })
 }
@@ -1,5 +1,6 @@
+use serde_json::Value as JsonValue;
 use sqlx::FromRow;
-use time::{Date, OffsetDateTime};
+use time::{Date, OffsetDateTime, Time};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, FromRow)]
@@ -12,6 +13,45 @@ pub(crate) struct MeetingInfo {
     pub(crate) created_by: Uuid,
     /// Date and time of meeting creation
     pub(crate) created_at: OffsetDateTime,
+    /// IANA name of the timezone in which meeting takes place
+    pub(crate) timezone: String,
+    /// Whether meeting has availability grid
+    pub(crate) has_availability_grid: bool,
+    #[sqlx(flatten)]
+    pub(crate) settings: MeetingSettings,
+    /// Deadline after which participants can no longer vote
+    pub(crate) voting_closes_at: Option<OffsetDateTime>,
+    /// Date and time at which creator closed voting
+    pub(crate) voting_closed_at: Option<OffsetDateTime>,
+    /// Id of the proposed date chosen as the outcome of voting
+    pub(crate) final_date_id: Option<Uuid>,
+    /// Version of meeting, incremented by every change of its details
+    /// or dates
+    pub(crate) version: i64,
+}
+
+/// Settings columns of `meeting` table.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct MeetingSettings {
+    pub(crate) holiday_calendar: Option<String>,
+    pub(crate) holiday_policy: HolidayPolicy,
+    pub(crate) exclude_weekends: bool,
+    pub(crate) participants_can_propose: bool,
+    pub(crate) proposals_require_approval: bool,
+    pub(crate) hide_results: bool,
+    pub(crate) anonymous_votes: bool,
+    pub(crate) auto_finalize: bool,
+    pub(crate) min_yes_votes: Option<i32>,
+    pub(crate) max_capacity: Option<i32>,
+    pub(crate) unique_participant_names: bool,
+}
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "holiday_policy")]
+#[sqlx(rename_all = "lowercase")]
+pub(crate) enum HolidayPolicy {
+    Flag,
+    Reject,
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -34,19 +74,42 @@ pub(crate) enum Vote {
 }
 
 #[derive(Debug, Clone, FromRow)]
-pub(crate) struct ParticipantsProposedDatesVotes {
-    /// User id. May be NULL if this row contains date that no one has voted on.
-    pub(crate) user_id: Option<Uuid>,
-    /// User name. May be NULL <=> user_id is NULL
-    pub(crate) name: Option<String>,
-    /// Date id. May be NULL if this row contains user that has not voted on any date.
-    pub(crate) date_id: Option<Uuid>,
-    /// Date. May be NULL <=> date_id is NULL
-    pub(crate) date: Option<Date>,
-    /// Vote. May be NULL <=> date_id is NULL.
-    pub(crate) vote: Option<Vote>,
-    /// Optional vote comment. May be NOT NULL <=> date_id is NOT NULL
-    pub(crate) comment: Option<String>,
+pub(crate) struct AvailabilityGrid {
+    pub(crate) first_day: Date,
+    pub(crate) last_day: Date,
+    pub(crate) day_starts_at: Time,
+    pub(crate) day_ends_at: Time,
+    pub(crate) slot_minutes: i32,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ParticipantAvailability {
+    pub(crate) user_id: Uuid,
+    /// Bitmap of free slots, most significant bit first
+    pub(crate) slots: Vec<u8>,
+}
+
+/// Availability grid of a meeting together with availability
+/// submitted by its participants.
+#[derive(Debug, Clone)]
+pub(crate) struct MeetingAvailability {
+    /// IANA name of the timezone in which meeting takes place
+    pub(crate) timezone: String,
+    /// Grid of the meeting. `None` if meeting is a poll.
+    pub(crate) grid: Option<AvailabilityGrid>,
+    pub(crate) participants: Vec<ParticipantAvailability>,
+}
+
+/// Meeting in which an account takes part.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct AccountMeeting {
+    pub(crate) meeting_id: Uuid,
+    pub(crate) name: String,
+    pub(crate) created_at: OffsetDateTime,
+    pub(crate) created_by: Uuid,
+    /// Participant through which the account takes part in the meeting
+    pub(crate) user_id: Uuid,
+    pub(crate) user_secret_token: Uuid,
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -60,3 +123,165 @@ impl UserSecretToken {
         self.secret_token
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ExportedMeeting {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    /// Id of the user that created the meeting
+    pub(crate) created_by: Uuid,
+    pub(crate) created_at: OffsetDateTime,
+    pub(crate) expires_at: OffsetDateTime,
+    pub(crate) timezone: String,
+    #[sqlx(flatten)]
+    pub(crate) settings: MeetingSettings,
+    pub(crate) voting_closes_at: Option<OffsetDateTime>,
+    pub(crate) voting_closed_at: Option<OffsetDateTime>,
+    pub(crate) final_date_id: Option<Uuid>,
+}
+
+/// Participant of a meeting.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct Participant {
+    pub(crate) id: Uuid,
+    pub(crate) name: String,
+    pub(crate) timezone: Option<String>,
+    pub(crate) required: bool,
+    pub(crate) joined_at: OffsetDateTime,
+}
+
+/// Date proposed in a meeting, either a whole day or a time slot.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ProposedDate {
+    pub(crate) id: Uuid,
+    pub(crate) date: Option<Date>,
+    pub(crate) starts_at: Option<OffsetDateTime>,
+    pub(crate) duration_minutes: Option<i32>,
+    pub(crate) proposed_by: Option<Uuid>,
+    pub(crate) approved: bool,
+}
+
+/// Vote cast on one of meeting's proposed dates.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct DateVote {
+    pub(crate) proposed_date_id: Uuid,
+    pub(crate) user_id: Uuid,
+    pub(crate) vote: Vote,
+}
+
+/// Approved proposed dates of a meeting, in chronological order, together
+/// with votes cast on them.
+#[derive(Debug, Clone)]
+pub(crate) struct MeetingVotes {
+    pub(crate) dates: Vec<Uuid>,
+    pub(crate) votes: Vec<DateVote>,
+    /// Participants without whom meeting can not take place
+    pub(crate) required_participants: Vec<Uuid>,
+}
+
+/// Proposed date looked up by its id.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ProposedDateStatus {
+    pub(crate) approved: bool,
+}
+
+/// Vote of a participant, with its comment.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ParticipantVote {
+    pub(crate) proposed_date_id: Uuid,
+    pub(crate) user_id: Uuid,
+    pub(crate) vote: Vote,
+    pub(crate) comment: Option<String>,
+}
+
+/// Participants of a meeting, its proposed dates and votes cast on them.
+#[derive(Debug, Clone)]
+pub(crate) struct ParticipantsDatesVotes {
+    /// Participants in order in which they joined, participants that joined
+    /// at the same time are ordered by id
+    pub(crate) participants: Vec<Participant>,
+    /// Proposed dates in chronological order
+    pub(crate) proposed_dates: Vec<ProposedDate>,
+    pub(crate) votes: Vec<ParticipantVote>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct ExportedComment {
+    pub(crate) id: Uuid,
+    pub(crate) user_id: Uuid,
+    pub(crate) message: String,
+    pub(crate) posted_at: OffsetDateTime,
+}
+
+/// Meeting as shown to its viewers, read from a single snapshot of storage.
+#[derive(Debug, Clone)]
+pub(crate) struct MeetingSnapshot {
+    pub(crate) info: MeetingInfo,
+    /// Comments of the meeting, newest first
+    pub(crate) comments: Vec<MeetingComment>,
+    pub(crate) participants_dates_votes: ParticipantsDatesVotes,
+}
+
+/// All rows belonging to a single meeting.
+#[derive(Debug, Clone)]
+pub(crate) struct MeetingExport {
+    pub(crate) meeting: ExportedMeeting,
+    pub(crate) participants: Vec<Participant>,
+    pub(crate) proposed_dates: Vec<ProposedDate>,
+    pub(crate) votes: Vec<ParticipantVote>,
+    pub(crate) comments: Vec<ExportedComment>,
+    pub(crate) availability_grid: Option<AvailabilityGrid>,
+    pub(crate) availability: Vec<ParticipantAvailability>,
+}
+
+#[derive(Debug, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "meeting_history_action")]
+#[sqlx(rename_all = "snake_case")]
+pub(crate) enum HistoryAction {
+    DetailsUpdated,
+    DateProposed,
+    DateApproved,
+    DateRejected,
+    VoteCast,
+    CommentPosted,
+    ParticipantRequiredChanged,
+    VotingClosed,
+    MeetingFinalized,
+}
+
+/// Entry of meeting's append-only history.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct HistoryEntry {
+    pub(crate) id: i64,
+    /// User that made the change. `None` if user was deleted.
+    pub(crate) actor_id: Option<Uuid>,
+    pub(crate) recorded_at: OffsetDateTime,
+    pub(crate) action: HistoryAction,
+    pub(crate) proposed_date_id: Option<Uuid>,
+    pub(crate) old_value: Option<JsonValue>,
+    pub(crate) new_value: Option<JsonValue>,
+}
+
+/// Name and description columns of `meeting` table.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct MeetingDetails {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+}
+
+/// Vote as stored in `proposed_date_user_votes`.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct StoredVote {
+    pub(crate) vote: Vote,
+    pub(crate) comment: Option<String>,
+}
+
+/// Columns of proposed date deleted from `proposed_date`.
+#[derive(Debug, Clone, FromRow)]
+pub(crate) struct DeletedProposedDate {
+    pub(crate) date: Option<Date>,
+    pub(crate) starts_at: Option<OffsetDateTime>,
+    pub(crate) duration_minutes: Option<i32>,
+    pub(crate) approved: bool,
+}
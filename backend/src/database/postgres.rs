@@ -0,0 +1,2279 @@
+//! Storage keeping data in a Postgres database.
+//!
+//! Queries are checked against `database/schema.sql` at compile time. Their
+//! descriptions are cached in `backend/.sqlx`, so the crate builds without a
+//! running database; after changing a query or the schema, regenerate the
+//! cache with `cargo sqlx prepare` against a database with the new schema.
+
+use anyhow::{self, Context, Result};
+use axum::async_trait;
+use serde_json::{json, Value as JsonValue};
+use sqlx::{PgConnection, PgPool};
+use tracing::{debug, trace};
+use uuid::Uuid;
+
+use super::{
+    details_value, models, proposed_date_value, required_value, vote_value, AddProposedDateError,
+    CastVoteError, ImportMeetingError, JoinMeetingError, SetAvailabilityError, Storage,
+    VersionedUpdateError,
+};
+use crate::api::common::DateOption;
+use crate::app::business_logic;
+use crate::app::validation::display_name_key;
+
+/// Storage keeping data in Postgres database.
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresStorage {
+    pool: PgPool,
+}
+
+impl PostgresStorage {
+    pub(crate) fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Storage for PostgresStorage {
+    async fn get_meeting_info(&self, id: Uuid) -> Result<Option<models::MeetingInfo>> {
+        get_meeting_info(id, &self.pool).await
+    }
+
+    async fn get_meeting_snapshot(&self, id: Uuid) -> Result<Option<models::MeetingSnapshot>> {
+        get_meeting_snapshot(id, &self.pool).await
+    }
+
+    async fn create_new_meeting(
+        &self,
+        user: &business_logic::User,
+        meeting: &business_logic::Meeting,
+    ) -> Result<()> {
+        create_new_meeting(user, meeting, &self.pool).await
+    }
+
+    async fn join_meeting(
+        &self,
+        user: &business_logic::User,
+        meeting_id: Uuid,
+    ) -> Result<(), JoinMeetingError> {
+        join_meeting(user, meeting_id, &self.pool).await
+    }
+
+    async fn post_comment(&self, meeting_comment: &business_logic::MeetingComment) -> Result<()> {
+        post_comment(meeting_comment, &self.pool).await
+    }
+
+    async fn add_proposed_date(
+        &self,
+        proposed_date: &business_logic::ProposedDate,
+        expected_version: i64,
+    ) -> Result<(), AddProposedDateError> {
+        add_proposed_date(proposed_date, expected_version, &self.pool).await
+    }
+
+    async fn add_proposed_dates(
+        &self,
+        meeting_id: Uuid,
+        proposed_dates: &[business_logic::ProposedDate],
+        expected_version: i64,
+    ) -> Result<(), AddProposedDateError> {
+        add_proposed_dates(meeting_id, proposed_dates, expected_version, &self.pool).await
+    }
+
+    async fn get_meeting_availability(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<models::MeetingAvailability>> {
+        get_meeting_availability(id, &self.pool).await
+    }
+
+    async fn get_availability_grid(
+        &self,
+        meeting_id: Uuid,
+    ) -> Result<Option<models::AvailabilityGrid>> {
+        get_availability_grid(meeting_id, &self.pool).await
+    }
+
+    async fn set_availability(
+        &self,
+        availability: &business_logic::ParticipantAvailability,
+    ) -> Result<(), SetAvailabilityError> {
+        set_availability(availability, &self.pool).await
+    }
+
+    async fn get_proposed_date_status(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+    ) -> Result<Option<models::ProposedDateStatus>> {
+        get_proposed_date_status(meeting_id, date_id, &self.pool).await
+    }
+
+    async fn approve_proposed_date(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        approve_proposed_date(meeting_id, date_id, actor_id, expected_version, &self.pool).await
+    }
+
+    async fn delete_pending_proposed_date(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        delete_pending_proposed_date(meeting_id, date_id, actor_id, expected_version, &self.pool)
+            .await
+    }
+
+    async fn cast_vote(
+        &self,
+        meeting_id: Uuid,
+        vote: &business_logic::ParticipantVote,
+    ) -> Result<(), CastVoteError> {
+        cast_vote(meeting_id, vote, &self.pool).await
+    }
+
+    async fn close_voting(
+        &self,
+        meeting_id: Uuid,
+        actor_id: Uuid,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        close_voting(meeting_id, actor_id, expected_version, &self.pool).await
+    }
+
+    async fn get_meeting_votes(&self, meeting_id: Uuid) -> Result<models::MeetingVotes> {
+        get_meeting_votes(meeting_id, &self.pool).await
+    }
+
+    async fn update_meeting_details(
+        &self,
+        meeting_id: Uuid,
+        actor_id: Uuid,
+        update: &business_logic::MeetingDetailsUpdate,
+        expected_version: i64,
+    ) -> Result<(), VersionedUpdateError> {
+        update_meeting_details(meeting_id, actor_id, update, expected_version, &self.pool).await
+    }
+
+    async fn set_participant_required(
+        &self,
+        meeting_id: Uuid,
+        participant_id: Uuid,
+        actor_id: Uuid,
+        required: bool,
+        expected_version: i64,
+    ) -> Result<bool, VersionedUpdateError> {
+        set_participant_required(
+            meeting_id,
+            participant_id,
+            actor_id,
+            required,
+            expected_version,
+            &self.pool,
+        )
+        .await
+    }
+
+    async fn finalize_meeting(
+        &self,
+        meeting_id: Uuid,
+        date_id: Uuid,
+        actor_id: Option<Uuid>,
+        expected_version: Option<i64>,
+    ) -> Result<bool, VersionedUpdateError> {
+        finalize_meeting(meeting_id, date_id, actor_id, expected_version, &self.pool).await
+    }
+
+    async fn is_meeting_participant(&self, meeting_id: Uuid, user_id: Uuid) -> Result<bool> {
+        is_meeting_participant(meeting_id, user_id, &self.pool).await
+    }
+
+    async fn get_meeting_history(&self, meeting_id: Uuid) -> Result<Vec<models::HistoryEntry>> {
+        get_meeting_history(meeting_id, &self.pool).await
+    }
+
+    async fn get_user_secret_token(&self, user_id: Uuid) -> Result<Option<Uuid>> {
+        get_user_secret_token(user_id, &self.pool).await
+    }
+
+    async fn create_account(&self, account: &business_logic::Account) -> Result<()> {
+        create_account(account, &self.pool).await
+    }
+
+    async fn get_account_secret_token(&self, account_id: Uuid) -> Result<Option<Uuid>> {
+        get_account_secret_token(account_id, &self.pool).await
+    }
+
+    async fn get_account_meetings(&self, account_id: Uuid) -> Result<Vec<models::AccountMeeting>> {
+        get_account_meetings(account_id, &self.pool).await
+    }
+
+    async fn link_account(
+        &self,
+        account_id: Uuid,
+        meeting_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool> {
+        link_account(account_id, meeting_id, user_id, &self.pool).await
+    }
+
+    async fn meeting_exists(&self, meeting_id: Uuid) -> Result<bool> {
+        meeting_exists(meeting_id, &self.pool).await
+    }
+
+    async fn get_meeting_export(&self, id: Uuid) -> Result<Option<models::MeetingExport>> {
+        get_meeting_export(id, &self.pool).await
+    }
+
+    async fn import_meeting(
+        &self,
+        import: &business_logic::MeetingImport,
+    ) -> Result<(), ImportMeetingError> {
+        import_meeting(import, &self.pool).await
+    }
+}
+
+/// Builds [`models::MeetingSettings`] from a row selected by a checked query
+/// macro, which can not fill flattened fields.
+macro_rules! meeting_settings {
+    ($row:ident) => {
+        models::MeetingSettings {
+            holiday_calendar: $row.holiday_calendar,
+            holiday_policy: $row.holiday_policy,
+            exclude_weekends: $row.exclude_weekends,
+            participants_can_propose: $row.participants_can_propose,
+            proposals_require_approval: $row.proposals_require_approval,
+            hide_results: $row.hide_results,
+            anonymous_votes: $row.anonymous_votes,
+            auto_finalize: $row.auto_finalize,
+            min_yes_votes: $row.min_yes_votes,
+            max_capacity: $row.max_capacity,
+            unique_participant_names: $row.unique_participant_names,
+        }
+    };
+}
+
+#[tracing::instrument(skip(pool))]
+async fn get_meeting_info(id: Uuid, pool: &PgPool) -> Result<Option<models::MeetingInfo>> {
+    debug!(?id, "Queering meeting from database");
+    let mut connection = pool
+        .acquire()
+        .await
+        .context("failed to acquire connection")?;
+    let meeting = select_meeting_info(id, &mut connection).await?;
+    debug!(?meeting, "Received meeting from database");
+    Ok(meeting)
+}
+
+/// Returns meeting together with its comments, participants, proposed dates
+/// and votes, all read from a single snapshot of the database.
+#[tracing::instrument(skip(pool))]
+async fn get_meeting_snapshot(id: Uuid, pool: &PgPool) -> Result<Option<models::MeetingSnapshot>> {
+    debug!(?id, "Queering meeting snapshot from database");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+    // In repeatable read transaction every query sees the snapshot taken by
+    // the first one, so a vote or comment committed in between can not show
+    // up in only some of them.
+    sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *transaction)
+        .await
+        .context("failed to set transaction isolation level")?;
+    let Some(info) = select_meeting_info(id, &mut transaction).await? else {
+        debug!("Meeting does not exist");
+        return Ok(None);
+    };
+    let comments = select_meeting_comments(id, &mut transaction).await?;
+    let participants_dates_votes = select_participants_dates_votes(id, &mut transaction).await?;
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+
+    let snapshot = models::MeetingSnapshot {
+        info,
+        comments,
+        participants_dates_votes,
+    };
+    debug!(?snapshot, "Received meeting snapshot from database");
+    Ok(Some(snapshot))
+}
+
+async fn select_meeting_info(
+    id: Uuid,
+    connection: &mut PgConnection,
+) -> Result<Option<models::MeetingInfo>> {
+    let meeting = sqlx::query!(
+        r#"
+SELECT
+    meeting.name,
+    meeting.description,
+    users.id AS created_by,
+    meeting.created_at,
+    meeting.timezone,
+    EXISTS (
+        SELECT 1 FROM availability_grid WHERE availability_grid.meeting_id = meeting.id
+    ) AS "has_availability_grid!",
+    meeting.holiday_calendar,
+    meeting.holiday_policy AS "holiday_policy: models::HolidayPolicy",
+    meeting.exclude_weekends,
+    meeting.participants_can_propose,
+    meeting.proposals_require_approval,
+    meeting.hide_results,
+    meeting.anonymous_votes,
+    meeting.auto_finalize,
+    meeting.min_yes_votes,
+    meeting.max_capacity,
+    meeting.unique_participant_names,
+    meeting.voting_closes_at,
+    meeting.voting_closed_at,
+    meeting_final_date.proposed_date_id AS "final_date_id?",
+    meeting.version
+FROM
+    meeting
+INNER JOIN users
+    ON meeting.user_id = users.id
+LEFT JOIN meeting_final_date
+    ON meeting_final_date.meeting_id = meeting.id
+WHERE
+    meeting.id = $1
+"#,
+        id
+    )
+    .fetch_optional(connection)
+    .await
+    .context("Failed to query meeting from database")?
+    .map(|row| models::MeetingInfo {
+        name: row.name,
+        description: row.description,
+        created_by: row.created_by,
+        created_at: row.created_at,
+        timezone: row.timezone,
+        has_availability_grid: row.has_availability_grid,
+        settings: meeting_settings!(row),
+        voting_closes_at: row.voting_closes_at,
+        voting_closed_at: row.voting_closed_at,
+        final_date_id: row.final_date_id,
+        version: row.version,
+    });
+    Ok(meeting)
+}
+
+/// Selects comments of meeting, newest first.
+async fn select_meeting_comments(
+    id: Uuid,
+    connection: &mut PgConnection,
+) -> Result<Vec<models::MeetingComment>> {
+    let comments = sqlx::query_as!(
+        models::MeetingComment,
+        r#"
+SELECT
+    users.id AS written_by, meeting_comment.message, meeting_comment.posted_at
+FROM
+    meeting_comment
+INNER JOIN users
+    ON meeting_comment.user_id = users.id
+WHERE
+    meeting_comment.meeting_id = $1
+ORDER BY
+    meeting_comment.posted_at DESC
+"#,
+        id
+    )
+    .fetch_all(connection)
+    .await
+    .context("Failed to query meeting comments from database")?;
+    Ok(comments)
+}
+
+/// Selects participants of meeting, its proposed dates and votes cast on
+/// them, each with a separate query filtered by meeting id.
+async fn select_participants_dates_votes(
+    id: Uuid,
+    connection: &mut PgConnection,
+) -> Result<models::ParticipantsDatesVotes> {
+    let participants = sqlx::query_as!(
+        models::Participant,
+        r#"
+SELECT
+    users.id, users.name, meeting_participants.timezone, meeting_participants.required,
+    meeting_participants.joined_at
+FROM
+    meeting_participants
+INNER JOIN users
+    ON meeting_participants.user_id = users.id
+WHERE
+    meeting_participants.meeting_id = $1
+ORDER BY
+    meeting_participants.joined_at, users.id
+"#,
+        id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .context("failed to query meeting participants")?;
+    let proposed_dates = sqlx::query_as!(
+        models::ProposedDate,
+        r#"
+SELECT
+    proposed_date.id,
+    proposed_date.date,
+    proposed_date.starts_at,
+    proposed_date.duration_minutes,
+    proposed_date.proposed_by,
+    proposed_date.approved
+FROM
+    proposed_date
+INNER JOIN meeting
+    ON proposed_date.meeting_id = meeting.id
+WHERE
+    proposed_date.meeting_id = $1
+ORDER BY
+    -- Chronologically in meeting's timezone, whole day before time slots
+    COALESCE(proposed_date.date, (proposed_date.starts_at AT TIME ZONE meeting.timezone)::date),
+    proposed_date.starts_at NULLS FIRST,
+    proposed_date.duration_minutes
+"#,
+        id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .context("failed to query proposed dates")?;
+    let votes = sqlx::query_as!(
+        models::ParticipantVote,
+        r#"
+SELECT
+    proposed_date_user_votes.proposed_date_id,
+    proposed_date_user_votes.user_id,
+    proposed_date_user_votes.vote AS "vote: models::Vote",
+    proposed_date_user_votes.comment
+FROM
+    proposed_date_user_votes
+INNER JOIN proposed_date
+    ON proposed_date_user_votes.proposed_date_id = proposed_date.id
+WHERE
+    proposed_date.meeting_id = $1
+"#,
+        id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .context("failed to query votes")?;
+
+    Ok(models::ParticipantsDatesVotes {
+        participants,
+        proposed_dates,
+        votes,
+    })
+}
+
+async fn create_new_meeting(
+    user: &business_logic::User,
+    meeting: &business_logic::Meeting,
+    pool: &PgPool,
+) -> Result<()> {
+    debug!(?user, ?meeting, "Creating new meeting");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    sqlx::query!(
+        r#"
+INSERT INTO
+    users(id, secret_token, name)
+VALUES
+    ($1, $2, $3)
+"#,
+        user.id,
+        user.secret_token,
+        user.name
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to insert into users")?;
+    sqlx::query!(
+        r#"
+INSERT INTO
+    meeting(
+        id, name, description, created_at, expires_at, user_id, timezone,
+        holiday_calendar, holiday_policy, exclude_weekends,
+        participants_can_propose, proposals_require_approval, hide_results,
+        anonymous_votes, auto_finalize, min_yes_votes, max_capacity,
+        unique_participant_names, voting_closes_at, voting_closed_at
+    )
+VALUES
+    (
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+        $18, $19, $20
+    )
+"#,
+        meeting.id,
+        meeting.name,
+        meeting.description,
+        meeting.created_at,
+        meeting.expires_at,
+        meeting.user_id,
+        meeting.timezone,
+        meeting.settings.holiday_calendar,
+        models::HolidayPolicy::from(meeting.settings.holiday_policy) as models::HolidayPolicy,
+        meeting.settings.exclude_weekends,
+        meeting.settings.participants_can_propose,
+        meeting.settings.proposals_require_approval,
+        meeting.settings.hide_results,
+        meeting.settings.anonymous_votes,
+        meeting.settings.auto_finalize,
+        meeting.settings.min_yes_votes.map(i32::from),
+        meeting.settings.max_capacity.map(i32::from),
+        meeting.settings.unique_participant_names,
+        meeting.voting_closes_at,
+        meeting.voting_closed_at
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to insert into meeting")?;
+    sqlx::query!(
+        r#"
+INSERT INTO
+    meeting_participants(user_id, meeting_id, timezone, account_id, joined_at)
+VALUES
+    ($1, $2, $3, $4, $5)
+"#,
+        user.id,
+        meeting.id,
+        user.timezone,
+        user.account_id,
+        user.joined_at
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to insert into meeting_participants")?;
+    if let Some(grid) = meeting.availability_grid {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    availability_grid(meeting_id, first_day, last_day, day_starts_at, day_ends_at, slot_minutes)
+VALUES
+    ($1, $2, $3, $4, $5, $6)
+"#,
+            meeting.id,
+            grid.first_day,
+            grid.last_day,
+            grid.day_starts_at,
+            grid.day_ends_at,
+            i32::from(grid.slot_minutes)
+        )
+        .execute(&mut *transaction)
+        .await
+        .context("failed to insert into availability_grid")?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Successfully created new meeting");
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+async fn join_meeting(
+    user: &business_logic::User,
+    meeting_id: Uuid,
+    pool: &PgPool,
+) -> Result<(), JoinMeetingError> {
+    debug!(participant_data=?user, ?meeting_id, "Creating new participant");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    // Lock serializes joins, so that two participants with the same name
+    // can not join concurrently
+    let unique_participant_names: bool = sqlx::query_scalar!(
+        r#"
+SELECT
+    unique_participant_names
+FROM
+    meeting
+WHERE
+    id = $1
+FOR NO KEY UPDATE
+"#,
+        meeting_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .with_context(|| format!("Failed to check if meeting with id `{meeting_id}` exists"))?
+    .ok_or(JoinMeetingError::NonexistentMeeting(meeting_id))?;
+
+    if unique_participant_names {
+        let names: Vec<String> = sqlx::query_scalar!(
+            r#"
+SELECT
+    users.name
+FROM
+    meeting_participants
+    JOIN users ON users.id = meeting_participants.user_id
+WHERE
+    meeting_participants.meeting_id = $1
+"#,
+            meeting_id
+        )
+        .fetch_all(&mut *transaction)
+        .await
+        .context("failed to select names of participants")?;
+        let key = display_name_key(&user.name);
+        if names.iter().any(|name| display_name_key(name) == key) {
+            debug!(
+                name = user.name,
+                "Participant with the same name already joined"
+            );
+            return Err(JoinMeetingError::DuplicateName(user.name.clone()));
+        }
+    }
+
+    sqlx::query!(
+        r#"
+INSERT INTO
+    users(id, secret_token, name)
+VALUES
+    ($1, $2, $3)
+"#,
+        user.id,
+        user.secret_token,
+        user.name
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to insert into users")?;
+    match sqlx::query!(
+        r#"
+INSERT INTO
+    meeting_participants(user_id, meeting_id, timezone, account_id, joined_at)
+VALUES
+    ($1, $2, $3, $4, $5)
+"#,
+        user.id,
+        meeting_id,
+        user.timezone,
+        user.account_id,
+        user.joined_at
+    )
+    .execute(&mut *transaction)
+    .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            debug!(?error, "Account already takes part in the meeting");
+            return Err(JoinMeetingError::AlreadyJoined);
+        }
+        Err(error) => {
+            return Err(anyhow::Error::new(error)
+                .context("failed to insert into meeting_participants")
+                .into())
+        }
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Successfully added new participant");
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+async fn post_comment(
+    meeting_comment: &business_logic::MeetingComment,
+    pool: &PgPool,
+) -> Result<()> {
+    debug!("Inserting meeting comment into database");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    sqlx::query!(
+        r#"
+INSERT INTO
+    meeting_comment(id, user_id, meeting_id, message, posted_at)
+VALUES
+    ($1, $2, $3, $4, $5)
+"#,
+        meeting_comment.id,
+        meeting_comment.user_id,
+        meeting_comment.meeting_id,
+        meeting_comment.message,
+        meeting_comment.posted_at
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to insert meeting into database")?;
+    let record = HistoryRecord {
+        meeting_id: meeting_comment.meeting_id,
+        actor_id: Some(meeting_comment.user_id),
+        action: models::HistoryAction::CommentPosted,
+        proposed_date_id: None,
+        old_value: None,
+        new_value: Some(json!({
+            "id": meeting_comment.id,
+            "message": meeting_comment.message,
+        })),
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Meeting comment inserted successfully");
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+async fn add_proposed_date(
+    proposed_date: &business_logic::ProposedDate,
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<(), AddProposedDateError> {
+    debug!("Inserting proposed date into database");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(
+        proposed_date.meeting_id,
+        Some(expected_version),
+        &mut transaction,
+    )
+    .await?;
+    let (date, starts_at, duration_minutes) = proposed_date.option.into_columns();
+    match sqlx::query!(
+        r#"
+INSERT INTO
+    proposed_date(id, meeting_id, date, starts_at, duration_minutes, proposed_by, approved)
+VALUES
+    ($1, $2, $3, $4, $5, $6, $7)
+"#,
+        proposed_date.id,
+        proposed_date.meeting_id,
+        date,
+        starts_at,
+        duration_minutes,
+        proposed_date.proposed_by,
+        proposed_date.approved
+    )
+    .execute(&mut *transaction)
+    .await
+    {
+        Ok(_) => {}
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            debug!(?error, "Date is already proposed");
+            return Err(AddProposedDateError::AlreadyProposed);
+        }
+        Err(error) => {
+            return Err(anyhow::Error::new(error)
+                .context("failed to insert proposed date into database")
+                .into())
+        }
+    }
+    record_history(
+        HistoryRecord::date_proposed(proposed_date),
+        &mut transaction,
+    )
+    .await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Proposed date inserted successfully");
+    Ok(())
+}
+
+#[tracing::instrument(skip(pool))]
+async fn get_meeting_availability(
+    id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<models::MeetingAvailability>> {
+    debug!(?id, "Queering meeting availability from database");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    let timezone = match sqlx::query_scalar!(
+        r#"
+SELECT
+    timezone
+FROM
+    meeting
+WHERE
+    id = $1
+"#,
+        id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query meeting")?
+    {
+        Some(timezone) => timezone,
+        None => {
+            debug!("Meeting does not exist");
+            return Ok(None);
+        }
+    };
+    let grid = sqlx::query_as!(
+        models::AvailabilityGrid,
+        r#"
+SELECT
+    first_day, last_day, day_starts_at, day_ends_at, slot_minutes
+FROM
+    availability_grid
+WHERE
+    meeting_id = $1
+"#,
+        id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query availability grid")?;
+    let participants = sqlx::query_as!(
+        models::ParticipantAvailability,
+        r#"
+SELECT
+    user_id, slots
+FROM
+    participant_availability
+WHERE
+    meeting_id = $1
+"#,
+        id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to query participant availability")?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+
+    let availability = models::MeetingAvailability {
+        timezone,
+        grid,
+        participants,
+    };
+    debug!(?availability, "Received meeting availability from database");
+    Ok(Some(availability))
+}
+
+/// Returns availability grid of the meeting, or `None` if meeting
+/// does not exist or is a poll.
+#[tracing::instrument(skip(pool))]
+async fn get_availability_grid(
+    meeting_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<models::AvailabilityGrid>> {
+    debug!(?meeting_id, "Queering availability grid from database");
+    let grid = sqlx::query_as!(
+        models::AvailabilityGrid,
+        r#"
+SELECT
+    first_day, last_day, day_starts_at, day_ends_at, slot_minutes
+FROM
+    availability_grid
+WHERE
+    meeting_id = $1
+"#,
+        meeting_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("failed to query availability grid")?;
+    debug!(?grid, "Received availability grid from database");
+    Ok(grid)
+}
+
+/// Inserts or replaces availability of a meeting participant.
+#[tracing::instrument(skip(pool))]
+async fn set_availability(
+    availability: &business_logic::ParticipantAvailability,
+    pool: &PgPool,
+) -> Result<(), SetAvailabilityError> {
+    debug!("Upserting participant availability");
+    let result = sqlx::query!(
+        r#"
+INSERT INTO
+    participant_availability(user_id, meeting_id, slots)
+SELECT
+    $1, $2, $3
+WHERE EXISTS (
+    SELECT 1 FROM meeting_participants WHERE user_id = $1 AND meeting_id = $2
+)
+ON CONFLICT (user_id) DO UPDATE SET
+    slots = EXCLUDED.slots
+"#,
+        availability.user_id,
+        availability.meeting_id,
+        availability.slots.to_bytes()
+    )
+    .execute(pool)
+    .await
+    .context("failed to upsert participant availability")?;
+
+    if result.rows_affected() == 0 {
+        debug!("User is not a participant of the meeting");
+        return Err(SetAvailabilityError::NotParticipant);
+    }
+    debug!("Participant availability upserted successfully");
+    Ok(())
+}
+
+/// Inserts all proposed dates of meeting with `meeting_id` in a single
+/// transaction. If any of them is already proposed, none are inserted.
+#[tracing::instrument(skip_all, fields(count = proposed_dates.len()))]
+async fn add_proposed_dates(
+    meeting_id: Uuid,
+    proposed_dates: &[business_logic::ProposedDate],
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<(), AddProposedDateError> {
+    debug!("Inserting proposed dates into database");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, Some(expected_version), &mut transaction).await?;
+
+    for proposed_date in proposed_dates {
+        let (date, starts_at, duration_minutes) = proposed_date.option.into_columns();
+        match sqlx::query!(
+            r#"
+INSERT INTO
+    proposed_date(id, meeting_id, date, starts_at, duration_minutes, proposed_by, approved)
+VALUES
+    ($1, $2, $3, $4, $5, $6, $7)
+"#,
+            proposed_date.id,
+            proposed_date.meeting_id,
+            date,
+            starts_at,
+            duration_minutes,
+            proposed_date.proposed_by,
+            proposed_date.approved
+        )
+        .execute(&mut *transaction)
+        .await
+        {
+            Ok(_) => {}
+            Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+                debug!(?error, ?proposed_date, "Date is already proposed");
+                return Err(AddProposedDateError::AlreadyProposed);
+            }
+            Err(error) => {
+                return Err(anyhow::Error::new(error)
+                    .context("failed to insert proposed date into database")
+                    .into())
+            }
+        }
+        record_history(
+            HistoryRecord::date_proposed(proposed_date),
+            &mut transaction,
+        )
+        .await?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Proposed dates inserted successfully");
+    Ok(())
+}
+
+/// Returns approval status of proposed date with `date_id`, or `None`
+/// if meeting with `meeting_id` has no such date.
+#[tracing::instrument(skip(pool))]
+async fn get_proposed_date_status(
+    meeting_id: Uuid,
+    date_id: Uuid,
+    pool: &PgPool,
+) -> Result<Option<models::ProposedDateStatus>> {
+    debug!("Queering proposed date status from database");
+    let status = sqlx::query_as!(
+        models::ProposedDateStatus,
+        r#"
+SELECT
+    approved
+FROM
+    proposed_date
+WHERE
+    id = $1 AND meeting_id = $2
+"#,
+        date_id,
+        meeting_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("failed to query proposed date")?;
+    debug!(?status, "Received proposed date status from database");
+    Ok(status)
+}
+
+/// Approves proposed date on behalf of `actor_id`. Returns `false` if
+/// meeting has no such date.
+#[tracing::instrument(skip(pool))]
+async fn approve_proposed_date(
+    meeting_id: Uuid,
+    date_id: Uuid,
+    actor_id: Uuid,
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<bool, VersionedUpdateError> {
+    debug!("Approving proposed date");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, Some(expected_version), &mut transaction).await?;
+
+    let status: Option<models::ProposedDateStatus> = sqlx::query_as!(
+        models::ProposedDateStatus,
+        r#"
+SELECT
+    approved
+FROM
+    proposed_date
+WHERE
+    id = $1 AND meeting_id = $2
+FOR UPDATE
+"#,
+        date_id,
+        meeting_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query proposed date")?;
+    let Some(status) = status else {
+        debug!("No such proposed date");
+        return Ok(false);
+    };
+    if !status.approved {
+        sqlx::query!(
+            r#"
+UPDATE
+    proposed_date
+SET
+    approved = TRUE
+WHERE
+    id = $1
+"#,
+            date_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .context("failed to approve proposed date")?;
+        let record = HistoryRecord {
+            meeting_id,
+            actor_id: Some(actor_id),
+            action: models::HistoryAction::DateApproved,
+            proposed_date_id: Some(date_id),
+            old_value: Some(json!({ "approved": false })),
+            new_value: Some(json!({ "approved": true })),
+        };
+        record_history(record, &mut transaction).await?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Approved proposed date");
+    Ok(true)
+}
+
+/// Deletes proposed date that awaits approval on behalf of `actor_id`.
+/// Returns `false` if meeting has no such pending date.
+#[tracing::instrument(skip(pool))]
+async fn delete_pending_proposed_date(
+    meeting_id: Uuid,
+    date_id: Uuid,
+    actor_id: Uuid,
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<bool, VersionedUpdateError> {
+    debug!("Deleting pending proposed date");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, Some(expected_version), &mut transaction).await?;
+
+    let deleted: Option<models::DeletedProposedDate> = sqlx::query_as!(
+        models::DeletedProposedDate,
+        r#"
+DELETE FROM
+    proposed_date
+WHERE
+    id = $1 AND meeting_id = $2 AND NOT approved
+RETURNING
+    date, starts_at, duration_minutes, approved
+"#,
+        date_id,
+        meeting_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to delete proposed date")?;
+    let Some(deleted) = deleted else {
+        debug!("No such pending proposed date");
+        return Ok(false);
+    };
+    let option =
+        DateOption::from_columns(deleted.date, deleted.starts_at, deleted.duration_minutes)
+            .context("deleted proposed date is neither a whole day nor a time slot")?;
+    let record = HistoryRecord {
+        meeting_id,
+        actor_id: Some(actor_id),
+        action: models::HistoryAction::DateRejected,
+        proposed_date_id: Some(date_id),
+        old_value: Some(proposed_date_value(option, deleted.approved)),
+        new_value: None,
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Deleted pending proposed date");
+    Ok(true)
+}
+
+/// Inserts participant's vote, replacing their previous vote on the same date.
+#[tracing::instrument(skip(pool))]
+async fn cast_vote(
+    meeting_id: Uuid,
+    vote: &business_logic::ParticipantVote,
+    pool: &PgPool,
+) -> Result<(), CastVoteError> {
+    debug!("Upserting vote");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    // Closing voting and finalization update the meeting row, so they can
+    // not commit between this check and the vote.
+    let meeting = sqlx::query!(
+        r#"
+SELECT
+    voting_closed_at, voting_closes_at
+FROM
+    meeting
+WHERE
+    id = $1
+FOR SHARE
+"#,
+        meeting_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .context("failed to query meeting")?;
+    if business_logic::is_voting_closed(meeting.voting_closed_at, meeting.voting_closes_at) {
+        debug!("Voting is closed");
+        return Err(CastVoteError::VotingClosed);
+    }
+
+    let previous_vote: Option<models::StoredVote> = sqlx::query_as!(
+        models::StoredVote,
+        r#"
+SELECT
+    vote AS "vote: models::Vote", comment
+FROM
+    proposed_date_user_votes
+WHERE
+    proposed_date_id = $1 AND user_id = $2
+FOR UPDATE
+"#,
+        vote.proposed_date_id,
+        vote.user_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query previous vote")?;
+    sqlx::query!(
+        r#"
+INSERT INTO
+    proposed_date_user_votes(proposed_date_id, user_id, vote, comment)
+VALUES
+    ($1, $2, $3, $4)
+ON CONFLICT (proposed_date_id, user_id) DO UPDATE SET
+    vote = EXCLUDED.vote,
+    comment = EXCLUDED.comment
+"#,
+        vote.proposed_date_id,
+        vote.user_id,
+        models::Vote::from(vote.vote) as models::Vote,
+        vote.comment
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to upsert vote")?;
+    let record = HistoryRecord {
+        meeting_id,
+        actor_id: Some(vote.user_id),
+        action: models::HistoryAction::VoteCast,
+        proposed_date_id: Some(vote.proposed_date_id),
+        old_value: previous_vote.map(|previous| vote_value(previous.vote.into(), previous.comment)),
+        new_value: Some(vote_value(vote.vote, vote.comment.clone())),
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Vote upserted successfully");
+    Ok(())
+}
+
+/// Closes voting in meeting. Returns `false` if voting was already closed.
+#[tracing::instrument(skip(pool))]
+async fn close_voting(
+    meeting_id: Uuid,
+    actor_id: Uuid,
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<bool, VersionedUpdateError> {
+    debug!("Closing voting");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, Some(expected_version), &mut transaction).await?;
+    let result = sqlx::query!(
+        r#"
+UPDATE
+    meeting
+SET
+    voting_closed_at = NOW()
+WHERE
+    id = $1 AND voting_closed_at IS NULL
+"#,
+        meeting_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to close voting")?;
+    let closed = result.rows_affected() > 0;
+    if !closed {
+        debug!("Voting was already closed");
+        return Ok(false);
+    }
+    let record = HistoryRecord {
+        meeting_id,
+        actor_id: Some(actor_id),
+        action: models::HistoryAction::VotingClosed,
+        proposed_date_id: None,
+        old_value: None,
+        new_value: None,
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Closed voting");
+    Ok(true)
+}
+
+/// Returns approved proposed dates of meeting and votes cast on them.
+#[tracing::instrument(skip(pool))]
+async fn get_meeting_votes(meeting_id: Uuid, pool: &PgPool) -> Result<models::MeetingVotes> {
+    debug!("Queering meeting votes from database");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+    // Votes are tallied against dates and required participants, so all of
+    // them have to come from the same snapshot.
+    sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *transaction)
+        .await
+        .context("failed to set transaction isolation level")?;
+    let dates = sqlx::query_scalar!(
+        r#"
+SELECT
+    proposed_date.id
+FROM
+    proposed_date
+INNER JOIN meeting
+    ON proposed_date.meeting_id = meeting.id
+WHERE
+    proposed_date.meeting_id = $1 AND proposed_date.approved
+ORDER BY
+    -- Chronologically in meeting's timezone, whole day before time slots
+    COALESCE(proposed_date.date, (proposed_date.starts_at AT TIME ZONE meeting.timezone)::date),
+    proposed_date.starts_at NULLS FIRST,
+    proposed_date.duration_minutes
+"#,
+        meeting_id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to query proposed dates")?;
+    let votes = sqlx::query_as!(
+        models::DateVote,
+        r#"
+SELECT
+    proposed_date_user_votes.proposed_date_id,
+    proposed_date_user_votes.user_id,
+    proposed_date_user_votes.vote AS "vote: models::Vote"
+FROM
+    proposed_date_user_votes
+INNER JOIN proposed_date
+    ON proposed_date_user_votes.proposed_date_id = proposed_date.id
+WHERE
+    proposed_date.meeting_id = $1 AND proposed_date.approved
+"#,
+        meeting_id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to query votes")?;
+    let required_participants = sqlx::query_scalar!(
+        r#"
+SELECT
+    user_id
+FROM
+    meeting_participants
+WHERE
+    meeting_id = $1 AND required
+"#,
+        meeting_id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to query required participants")?;
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+
+    let meeting_votes = models::MeetingVotes {
+        dates,
+        votes,
+        required_participants,
+    };
+    debug!(?meeting_votes, "Received meeting votes from database");
+    Ok(meeting_votes)
+}
+
+/// Updates name and description of meeting on behalf of `actor_id`.
+#[tracing::instrument(skip(pool))]
+async fn update_meeting_details(
+    meeting_id: Uuid,
+    actor_id: Uuid,
+    update: &business_logic::MeetingDetailsUpdate,
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<(), VersionedUpdateError> {
+    debug!("Updating meeting details");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, Some(expected_version), &mut transaction).await?;
+    let old_details: models::MeetingDetails = sqlx::query_as!(
+        models::MeetingDetails,
+        r#"
+SELECT
+    name, description
+FROM
+    meeting
+WHERE
+    id = $1
+"#,
+        meeting_id
+    )
+    .fetch_one(&mut *transaction)
+    .await
+    .context("failed to query meeting details")?;
+    let new_details = models::MeetingDetails {
+        name: update
+            .name
+            .clone()
+            .unwrap_or_else(|| old_details.name.clone()),
+        description: match update.description {
+            Some(ref description) => description.clone(),
+            None => old_details.description.clone(),
+        },
+    };
+    sqlx::query!(
+        r#"
+UPDATE
+    meeting
+SET
+    name = $2,
+    description = $3
+WHERE
+    id = $1
+"#,
+        meeting_id,
+        new_details.name,
+        new_details.description
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to update meeting details")?;
+    let record = HistoryRecord {
+        meeting_id,
+        actor_id: Some(actor_id),
+        action: models::HistoryAction::DetailsUpdated,
+        proposed_date_id: None,
+        old_value: Some(details_value(old_details)),
+        new_value: Some(details_value(new_details)),
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Updated meeting details");
+    Ok(())
+}
+
+/// Marks participant of meeting as required or optional. Returns `false`
+/// if meeting has no such participant.
+#[tracing::instrument(skip(pool))]
+async fn set_participant_required(
+    meeting_id: Uuid,
+    participant_id: Uuid,
+    actor_id: Uuid,
+    required: bool,
+    expected_version: i64,
+    pool: &PgPool,
+) -> Result<bool, VersionedUpdateError> {
+    debug!("Updating participant");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, Some(expected_version), &mut transaction).await?;
+    let Some(was_required) = sqlx::query_scalar!(
+        r#"
+SELECT
+    required
+FROM
+    meeting_participants
+WHERE
+    user_id = $1 AND meeting_id = $2
+"#,
+        participant_id,
+        meeting_id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query meeting_participants")?
+    else {
+        debug!("No such participant");
+        return Ok(false);
+    };
+    sqlx::query!(
+        r#"
+UPDATE
+    meeting_participants
+SET
+    required = $3
+WHERE
+    user_id = $1 AND meeting_id = $2
+"#,
+        participant_id,
+        meeting_id,
+        required
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to update meeting_participants")?;
+    let record = HistoryRecord {
+        meeting_id,
+        actor_id: Some(actor_id),
+        action: models::HistoryAction::ParticipantRequiredChanged,
+        proposed_date_id: None,
+        old_value: Some(required_value(participant_id, was_required)),
+        new_value: Some(required_value(participant_id, required)),
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Updated participant");
+    Ok(true)
+}
+
+/// Sets final date of meeting and closes voting if it is still open.
+/// Returns `false` if meeting was already finalized. Version of meeting is
+/// only checked if `expected_version` is given, as automatic finalization
+/// is not requested by any client.
+#[tracing::instrument(skip(pool))]
+async fn finalize_meeting(
+    meeting_id: Uuid,
+    date_id: Uuid,
+    actor_id: Option<Uuid>,
+    expected_version: Option<i64>,
+    pool: &PgPool,
+) -> Result<bool, VersionedUpdateError> {
+    debug!("Finalizing meeting");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    bump_meeting_version(meeting_id, expected_version, &mut transaction).await?;
+    let result = sqlx::query!(
+        r#"
+INSERT INTO
+    meeting_final_date(meeting_id, proposed_date_id)
+VALUES
+    ($1, $2)
+ON CONFLICT (meeting_id) DO NOTHING
+"#,
+        meeting_id,
+        date_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to insert into meeting_final_date")?;
+    let finalized = result.rows_affected() > 0;
+    if !finalized {
+        debug!("Meeting was already finalized");
+        return Ok(false);
+    }
+    sqlx::query!(
+        r#"
+UPDATE
+    meeting
+SET
+    voting_closed_at = LEAST(NOW(), voting_closes_at)
+WHERE
+    id = $1 AND voting_closed_at IS NULL
+"#,
+        meeting_id
+    )
+    .execute(&mut *transaction)
+    .await
+    .context("failed to close voting")?;
+    let record = HistoryRecord {
+        meeting_id,
+        actor_id,
+        action: models::HistoryAction::MeetingFinalized,
+        proposed_date_id: Some(date_id),
+        old_value: None,
+        new_value: None,
+    };
+    record_history(record, &mut transaction).await?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    debug!("Finalized meeting");
+    Ok(true)
+}
+
+/// Checks if user with `user_id` is a participant of meeting with `meeting_id`.
+async fn is_meeting_participant(meeting_id: Uuid, user_id: Uuid, pool: &PgPool) -> Result<bool> {
+    debug!(
+        ?meeting_id,
+        ?user_id,
+        "Checking if user is meeting participant"
+    );
+    let is_participant = sqlx::query!(
+        r#"
+SELECT
+    user_id
+FROM
+    meeting_participants
+WHERE
+    user_id = $1 AND meeting_id = $2
+"#,
+        user_id,
+        meeting_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("failed to check if user is meeting participant")?
+    .is_some();
+
+    debug!(?is_participant, "Received status from database");
+    Ok(is_participant)
+}
+
+/// Returns history of meeting in the order it was recorded.
+#[tracing::instrument(skip(pool))]
+async fn get_meeting_history(meeting_id: Uuid, pool: &PgPool) -> Result<Vec<models::HistoryEntry>> {
+    debug!("Queering meeting history from database");
+    let history: Vec<models::HistoryEntry> = sqlx::query_as!(
+        models::HistoryEntry,
+        r#"
+SELECT
+    id, actor_id, recorded_at, action AS "action: models::HistoryAction", proposed_date_id,
+    old_value, new_value
+FROM
+    meeting_history
+WHERE
+    meeting_id = $1
+ORDER BY
+    id
+"#,
+        meeting_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to query meeting history")?;
+    debug!(
+        entries = history.len(),
+        "Received meeting history from database"
+    );
+    Ok(history)
+}
+
+/// Increments version of meeting. If `expected_version` is given, fails
+/// with [`VersionedUpdateError::StaleVersion`] unless it is the current
+/// version. Must be executed in the same transaction as the versioned
+/// change, which keeps row of the meeting locked until it is committed.
+async fn bump_meeting_version(
+    meeting_id: Uuid,
+    expected_version: Option<i64>,
+    connection: &mut PgConnection,
+) -> Result<(), VersionedUpdateError> {
+    trace!(?expected_version, "Bumping meeting version");
+    let result = sqlx::query!(
+        r#"
+UPDATE
+    meeting
+SET
+    version = version + 1
+WHERE
+    id = $1 AND ($2::BIGINT IS NULL OR version = $2)
+"#,
+        meeting_id,
+        expected_version
+    )
+    .execute(connection)
+    .await
+    .context("failed to update meeting version")?;
+    if result.rows_affected() == 0 {
+        debug!(?expected_version, "Meeting version is stale");
+        return Err(VersionedUpdateError::StaleVersion);
+    }
+    Ok(())
+}
+
+/// Change of meeting appended to its history.
+#[derive(Debug)]
+struct HistoryRecord {
+    meeting_id: Uuid,
+    actor_id: Option<Uuid>,
+    action: models::HistoryAction,
+    proposed_date_id: Option<Uuid>,
+    old_value: Option<JsonValue>,
+    new_value: Option<JsonValue>,
+}
+
+impl HistoryRecord {
+    fn date_proposed(proposed_date: &business_logic::ProposedDate) -> Self {
+        Self {
+            meeting_id: proposed_date.meeting_id,
+            actor_id: proposed_date.proposed_by,
+            action: models::HistoryAction::DateProposed,
+            proposed_date_id: Some(proposed_date.id),
+            old_value: None,
+            new_value: Some(proposed_date_value(
+                proposed_date.option,
+                proposed_date.approved,
+            )),
+        }
+    }
+}
+
+/// Appends `record` to meeting history. Must be executed in the same
+/// transaction as the recorded change.
+async fn record_history(record: HistoryRecord, connection: &mut PgConnection) -> Result<()> {
+    trace!(?record, "Recording meeting history");
+    sqlx::query!(
+        r#"
+INSERT INTO
+    meeting_history(meeting_id, actor_id, action, proposed_date_id, old_value, new_value)
+VALUES
+    ($1, $2, $3, $4, $5, $6)
+"#,
+        record.meeting_id,
+        record.actor_id,
+        record.action as models::HistoryAction,
+        record.proposed_date_id,
+        record.old_value,
+        record.new_value
+    )
+    .execute(connection)
+    .await
+    .context("failed to insert into meeting_history")?;
+    Ok(())
+}
+
+/// Returns `Some(secret_token)` of user with `user_id` from database.
+/// If user with provided `user_id` does not exist in the database this
+/// function will return `None`.
+async fn get_user_secret_token(user_id: Uuid, pool: &PgPool) -> Result<Option<Uuid>> {
+    debug!(?user_id, "Getting secret token of user");
+    let token = sqlx::query_as!(
+        models::UserSecretToken,
+        r#"
+SELECT
+    secret_token
+FROM
+    users
+WHERE
+    id = $1
+"#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("failed to get user's secret token")?
+    .map(models::UserSecretToken::into_token);
+
+    debug!(?token, "Received user's secret token");
+    Ok(token)
+}
+
+#[tracing::instrument(skip(pool))]
+async fn create_account(account: &business_logic::Account, pool: &PgPool) -> Result<()> {
+    debug!("Creating new account");
+    sqlx::query!(
+        r#"
+INSERT INTO
+    account(id, secret_token, name, created_at)
+VALUES
+    ($1, $2, $3, $4)
+"#,
+        account.id,
+        account.secret_token,
+        account.name,
+        account.created_at
+    )
+    .execute(pool)
+    .await
+    .context("failed to insert into account")?;
+
+    debug!("Created new account");
+    Ok(())
+}
+
+/// Returns `Some(secret_token)` of account with `account_id`, or `None` if
+/// there is no such account.
+async fn get_account_secret_token(account_id: Uuid, pool: &PgPool) -> Result<Option<Uuid>> {
+    debug!(?account_id, "Getting secret token of account");
+    let token = sqlx::query_as!(
+        models::UserSecretToken,
+        r#"
+SELECT
+    secret_token
+FROM
+    account
+WHERE
+    id = $1
+"#,
+        account_id
+    )
+    .fetch_optional(pool)
+    .await
+    .context("failed to get account's secret token")?
+    .map(models::UserSecretToken::into_token);
+
+    Ok(token)
+}
+
+/// Returns meetings created or joined by account, newest first.
+#[tracing::instrument(skip(pool))]
+async fn get_account_meetings(
+    account_id: Uuid,
+    pool: &PgPool,
+) -> Result<Vec<models::AccountMeeting>> {
+    debug!("Getting meetings of account");
+    let meetings = sqlx::query_as!(
+        models::AccountMeeting,
+        r#"
+SELECT
+    meeting.id AS meeting_id,
+    meeting.name,
+    meeting.created_at,
+    meeting.user_id AS created_by,
+    users.id AS user_id,
+    users.secret_token AS user_secret_token
+FROM
+    meeting_participants
+INNER JOIN meeting
+    ON meeting_participants.meeting_id = meeting.id
+INNER JOIN users
+    ON meeting_participants.user_id = users.id
+WHERE
+    meeting_participants.account_id = $1
+ORDER BY
+    meeting.created_at DESC, meeting.id
+"#,
+        account_id
+    )
+    .fetch_all(pool)
+    .await
+    .context("failed to select meetings of account")?;
+
+    Ok(meetings)
+}
+
+/// Links participant `user_id` of meeting `meeting_id` to account. Returns
+/// `false` if the participant is linked to another account or the account
+/// already takes part in the meeting as another participant.
+#[tracing::instrument(skip(pool))]
+async fn link_account(
+    account_id: Uuid,
+    meeting_id: Uuid,
+    user_id: Uuid,
+    pool: &PgPool,
+) -> Result<bool> {
+    debug!("Linking participant to account");
+    match sqlx::query!(
+        r#"
+UPDATE
+    meeting_participants
+SET
+    account_id = $1
+WHERE
+    meeting_id = $2 AND user_id = $3 AND (account_id IS NULL OR account_id = $1)
+"#,
+        account_id,
+        meeting_id,
+        user_id
+    )
+    .execute(pool)
+    .await
+    {
+        Ok(result) => {
+            let linked = result.rows_affected() > 0;
+            debug!(?linked, "Linked participant to account");
+            Ok(linked)
+        }
+        Err(sqlx::Error::Database(error)) if error.is_unique_violation() => {
+            debug!(?error, "Account already takes part in the meeting");
+            Ok(false)
+        }
+        Err(error) => Err(anyhow::Error::new(error).context("failed to link participant")),
+    }
+}
+
+/// Checks it meeting with provided ID exists. Must be executed inside
+/// transaction to avoid time-of-check-time-of-use bugs.
+async fn meeting_exists(meeting_id: Uuid, pool: &PgPool) -> Result<bool> {
+    debug!(?meeting_id, "Checking if meeting exists");
+
+    let exists = sqlx::query!(
+        r#"
+SELECT
+    id
+FROM
+    meeting
+WHERE
+    meeting.id = $1
+"#,
+        meeting_id
+    )
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("Failed to check if meeting with id `{meeting_id}` exists"))?
+    .is_some();
+
+    debug!(?exists, "Received status from database");
+    Ok(exists)
+}
+
+#[tracing::instrument(skip(pool))]
+async fn get_meeting_export(id: Uuid, pool: &PgPool) -> Result<Option<models::MeetingExport>> {
+    debug!(?id, "Exporting meeting from database");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+    // Export has to be consistent to be imported again, so votes must not
+    // refer to dates or participants missing from other queries.
+    sqlx::query!("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *transaction)
+        .await
+        .context("failed to set transaction isolation level")?;
+
+    let meeting = match sqlx::query!(
+        r#"
+SELECT
+    id, name, description, user_id AS created_by, created_at, expires_at, timezone,
+    holiday_calendar, holiday_policy AS "holiday_policy: models::HolidayPolicy",
+    exclude_weekends, participants_can_propose, proposals_require_approval, hide_results,
+    anonymous_votes, auto_finalize, min_yes_votes, max_capacity, unique_participant_names,
+    voting_closes_at, voting_closed_at,
+    (
+        SELECT proposed_date_id FROM meeting_final_date WHERE meeting_id = meeting.id
+    ) AS final_date_id
+FROM
+    meeting
+WHERE
+    id = $1
+"#,
+        id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query meeting")?
+    {
+        Some(row) => models::ExportedMeeting {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+            timezone: row.timezone,
+            settings: meeting_settings!(row),
+            voting_closes_at: row.voting_closes_at,
+            voting_closed_at: row.voting_closed_at,
+            final_date_id: row.final_date_id,
+        },
+        None => {
+            debug!("Meeting does not exist");
+            return Ok(None);
+        }
+    };
+    let models::ParticipantsDatesVotes {
+        participants,
+        proposed_dates,
+        votes,
+    } = select_participants_dates_votes(id, &mut transaction).await?;
+    let comments = sqlx::query_as!(
+        models::ExportedComment,
+        r#"
+SELECT
+    id, user_id, message, posted_at
+FROM
+    meeting_comment
+WHERE
+    meeting_id = $1
+ORDER BY
+    posted_at
+"#,
+        id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to query meeting comments")?;
+    let availability_grid = sqlx::query_as!(
+        models::AvailabilityGrid,
+        r#"
+SELECT
+    first_day, last_day, day_starts_at, day_ends_at, slot_minutes
+FROM
+    availability_grid
+WHERE
+    meeting_id = $1
+"#,
+        id
+    )
+    .fetch_optional(&mut *transaction)
+    .await
+    .context("failed to query availability grid")?;
+    let availability = sqlx::query_as!(
+        models::ParticipantAvailability,
+        r#"
+SELECT
+    user_id, slots
+FROM
+    participant_availability
+WHERE
+    meeting_id = $1
+"#,
+        id
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to query participant availability")?;
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+
+    let export = models::MeetingExport {
+        meeting,
+        participants,
+        proposed_dates,
+        votes,
+        comments,
+        availability_grid,
+        availability,
+    };
+    debug!(?export, "Exported meeting from database");
+    Ok(Some(export))
+}
+
+/// Inserts all rows of the imported meeting in a single transaction,
+/// keeping their original ids.
+#[tracing::instrument(skip_all, fields(meeting_id = ?import.meeting.id))]
+async fn import_meeting(
+    import: &business_logic::MeetingImport,
+    pool: &PgPool,
+) -> Result<(), ImportMeetingError> {
+    let business_logic::MeetingImport {
+        meeting,
+        participants,
+        proposed_dates,
+        votes,
+        comments,
+        availability,
+    } = import;
+
+    debug!("Importing meeting into database");
+    trace!("Starting transaction");
+    let mut transaction = pool.begin().await.context("failed to begin transaction")?;
+
+    let participant_ids = participants.iter().map(|p| p.id).collect::<Vec<_>>();
+    let date_ids = proposed_dates.iter().map(|d| d.id).collect::<Vec<_>>();
+    let comment_ids = comments.iter().map(|c| c.id).collect::<Vec<_>>();
+    let conflicts = sqlx::query!(
+        r#"
+SELECT 'meeting' AS "kind!", id AS "id!" FROM meeting WHERE id = $1
+UNION ALL
+SELECT 'participant' AS kind, id FROM users WHERE id = ANY($2)
+UNION ALL
+SELECT 'proposed date' AS kind, id FROM proposed_date WHERE id = ANY($3)
+UNION ALL
+SELECT 'comment' AS kind, id FROM meeting_comment WHERE id = ANY($4)
+"#,
+        meeting.id,
+        &participant_ids,
+        &date_ids,
+        &comment_ids
+    )
+    .fetch_all(&mut *transaction)
+    .await
+    .context("failed to check for conflicting rows")?;
+    let conflicts = conflicts
+        .into_iter()
+        .map(|row| (row.kind, row.id))
+        .collect::<Vec<_>>();
+    if !conflicts.is_empty() {
+        let description = conflicts
+            .iter()
+            .map(|(kind, id)| format!("{kind} `{id}` already exists"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        debug!(?conflicts, "Imported meeting conflicts with existing rows");
+        return Err(ImportMeetingError::Conflict(description));
+    }
+
+    // Participants have to be inserted before the meeting, since meeting
+    // references its creator. Rows that violate unique constraints at this
+    // point were inserted concurrently after the conflict check.
+    for user in participants {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    users(id, secret_token, name)
+VALUES
+    ($1, $2, $3)
+"#,
+            user.id,
+            user.secret_token,
+            user.name
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error("failed to insert into users"))?;
+    }
+    sqlx::query!(
+        r#"
+INSERT INTO
+    meeting(
+        id, name, description, created_at, expires_at, user_id, timezone,
+        holiday_calendar, holiday_policy, exclude_weekends,
+        participants_can_propose, proposals_require_approval, hide_results,
+        anonymous_votes, auto_finalize, min_yes_votes, max_capacity,
+        unique_participant_names, voting_closes_at, voting_closed_at
+    )
+VALUES
+    (
+        $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17,
+        $18, $19, $20
+    )
+"#,
+        meeting.id,
+        meeting.name,
+        meeting.description,
+        meeting.created_at,
+        meeting.expires_at,
+        meeting.user_id,
+        meeting.timezone,
+        meeting.settings.holiday_calendar,
+        models::HolidayPolicy::from(meeting.settings.holiday_policy) as models::HolidayPolicy,
+        meeting.settings.exclude_weekends,
+        meeting.settings.participants_can_propose,
+        meeting.settings.proposals_require_approval,
+        meeting.settings.hide_results,
+        meeting.settings.anonymous_votes,
+        meeting.settings.auto_finalize,
+        meeting.settings.min_yes_votes.map(i32::from),
+        meeting.settings.max_capacity.map(i32::from),
+        meeting.settings.unique_participant_names,
+        meeting.voting_closes_at,
+        meeting.voting_closed_at
+    )
+    .execute(&mut *transaction)
+    .await
+    .map_err(import_error("failed to insert into meeting"))?;
+    for user in participants {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    meeting_participants(user_id, meeting_id, timezone, required, joined_at)
+VALUES
+    ($1, $2, $3, $4, $5)
+"#,
+            user.id,
+            meeting.id,
+            user.timezone,
+            user.required,
+            user.joined_at
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error("failed to insert into meeting_participants"))?;
+    }
+    for proposed_date in proposed_dates {
+        let (date, starts_at, duration_minutes) = proposed_date.option.into_columns();
+        sqlx::query!(
+            r#"
+INSERT INTO
+    proposed_date(id, meeting_id, date, starts_at, duration_minutes, proposed_by, approved)
+VALUES
+    ($1, $2, $3, $4, $5, $6, $7)
+"#,
+            proposed_date.id,
+            proposed_date.meeting_id,
+            date,
+            starts_at,
+            duration_minutes,
+            proposed_date.proposed_by,
+            proposed_date.approved
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error("failed to insert into proposed_date"))?;
+    }
+    for vote in votes {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    proposed_date_user_votes(proposed_date_id, user_id, vote, comment)
+VALUES
+    ($1, $2, $3, $4)
+"#,
+            vote.proposed_date_id,
+            vote.user_id,
+            models::Vote::from(vote.vote) as models::Vote,
+            vote.comment
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error(
+            "failed to insert into proposed_date_user_votes",
+        ))?;
+    }
+    for comment in comments {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    meeting_comment(id, user_id, meeting_id, message, posted_at)
+VALUES
+    ($1, $2, $3, $4, $5)
+"#,
+            comment.id,
+            comment.user_id,
+            comment.meeting_id,
+            comment.message,
+            comment.posted_at
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error("failed to insert into meeting_comment"))?;
+    }
+    if let Some(final_date_id) = meeting.final_date_id {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    meeting_final_date(meeting_id, proposed_date_id)
+VALUES
+    ($1, $2)
+"#,
+            meeting.id,
+            final_date_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error("failed to insert into meeting_final_date"))?;
+    }
+    if let Some(grid) = meeting.availability_grid {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    availability_grid(meeting_id, first_day, last_day, day_starts_at, day_ends_at, slot_minutes)
+VALUES
+    ($1, $2, $3, $4, $5, $6)
+"#,
+            meeting.id,
+            grid.first_day,
+            grid.last_day,
+            grid.day_starts_at,
+            grid.day_ends_at,
+            i32::from(grid.slot_minutes)
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error("failed to insert into availability_grid"))?;
+    }
+    for participant_availability in availability {
+        sqlx::query!(
+            r#"
+INSERT INTO
+    participant_availability(user_id, meeting_id, slots)
+VALUES
+    ($1, $2, $3)
+"#,
+            participant_availability.user_id,
+            participant_availability.meeting_id,
+            participant_availability.slots.to_bytes()
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(import_error(
+            "failed to insert into participant_availability",
+        ))?;
+    }
+
+    transaction
+        .commit()
+        .await
+        .context("failed to commit transaction")?;
+    trace!("Committed transaction");
+    debug!("Successfully imported meeting");
+    Ok(())
+}
+
+fn import_error(context: &'static str) -> impl Fn(sqlx::Error) -> ImportMeetingError {
+    move |error| match error {
+        sqlx::Error::Database(ref database_error) if database_error.is_unique_violation() => {
+            ImportMeetingError::Conflict(database_error.message().to_owned())
+        }
+        error => ImportMeetingError::Database(anyhow::Error::new(error).context(context)),
+    }
+}
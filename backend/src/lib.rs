@@ -3,5 +3,9 @@ pub(crate) mod app;
 pub(crate) mod config;
 pub(crate) mod database;
 
+#[cfg(feature = "bench")]
+#[doc(hidden)]
+pub mod bench;
+
 pub use app::run_server;
 pub use config::Config;
@@ -1,17 +1,40 @@
 -- Cleanup
 
+DROP TABLE IF EXISTS account CASCADE;
 DROP TABLE IF EXISTS users CASCADE;
 DROP TABLE IF EXISTS meeting CASCADE;
 DROP TABLE IF EXISTS proposed_date CASCADE;
 DROP TABLE IF EXISTS meeting_participants CASCADE;
 DROP TABLE IF EXISTS proposed_date_user_votes CASCADE;
 DROP TABLE IF EXISTS meeting_comment CASCADE;
+DROP TABLE IF EXISTS meeting_final_date CASCADE;
+DROP TABLE IF EXISTS availability_grid CASCADE;
+DROP TABLE IF EXISTS participant_availability CASCADE;
+DROP TABLE IF EXISTS meeting_history CASCADE;
 
 DROP TYPE IF EXISTS proposed_date_vote CASCADE;
+DROP TYPE IF EXISTS holiday_policy CASCADE;
+DROP TYPE IF EXISTS meeting_history_action CASCADE;
+
+DROP FUNCTION IF EXISTS reject_meeting_history_update CASCADE;
 
 -- Declarations
 
 CREATE TYPE proposed_date_vote AS ENUM ('no', 'maybe', 'ok');
+CREATE TYPE holiday_policy AS ENUM ('flag', 'reject');
+CREATE TYPE meeting_history_action AS ENUM (
+    'details_updated', 'date_proposed', 'date_approved', 'date_rejected', 'vote_cast',
+    'comment_posted', 'participant_required_changed', 'voting_closed', 'meeting_finalized'
+);
+
+-- Persistent identity of a person. Every participation in a meeting is
+-- still a separate row of `users`; accounts only link them together.
+CREATE TABLE account (
+    id UUID PRIMARY KEY,
+    secret_token UUID NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
 
 CREATE TABLE users (
     id UUID PRIMARY KEY,
@@ -26,29 +49,72 @@ CREATE TABLE meeting (
     created_at TIMESTAMP WITH TIME ZONE NOT NULL,
     expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
     user_id UUID NOT NULL, 
+    timezone VARCHAR(64) NOT NULL DEFAULT 'Etc/UTC',
+    holiday_calendar VARCHAR(64),
+    holiday_policy holiday_policy NOT NULL DEFAULT 'flag',
+    exclude_weekends BOOLEAN NOT NULL DEFAULT FALSE,
+    participants_can_propose BOOLEAN NOT NULL DEFAULT FALSE,
+    proposals_require_approval BOOLEAN NOT NULL DEFAULT FALSE,
+    hide_results BOOLEAN NOT NULL DEFAULT FALSE,
+    anonymous_votes BOOLEAN NOT NULL DEFAULT FALSE,
+    auto_finalize BOOLEAN NOT NULL DEFAULT FALSE,
+    min_yes_votes INTEGER,
+    max_capacity INTEGER,
+    unique_participant_names BOOLEAN NOT NULL DEFAULT FALSE,
+    voting_closes_at TIMESTAMP WITH TIME ZONE,
+    voting_closed_at TIMESTAMP WITH TIME ZONE,
+    -- Incremented by every change of meeting details or its dates
+    version BIGINT NOT NULL DEFAULT 1,
 
     CHECK (expires_at >= created_at),
+    CHECK (voting_closes_at >= created_at AND voting_closes_at <= expires_at),
+    CHECK (voting_closed_at >= created_at),
+    CHECK (min_yes_votes > 0),
+    CHECK (max_capacity > 0 AND max_capacity >= min_yes_votes),
+    CHECK (version > 0),
     FOREIGN KEY(user_id)
         REFERENCES users(id)
         ON DELETE CASCADE
         ON UPDATE CASCADE
 );
 
+-- Proposed date is either a whole day (only `date` is set)
+-- or a time slot (`starts_at` and `duration_minutes` are set).
 CREATE TABLE proposed_date (
     id UUID PRIMARY KEY,
     meeting_id UUID NOT NULL,
-    date DATE NOT NULL,
+    date DATE,
+    starts_at TIMESTAMP WITH TIME ZONE,
+    duration_minutes INTEGER,
+    proposed_by UUID,
+    approved BOOLEAN NOT NULL DEFAULT TRUE,
 
+    CHECK (
+        (date IS NOT NULL AND starts_at IS NULL AND duration_minutes IS NULL) OR
+        (date IS NULL AND starts_at IS NOT NULL AND duration_minutes > 0)
+    ),
     FOREIGN KEY(meeting_id)
         REFERENCES meeting(id)
         ON DELETE CASCADE
         ON UPDATE CASCADE,
-    UNIQUE(meeting_id, date)
+    FOREIGN KEY(proposed_by)
+        REFERENCES users(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE,
+    UNIQUE(meeting_id, date),
+    UNIQUE(meeting_id, starts_at, duration_minutes)
 );
 
+CREATE INDEX proposed_date_meeting_id_idx ON proposed_date(meeting_id);
+
 CREATE TABLE meeting_participants (
     user_id UUID NOT NULL UNIQUE,
     meeting_id UUID NOT NULL,
+    timezone VARCHAR(64),
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Account of the person behind this participant, if they have one
+    account_id UUID,
+    joined_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
 
     FOREIGN KEY(user_id)
         REFERENCES users(id)
@@ -57,9 +123,16 @@ CREATE TABLE meeting_participants (
     FOREIGN KEY(meeting_id)
         REFERENCES meeting(id)
         ON DELETE CASCADE
-        ON UPDATE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(account_id)
+        REFERENCES account(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE,
+    UNIQUE(account_id, meeting_id)
 );
 
+CREATE INDEX meeting_participants_meeting_id_idx ON meeting_participants(meeting_id);
+
 
 CREATE TABLE proposed_date_user_votes (
     proposed_date_id UUID NOT NULL,
@@ -95,3 +168,91 @@ CREATE TABLE meeting_comment (
         ON UPDATE CASCADE
 );
 
+CREATE INDEX meeting_comment_meeting_id_idx ON meeting_comment(meeting_id);
+
+-- Date chosen as the outcome of meeting's voting.
+CREATE TABLE meeting_final_date (
+    meeting_id UUID PRIMARY KEY,
+    proposed_date_id UUID NOT NULL,
+
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(proposed_date_id)
+        REFERENCES proposed_date(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Meetings with availability grid are not polls. Instead of voting on
+-- proposed dates participants mark slots of the grid in which they are free.
+CREATE TABLE availability_grid (
+    meeting_id UUID PRIMARY KEY,
+    first_day DATE NOT NULL,
+    last_day DATE NOT NULL,
+    day_starts_at TIME NOT NULL,
+    day_ends_at TIME NOT NULL,
+    slot_minutes INTEGER NOT NULL,
+
+    CHECK (last_day >= first_day),
+    CHECK (day_ends_at > day_starts_at),
+    CHECK (slot_minutes > 0),
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Bitmap with one bit for every slot of the grid, set if participant is free.
+CREATE TABLE participant_availability (
+    user_id UUID PRIMARY KEY,
+    meeting_id UUID NOT NULL,
+    slots BYTEA NOT NULL,
+
+    FOREIGN KEY(user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(meeting_id)
+        REFERENCES availability_grid(meeting_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Append-only log of changes of meeting details, votes, comments, proposed
+-- dates, required participants and of closing and finalizing the meeting.
+-- Values are JSON snapshots of the changed row before and after the change.
+-- `proposed_date_id` is not a foreign key, so that history of rejected
+-- dates is kept.
+CREATE TABLE meeting_history (
+    id BIGSERIAL PRIMARY KEY,
+    meeting_id UUID NOT NULL,
+    actor_id UUID,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    action meeting_history_action NOT NULL,
+    proposed_date_id UUID,
+    old_value JSONB,
+    new_value JSONB,
+
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(actor_id)
+        REFERENCES users(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE
+);
+
+CREATE FUNCTION reject_meeting_history_update() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'meeting history is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+-- Referential actions on `meeting_id` and `actor_id` are still allowed.
+CREATE TRIGGER meeting_history_is_append_only
+    BEFORE UPDATE OF recorded_at, action, proposed_date_id, old_value, new_value
+    ON meeting_history
+    FOR EACH ROW EXECUTE FUNCTION reject_meeting_history_update();
@@ -0,0 +1,244 @@
+-- Schema of the SQLite storage, equivalent to `schema.sql`. It is applied
+-- by the server on every start, so every statement must be idempotent.
+--
+-- Differences from Postgres:
+-- * UUIDs are stored as 16-byte blobs.
+-- * Timestamps are stored as RFC 3339 text in UTC and are compared with
+--   `julianday`, as text comparison breaks on fractional seconds.
+-- * Enums are text columns with `CHECK` constraints.
+-- * Foreign keys, and so cascading deletes, are enforced only on
+--   connections with `PRAGMA foreign_keys = ON`, which the server enables.
+
+-- Persistent identity of a person. Every participation in a meeting is
+-- still a separate row of `users`; accounts only link them together.
+CREATE TABLE IF NOT EXISTS account (
+    id BLOB PRIMARY KEY,
+    secret_token BLOB NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    created_at DATETIME NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS users (
+    id BLOB PRIMARY KEY,
+    secret_token BLOB NOT NULL,
+    name VARCHAR(100) NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS meeting (
+    id BLOB PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    description VARCHAR(1000),
+    created_at DATETIME NOT NULL,
+    expires_at DATETIME NOT NULL,
+    user_id BLOB NOT NULL,
+    timezone VARCHAR(64) NOT NULL DEFAULT 'Etc/UTC',
+    holiday_calendar VARCHAR(64),
+    holiday_policy TEXT NOT NULL DEFAULT 'flag'
+        CHECK (holiday_policy IN ('flag', 'reject')),
+    exclude_weekends BOOLEAN NOT NULL DEFAULT FALSE,
+    participants_can_propose BOOLEAN NOT NULL DEFAULT FALSE,
+    proposals_require_approval BOOLEAN NOT NULL DEFAULT FALSE,
+    hide_results BOOLEAN NOT NULL DEFAULT FALSE,
+    anonymous_votes BOOLEAN NOT NULL DEFAULT FALSE,
+    auto_finalize BOOLEAN NOT NULL DEFAULT FALSE,
+    min_yes_votes INTEGER,
+    max_capacity INTEGER,
+    unique_participant_names BOOLEAN NOT NULL DEFAULT FALSE,
+    voting_closes_at DATETIME,
+    voting_closed_at DATETIME,
+    -- Incremented by every change of meeting details or its dates
+    version BIGINT NOT NULL DEFAULT 1,
+
+    CHECK (julianday(expires_at) >= julianday(created_at)),
+    CHECK (
+        julianday(voting_closes_at) >= julianday(created_at) AND
+        julianday(voting_closes_at) <= julianday(expires_at)
+    ),
+    CHECK (julianday(voting_closed_at) >= julianday(created_at)),
+    CHECK (min_yes_votes > 0),
+    CHECK (max_capacity > 0 AND max_capacity >= min_yes_votes),
+    CHECK (version > 0),
+    FOREIGN KEY(user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Proposed date is either a whole day (only `date` is set)
+-- or a time slot (`starts_at` and `duration_minutes` are set).
+CREATE TABLE IF NOT EXISTS proposed_date (
+    id BLOB PRIMARY KEY,
+    meeting_id BLOB NOT NULL,
+    date DATE,
+    starts_at DATETIME,
+    duration_minutes INTEGER,
+    proposed_by BLOB,
+    approved BOOLEAN NOT NULL DEFAULT TRUE,
+
+    CHECK (
+        (date IS NOT NULL AND starts_at IS NULL AND duration_minutes IS NULL) OR
+        (date IS NULL AND starts_at IS NOT NULL AND duration_minutes > 0)
+    ),
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(proposed_by)
+        REFERENCES users(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE,
+    UNIQUE(meeting_id, date),
+    UNIQUE(meeting_id, starts_at, duration_minutes)
+);
+
+CREATE INDEX IF NOT EXISTS proposed_date_meeting_id_idx ON proposed_date(meeting_id);
+
+CREATE TABLE IF NOT EXISTS meeting_participants (
+    user_id BLOB NOT NULL UNIQUE,
+    meeting_id BLOB NOT NULL,
+    timezone VARCHAR(64),
+    required BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Account of the person behind this participant, if they have one
+    account_id BLOB,
+    joined_at DATETIME NOT NULL,
+
+    FOREIGN KEY(user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(account_id)
+        REFERENCES account(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE,
+    UNIQUE(account_id, meeting_id)
+);
+
+CREATE INDEX IF NOT EXISTS meeting_participants_meeting_id_idx ON meeting_participants(meeting_id);
+
+CREATE TABLE IF NOT EXISTS proposed_date_user_votes (
+    proposed_date_id BLOB NOT NULL,
+    user_id BLOB NOT NULL,
+    -- Emulates `proposed_date_vote` enum
+    vote TEXT NOT NULL CHECK (vote IN ('no', 'maybe', 'ok')),
+    comment VARCHAR(200),
+
+    FOREIGN KEY(proposed_date_id)
+        REFERENCES proposed_date(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    UNIQUE(proposed_date_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS meeting_comment (
+    id BLOB PRIMARY KEY,
+    user_id BLOB NOT NULL,
+    meeting_id BLOB NOT NULL,
+    message VARCHAR(1000) NOT NULL,
+    posted_at DATETIME NOT NULL,
+
+    FOREIGN KEY(user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS meeting_comment_meeting_id_idx ON meeting_comment(meeting_id);
+
+-- Date chosen as the outcome of meeting's voting.
+CREATE TABLE IF NOT EXISTS meeting_final_date (
+    meeting_id BLOB PRIMARY KEY,
+    proposed_date_id BLOB NOT NULL,
+
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(proposed_date_id)
+        REFERENCES proposed_date(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Meetings with availability grid are not polls. Instead of voting on
+-- proposed dates participants mark slots of the grid in which they are free.
+CREATE TABLE IF NOT EXISTS availability_grid (
+    meeting_id BLOB PRIMARY KEY,
+    first_day DATE NOT NULL,
+    last_day DATE NOT NULL,
+    day_starts_at TIME NOT NULL,
+    day_ends_at TIME NOT NULL,
+    slot_minutes INTEGER NOT NULL,
+
+    CHECK (last_day >= first_day),
+    CHECK (day_ends_at > day_starts_at),
+    CHECK (slot_minutes > 0),
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Bitmap with one bit for every slot of the grid, set if participant is free.
+CREATE TABLE IF NOT EXISTS participant_availability (
+    user_id BLOB PRIMARY KEY,
+    meeting_id BLOB NOT NULL,
+    slots BLOB NOT NULL,
+
+    FOREIGN KEY(user_id)
+        REFERENCES users(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(meeting_id)
+        REFERENCES availability_grid(meeting_id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE
+);
+
+-- Append-only log of changes of meeting details, votes, comments and
+-- proposed dates.
+-- Values are JSON snapshots of the changed row before and after the change.
+-- `proposed_date_id` is not a foreign key, so that history of rejected
+-- dates is kept.
+CREATE TABLE IF NOT EXISTS meeting_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id BLOB NOT NULL,
+    actor_id BLOB,
+    recorded_at DATETIME NOT NULL,
+    -- Emulates `meeting_history_action` enum
+    action TEXT NOT NULL CHECK (action IN (
+        'details_updated', 'date_proposed', 'date_approved', 'date_rejected', 'vote_cast',
+        'comment_posted', 'participant_required_changed', 'voting_closed', 'meeting_finalized'
+    )),
+    proposed_date_id BLOB,
+    old_value TEXT CHECK (old_value IS NULL OR json_valid(old_value)),
+    new_value TEXT CHECK (new_value IS NULL OR json_valid(new_value)),
+
+    FOREIGN KEY(meeting_id)
+        REFERENCES meeting(id)
+        ON DELETE CASCADE
+        ON UPDATE CASCADE,
+    FOREIGN KEY(actor_id)
+        REFERENCES users(id)
+        ON DELETE SET NULL
+        ON UPDATE CASCADE
+);
+
+-- Referential actions on `meeting_id` and `actor_id` are still allowed.
+CREATE TRIGGER IF NOT EXISTS meeting_history_is_append_only
+    BEFORE UPDATE OF recorded_at, action, proposed_date_id, old_value, new_value
+    ON meeting_history
+BEGIN
+    SELECT RAISE(ABORT, 'meeting history is append-only');
+END;
@@ -4,3 +4,9 @@ import pytest
 @pytest.fixture
 def server_address() -> str:
     return "localhost:4444"
+
+
+@pytest.fixture
+def admin_token() -> str:
+    """Must match `ADMIN_TOKEN` the tested server was started with"""
+    return "00000000-0000-0000-0000-000000000001"
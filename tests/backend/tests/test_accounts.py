@@ -0,0 +1,89 @@
+import uuid
+
+from tests.utils.actions import create_account, create_account_and_validate, create_meeting, \
+    create_meeting_and_validate, get_account_meetings, get_account_meetings_and_validate, \
+    get_meeting_info_and_validate, join_meeting, join_meeting_and_validate, link_account
+from tests.utils.models import CreateMeetingData
+
+
+def _create_meeting(server_address, name, account=None):
+    meeting_data = CreateMeetingData(meeting_name=name, meeting_description=None, user_name="user1",
+                                     account=account)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_account_lists_created_and_joined_meetings(server_address):
+    account = create_account_and_validate(server_address=server_address, name="Anna")
+    assert get_account_meetings_and_validate(server_address=server_address, account=account) == []
+
+    created = _create_meeting(server_address, "created", account=account)
+    other = _create_meeting(server_address, "joined")
+    joined = join_meeting_and_validate(server_address=server_address, meeting_id=other.meeting_id, name="Anna",
+                                       account=account)
+    _create_meeting(server_address, "unrelated")
+
+    meetings = get_account_meetings_and_validate(server_address=server_address, account=account)
+    assert [meeting["name"] for meeting in meetings] == ["joined", "created"]
+    assert meetings[0]["meeting_id"] == str(other.meeting_id)
+    assert meetings[0]["role"] == "participant"
+    assert meetings[0]["user_id"] == str(joined.id)
+    assert meetings[0]["user_secret_token"] == str(joined.secret_token)
+    assert meetings[1]["meeting_id"] == str(created.meeting_id)
+    assert meetings[1]["role"] == "creator"
+    assert meetings[1]["user_id"] == str(created.user_id)
+
+
+def test_anonymous_participant_can_be_linked(server_address):
+    account = create_account_and_validate(server_address=server_address, name="Anna")
+    new_meeting = _create_meeting(server_address, "test name")
+    participant = join_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                            name="Anna")
+
+    response = link_account(server_address=server_address, account=account, meeting_id=new_meeting.meeting_id,
+                            user_id=participant.id, user_token=participant.secret_token)
+    assert response.status_code == 204, f"{response.status_code=}"
+    meetings = get_account_meetings_and_validate(server_address=server_address, account=account)
+    assert [meeting["user_id"] for meeting in meetings] == [str(participant.id)]
+
+    # Account takes part in the meeting only once
+    response = link_account(server_address=server_address, account=account, meeting_id=new_meeting.meeting_id,
+                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    assert response.status_code == 409, f"{response.status_code=}"
+    response = join_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id, name="Anna 2",
+                            account=account)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    # Participant is linked to at most one account
+    other_account = create_account_and_validate(server_address=server_address, name="Anna")
+    response = link_account(server_address=server_address, account=other_account,
+                            meeting_id=new_meeting.meeting_id, user_id=participant.id,
+                            user_token=participant.secret_token)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id)
+    assert len(meeting_info.participants) == 2
+
+
+def test_account_credentials_are_checked(server_address):
+    account = create_account_and_validate(server_address=server_address, name="Anna")
+    wrong_token = {**account, "account_token": str(uuid.uuid4())}
+    unknown_account = {"account_id": str(uuid.uuid4()), "account_token": account["account_token"]}
+
+    assert get_account_meetings(server_address=server_address, account=wrong_token).status_code == 403
+    assert get_account_meetings(server_address=server_address, account=unknown_account).status_code == 401
+
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     account=wrong_token)
+    assert create_meeting(server_address=server_address, data=meeting_data).status_code == 403
+    new_meeting = _create_meeting(server_address, "test name")
+    response = join_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id, name="Anna",
+                            account=unknown_account)
+    assert response.status_code == 401, f"{response.status_code=}"
+    response = link_account(server_address=server_address, account=account, meeting_id=new_meeting.meeting_id,
+                            user_id=new_meeting.user_id, user_token=uuid.uuid4())
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_account_name_is_validated(server_address):
+    response = create_account(server_address=server_address, name=" ")
+    assert response.status_code == 422, f"{response.status_code=}"
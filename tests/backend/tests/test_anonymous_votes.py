@@ -0,0 +1,96 @@
+from datetime import date
+
+from tests.utils.actions import add_proposed_date_and_validate, cast_vote_and_validate, \
+    create_meeting_and_validate, export_meeting, get_meeting_info_and_validate, join_meeting_and_validate
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, DateVoteCounts, MeetingVote, \
+    Vote
+
+
+def _create_meeting_with_votes(server_address, settings):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     settings=settings)
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+    meeting_id = new_meeting.meeting_id
+
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    participants = [join_meeting_and_validate(server_address=server_address, meeting_id=meeting_id, name=name)
+                    for name in ["user2", "user3"]]
+    for participant, vote, comment in zip(participants, [Vote.YES, Vote.NO], ["sure", "busy"]):
+        data = CastVoteData(user_id=participant.id, user_token=participant.secret_token,
+                            date_id=date_id, vote=vote, comment=comment)
+        cast_vote_and_validate(server_address=server_address,
+                               meeting_id=meeting_id, data=data)
+
+    return new_meeting, participants, date_id
+
+
+def test_vote_counts_are_reported(server_address):
+    new_meeting, _, date_id = _create_meeting_with_votes(server_address, None)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert len(meeting_info.votes) == 2
+    assert meeting_info.vote_counts == [DateVoteCounts(date_id=date_id, yes=1, maybe=0, no=1,
+                                                       comments=["busy", "sure"])]
+
+
+def test_anonymous_votes_are_reported_only_as_counts(server_address):
+    new_meeting, _, date_id = _create_meeting_with_votes(
+        server_address, {"anonymous_votes": True})
+
+    # Not even the creator can see who voted
+    for viewer, viewer_token in [(None, None), (new_meeting.user_id, new_meeting.user_secret_token)]:
+        meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id,
+                                                     viewer=viewer, viewer_token=viewer_token)
+        assert meeting_info.votes == []
+        assert meeting_info.vote_counts == [DateVoteCounts(date_id=date_id, yes=1, maybe=0, no=1,
+                                                           comments=["busy", "sure"])]
+
+
+def test_participant_can_edit_own_anonymous_vote(server_address):
+    new_meeting, participants, date_id = _create_meeting_with_votes(
+        server_address, {"anonymous_votes": True})
+    participant = participants[0]
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id,
+                                                 viewer=participant.id, viewer_token=participant.secret_token)
+    assert meeting_info.votes == [MeetingVote(participant_id=participant.id, date_id=date_id, vote=Vote.YES,
+                                              comment="sure")]
+
+    data = CastVoteData(user_id=participant.id, user_token=participant.secret_token,
+                        date_id=date_id, vote=Vote.MAYBE)
+    cast_vote_and_validate(server_address=server_address,
+                           meeting_id=new_meeting.meeting_id, data=data)
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id,
+                                                 viewer=participant.id, viewer_token=participant.secret_token)
+    assert meeting_info.votes == [MeetingVote(participant_id=participant.id, date_id=date_id, vote=Vote.MAYBE,
+                                              comment=None)]
+    assert meeting_info.vote_counts == [DateVoteCounts(date_id=date_id, yes=0, maybe=1, no=1, comments=["busy"])]
+
+
+def test_hidden_anonymous_votes_have_no_counts(server_address):
+    new_meeting, _, _ = _create_meeting_with_votes(
+        server_address, {"anonymous_votes": True, "hide_results": True})
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.votes == []
+    assert meeting_info.vote_counts == []
+    assert meeting_info.results_hidden
+
+
+def test_meeting_with_anonymous_votes_can_not_be_exported(server_address):
+    new_meeting, _, _ = _create_meeting_with_votes(
+        server_address, {"anonymous_votes": True})
+
+    response = export_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                              user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    assert response.status_code == 403, f"{response.status_code=}"
+    assert "participant_id" not in response.text
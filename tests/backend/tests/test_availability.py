@@ -0,0 +1,163 @@
+from datetime import date, time
+import uuid
+
+from tests.utils.actions import add_proposed_date, create_meeting, create_meeting_and_validate, \
+    export_meeting_and_validate, get_availability, get_availability_and_validate, \
+    get_meeting_info_and_validate, join_meeting_and_validate, set_availability, set_availability_and_validate
+from tests.utils.models import AddProposedDateData, AvailabilityGrid, CreateMeetingData, SetAvailabilityData
+
+# Two days, 09:00 - 11:00 in 30 minute slots
+GRID = AvailabilityGrid(first_day=date(2023, 7, 24), last_day=date(2023, 7, 25),
+                        day_starts_at=time(9), day_ends_at=time(11), slot_minutes=30)
+
+
+def _create_availability_meeting(server_address):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     meeting_timezone="Europe/Warsaw", availability_grid=GRID)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_create_availability_meeting(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.mode == "availability"
+
+    availability = get_availability_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id)
+    assert availability["first_day"] == "2023-07-24"
+    assert availability["day_starts_at"] == "09:00:00"
+    assert availability["slot_minutes"] == 30
+    assert availability["participants"] == []
+    assert availability["heatmap"] == [[0, 0, 0, 0], [0, 0, 0, 0]]
+    assert availability["common_windows"] == []
+
+
+def test_heatmap_and_common_windows(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    user2 = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+
+    for user_id, user_token, slots in [
+        (new_meeting.user_id, new_meeting.user_secret_token, "01111101"),
+        (user2.id, user2.secret_token, "01101111"),
+    ]:
+        data = SetAvailabilityData(
+            user_id=user_id, user_token=user_token, slots=slots)
+        set_availability_and_validate(
+            server_address=server_address, meeting_id=meeting_id, data=data)
+
+    availability = get_availability_and_validate(
+        server_address=server_address, meeting_id=meeting_id)
+    assert availability["heatmap"] == [[0, 2, 2, 1], [2, 2, 1, 2]]
+    longest, second, third = availability["common_windows"]
+    assert longest["starts_at"] == "2023-07-24T07:30:00Z"
+    assert longest["duration_minutes"] == 60
+    assert longest["local"]["starts_at"] == "2023-07-24T09:30:00+02:00"
+    assert second["starts_at"] == "2023-07-25T07:00:00Z"
+    assert third["duration_minutes"] == 30
+
+
+def test_availability_can_be_replaced(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+
+    for slots in ["11111111", "00000001"]:
+        data = SetAvailabilityData(
+            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token, slots=slots)
+        set_availability_and_validate(
+            server_address=server_address, meeting_id=meeting_id, data=data)
+
+    availability = get_availability_and_validate(
+        server_address=server_address, meeting_id=meeting_id)
+    participant, = availability["participants"]
+    assert participant == {"participant_id": str(
+        new_meeting.user_id), "slots": "00000001"}
+
+
+def test_set_availability_with_invalid_slots_returns_400_bad_request(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+
+    for slots in ["1111111", "111111111", "1111111x"]:
+        data = SetAvailabilityData(
+            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token, slots=slots)
+        response = set_availability(
+            server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+        assert response.status_code == 400, f"{slots=} {response.status_code=}"
+
+
+def test_set_availability_in_other_meeting_returns_403_forbidden(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+    other_meeting = _create_availability_meeting(server_address)
+
+    data = SetAvailabilityData(user_id=other_meeting.user_id, user_token=other_meeting.user_secret_token,
+                               slots="11111111")
+    response = set_availability(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_availability_of_poll_returns_409_conflict(server_address):
+    meeting_data = CreateMeetingData(
+        meeting_name="test name", meeting_description=None, user_name="user1")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+
+    assert get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id).mode == "poll"
+    response = get_availability(
+        server_address=server_address, meeting_id=new_meeting.meeting_id)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    data = SetAvailabilityData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               slots="11111111")
+    response = set_availability(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+def test_proposing_date_in_availability_meeting_returns_409_conflict(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 24))
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+def test_availability_of_nonexistent_meeting_returns_404_not_found(server_address):
+    response = get_availability(
+        server_address=server_address, meeting_id=uuid.uuid4())
+
+    assert response.status_code == 404, f"{response.status_code=}"
+
+
+def test_create_meeting_with_invalid_grid_returns_400_bad_request(server_address):
+    grid = AvailabilityGrid(first_day=date(2023, 7, 24), last_day=date(2023, 7, 25),
+                            day_starts_at=time(9), day_ends_at=time(11), slot_minutes=45)
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     availability_grid=grid)
+
+    response = create_meeting(server_address=server_address, data=meeting_data)
+
+    assert response.status_code == 400, f"{response.status_code=}"
+
+
+def test_export_contains_availability(server_address):
+    new_meeting = _create_availability_meeting(server_address)
+    data = SetAvailabilityData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               slots="00111100")
+    set_availability_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    assert export["meeting"]["availability_grid"] == GRID.to_json_dict()
+    assert export["availability"] == [
+        {"participant_id": str(new_meeting.user_id), "slots": "00111100"}]
@@ -0,0 +1,84 @@
+from datetime import date
+
+import requests
+
+from tests.utils.actions import add_proposed_date, add_proposed_date_and_validate, cast_vote_and_validate, \
+    close_voting, create_meeting_and_validate, get_meeting_info, get_meeting_info_and_validate
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, Vote
+
+
+def _create_meeting(server_address):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1")
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def _date_data(new_meeting, day):
+    return AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, day))
+
+
+def test_new_meeting_has_first_version(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    response = get_meeting_info(server_address=server_address, id=new_meeting.meeting_id)
+    assert response.status_code == 200, f"{response.status_code=}"
+    assert response.json()["version"] == 1
+    assert response.headers["ETag"] == '"1"'
+
+
+def test_changes_increment_version(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+
+    add_proposed_date_and_validate(server_address=server_address, meeting_id=meeting_id,
+                                   data=_date_data(new_meeting, 25), version=1)
+    add_proposed_date_and_validate(server_address=server_address, meeting_id=meeting_id,
+                                   data=_date_data(new_meeting, 26), version=2)
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=meeting_id)
+    assert meeting_info.version == 3
+
+
+def test_stale_version_is_rejected(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    add_proposed_date_and_validate(server_address=server_address, meeting_id=meeting_id,
+                                   data=_date_data(new_meeting, 25), version=1)
+
+    response = add_proposed_date(server_address=server_address, meeting_id=meeting_id,
+                                 data=_date_data(new_meeting, 26), version=1)
+    assert response.status_code == 412, f"{response.status_code=}"
+    response = close_voting(server_address=server_address, meeting_id=meeting_id, user_id=new_meeting.user_id,
+                            user_token=new_meeting.user_secret_token, version=1)
+    assert response.status_code == 412, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=meeting_id)
+    assert meeting_info.version == 2
+    assert len(meeting_info.proposed_dates) == 1
+    assert meeting_info.voting_closed_at is None
+
+
+def test_if_match_is_required(server_address):
+    new_meeting = _create_meeting(server_address)
+    url = f"http://{server_address}/meeting/{new_meeting.meeting_id}/date"
+    data = _date_data(new_meeting, 25).to_json_dict()
+
+    response = requests.post(url=url, json=data)
+    assert response.status_code == 428, f"{response.status_code=}"
+
+    response = requests.post(url=url, json=data, headers={"If-Match": "1"})
+    assert response.status_code == 400, f"{response.status_code=}"
+
+
+def test_votes_do_not_change_version(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    add_proposed_date_and_validate(server_address=server_address, meeting_id=meeting_id,
+                                   data=_date_data(new_meeting, 25), version=1)
+    date_id = get_meeting_info_and_validate(server_address=server_address, id=meeting_id).proposed_dates[0].id
+    data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token, date_id=date_id,
+                        vote=Vote.YES)
+    cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=meeting_id)
+    assert meeting_info.version == 2
@@ -0,0 +1,223 @@
+from datetime import date, datetime, timezone
+import json
+import uuid
+
+from tests.utils.actions import add_proposed_date_and_validate, create_meeting_and_validate, \
+    export_meeting, export_meeting_and_validate, get_meeting_info_and_validate, import_meeting, \
+    join_meeting_and_validate, post_comment_and_validate
+from tests.utils.models import AddProposedDateData, CreateMeetingData, MeetingParticipant, \
+    PostCommentData
+
+
+def _create_populated_meeting(server_address):
+    meeting_data = CreateMeetingData(
+        meeting_name="test name", meeting_description="description", user_name="user1")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+    user2 = join_meeting_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2")
+
+    comment_data = PostCommentData(
+        user_id=user2.id, user_token=user2.secret_token, message="Hello")
+    post_comment_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=comment_data)
+
+    for proposed_date in [
+        AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                            date=date(2023, 7, 25)),
+        AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                            starts_at=datetime(2023, 7, 26, 14, tzinfo=timezone.utc), duration_minutes=90),
+    ]:
+        add_proposed_date_and_validate(
+            server_address=server_address, meeting_id=new_meeting.meeting_id, data=proposed_date)
+
+    return new_meeting, user2
+
+
+def _with_fresh_ids(export: dict) -> dict:
+    """Replaces every id in exported document, as if it came from other environment"""
+
+    text = json.dumps(export)
+    ids = [export["meeting"]["id"]]
+    ids += [p["id"] for p in export["participants"]]
+    ids += [d["id"] for d in export["proposed_dates"]]
+    ids += [c["id"] for c in export["comments"]]
+    for id in ids:
+        text = text.replace(id, str(uuid.uuid4()))
+    return json.loads(text)
+
+
+def test_export_meeting(server_address):
+    new_meeting, user2 = _create_populated_meeting(server_address)
+
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    assert export["format_version"] == 1
+    assert export["meeting"]["id"] == str(new_meeting.meeting_id)
+    assert export["meeting"]["name"] == "test name"
+    assert export["meeting"]["description"] == "description"
+    assert export["meeting"]["created_by"] == str(new_meeting.user_id)
+    assert sorted(p["id"] for p in export["participants"]) == \
+        sorted([str(new_meeting.user_id), str(user2.id)])
+    assert all("secret_token" not in p for p in export["participants"])
+    all_day, time_slot = export["proposed_dates"]
+    assert all_day["date"] == "2023-07-25"
+    assert time_slot["starts_at"] == "2023-07-26T14:00:00Z"
+    assert time_slot["duration_minutes"] == 90
+    assert export["votes"] == []
+    comment, = export["comments"]
+    assert comment["written_by"] == str(user2.id)
+    assert comment["message"] == "Hello"
+
+
+def test_export_meeting_by_participant_returns_403_forbidden(server_address):
+    new_meeting, user2 = _create_populated_meeting(server_address)
+
+    response = export_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                              user_id=user2.id, user_token=user2.secret_token)
+
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_export_nonexistent_meeting_returns_404_not_found(server_address):
+    new_meeting, _ = _create_populated_meeting(server_address)
+
+    response = export_meeting(server_address=server_address, meeting_id=uuid.uuid4(),
+                              user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    assert response.status_code == 404, f"{response.status_code=}"
+
+
+def test_import_meeting(server_address, admin_token):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    export = _with_fresh_ids(export)
+
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+    assert response.status_code == 201, f"{response.status_code=}"
+
+    imported = response.json()
+    assert imported["meeting_id"] == export["meeting"]["id"]
+    assert sorted(p["id"] for p in imported["participants"]) == \
+        sorted(p["id"] for p in export["participants"])
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=uuid.UUID(imported["meeting_id"]))
+    assert meeting_info.name == export["meeting"]["name"]
+    assert str(meeting_info.created_by) == export["meeting"]["created_by"]
+    assert sorted(meeting_info.participants) == sorted(
+        MeetingParticipant(id=uuid.UUID(p["id"]), name=p["name"], timezone=p["timezone"])
+        for p in export["participants"])
+    comment, = meeting_info.comments
+    assert comment.message == "Hello"
+    assert sorted(str(d.id) for d in meeting_info.proposed_dates) == \
+        sorted(d["id"] for d in export["proposed_dates"])
+
+    # Imported participants can use their new credentials
+    creator = next(p for p in imported["participants"]
+                   if p["id"] == export["meeting"]["created_by"])
+    reexport = export_meeting_and_validate(server_address=server_address, meeting_id=imported["meeting_id"],
+                                           user_id=creator["id"], user_token=creator["secret_token"])
+    assert reexport["comments"] == export["comments"]
+
+
+def test_import_existing_meeting_returns_409_conflict(server_address, admin_token):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+def test_import_meeting_with_invalid_admin_token_returns_403_forbidden(server_address):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    response = import_meeting(
+        server_address=server_address, admin_token=str(uuid.uuid4()), export=_with_fresh_ids(export))
+
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_import_meeting_with_unsupported_version_returns_400_bad_request(server_address, admin_token):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    export = _with_fresh_ids(export)
+    export["format_version"] = 999
+
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+
+    assert response.status_code == 400, f"{response.status_code=}"
+
+
+
+def test_import_meeting_with_duplicate_participant_names_returns_400_bad_request(server_address, admin_token):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    export = _with_fresh_ids(export)
+    export["meeting"]["settings"]["unique_participant_names"] = True
+    export["participants"][1]["name"] = " USER1 "
+
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+
+    assert response.status_code == 400, f"{response.status_code=}"
+
+    export["meeting"]["settings"]["unique_participant_names"] = False
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+
+    assert response.status_code == 201, f"{response.status_code=}"
+
+
+def test_import_meeting_normalizes_text(server_address, admin_token):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    export = _with_fresh_ids(export)
+    export["meeting"]["name"] = "  Café   meetup "
+    export["meeting"]["description"] = " first line\r\nsecond line  "
+    export["comments"][0]["message"] = "\tHello\r\n"
+
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+    assert response.status_code == 201, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=uuid.UUID(export["meeting"]["id"]))
+    assert meeting_info.name == "Café meetup"
+    assert meeting_info.description == "first line\nsecond line"
+    comment, = meeting_info.comments
+    assert comment.message == "Hello"
+
+
+def test_participants_that_joined_at_the_same_time_are_ordered_by_id(server_address, admin_token):
+    new_meeting, _ = _create_populated_meeting(server_address)
+    for name in ["user3", "user4"]:
+        join_meeting_and_validate(
+            server_address=server_address, meeting_id=new_meeting.meeting_id, name=name)
+    export = export_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                         user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    export = _with_fresh_ids(export)
+    for participant in export["participants"]:
+        participant["joined_at"] = "2030-01-01T10:00:00.123456Z"
+
+    response = import_meeting(
+        server_address=server_address, admin_token=admin_token, export=export)
+    assert response.status_code == 201, f"{response.status_code=}"
+
+    expected = sorted(uuid.UUID(p["id"]) for p in export["participants"])
+    for _ in range(3):
+        meeting_info = get_meeting_info_and_validate(
+            server_address=server_address, id=uuid.UUID(export["meeting"]["id"]))
+        assert [p.id for p in meeting_info.participants] == expected
@@ -0,0 +1,117 @@
+from datetime import date, datetime, timezone
+
+from tests.utils.actions import add_proposed_date_and_validate, create_meeting_and_validate, \
+    generate_proposed_dates, generate_proposed_dates_and_validate, get_meeting_info_and_validate, \
+    join_meeting_and_validate
+from tests.utils.models import AddProposedDateData, CreateMeetingData
+
+
+def _create_meeting(server_address, meeting_timezone=None):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     meeting_timezone=meeting_timezone)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_generate_weekdays(server_address):
+    new_meeting = _create_meeting(server_address)
+    rule = {
+        "first_day": "2023-08-11",
+        "last_day": "2023-08-24",
+        "weekdays": ["monday", "tuesday", "wednesday", "thursday", "friday"],
+        "excluded_dates": ["2023-08-15"],
+    }
+
+    generated = generate_proposed_dates_and_validate(server_address=server_address,
+                                                     meeting_id=new_meeting.meeting_id, user_id=new_meeting.user_id,
+                                                     user_token=new_meeting.user_secret_token, rule=rule)
+
+    assert [d.date for d in generated] == [
+        date(2023, 8, 11), date(2023, 8, 14), date(2023, 8, 16), date(2023, 8, 17), date(2023, 8, 18),
+        date(2023, 8, 21), date(2023, 8, 22), date(2023, 8, 23), date(2023, 8, 24),
+    ]
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert sorted(d.id for d in meeting_info.proposed_dates) == sorted(
+        d.id for d in generated)
+
+
+def test_generate_time_slots_with_recurrence(server_address):
+    new_meeting = _create_meeting(server_address, meeting_timezone="Europe/Warsaw")
+    rule = {
+        "first_day": "2023-07-31",
+        "last_day": "2023-09-30",
+        "recurrence": "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=3",
+        "time_slot": {"starts_at": "18:00:00", "duration_minutes": 90},
+    }
+
+    generated = generate_proposed_dates_and_validate(server_address=server_address,
+                                                     meeting_id=new_meeting.meeting_id, user_id=new_meeting.user_id,
+                                                     user_token=new_meeting.user_secret_token, rule=rule)
+
+    assert [d.starts_at for d in generated] == [
+        datetime(2023, 7, 31, 16, tzinfo=timezone.utc),
+        datetime(2023, 8, 2, 16, tzinfo=timezone.utc),
+        datetime(2023, 8, 14, 16, tzinfo=timezone.utc),
+    ]
+    assert all(d.duration_minutes == 90 for d in generated)
+    assert all(d.local.timezone == "Europe/Warsaw" for d in generated)
+
+
+def test_generate_too_many_dates_returns_400_bad_request(server_address):
+    new_meeting = _create_meeting(server_address)
+    rule = {"first_day": "2023-01-01", "last_day": "2023-12-31"}
+
+    response = generate_proposed_dates(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                       user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                       rule=rule)
+
+    assert response.status_code == 400, f"{response.status_code=}"
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.proposed_dates == []
+
+
+def test_generate_with_invalid_rule_returns_400_bad_request(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    for rule in [
+        {"first_day": "2023-08-02", "last_day": "2023-08-01"},
+        {"first_day": "2023-08-01", "last_day": "2023-08-31", "recurrence": "FREQ=YEARLY"},
+        {"first_day": "2023-08-01", "last_day": "2023-08-31", "weekdays": ["someday"]},
+        {"first_day": "2023-08-01", "last_day": "2023-08-01", "excluded_dates": ["2023-08-01"]},
+    ]:
+        response = generate_proposed_dates(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                           user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                           rule=rule)
+        assert response.status_code in (400, 422), f"{rule=} {response.status_code=}"
+
+
+def test_generate_already_proposed_date_returns_409_conflict(server_address):
+    new_meeting = _create_meeting(server_address)
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 8, 3))
+    add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+    rule = {"first_day": "2023-08-01", "last_day": "2023-08-05"}
+
+    response = generate_proposed_dates(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                       user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                       rule=rule)
+
+    assert response.status_code == 409, f"{response.status_code=}"
+    # Whole rule is rejected, not only the conflicting date
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert len(meeting_info.proposed_dates) == 1
+
+
+def test_generate_as_participant_returns_403_forbidden(server_address):
+    new_meeting = _create_meeting(server_address)
+    user2 = join_meeting_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2")
+    rule = {"first_day": "2023-08-01", "last_day": "2023-08-05"}
+
+    response = generate_proposed_dates(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                       user_id=user2.id, user_token=user2.secret_token, rule=rule)
+
+    assert response.status_code == 403, f"{response.status_code=}"
@@ -0,0 +1,103 @@
+from datetime import date
+
+from tests.utils.actions import add_proposed_date_and_validate, cast_vote, cast_vote_and_validate, close_voting, \
+    create_meeting_and_validate, get_meeting_info, get_meeting_info_and_validate, join_meeting_and_validate
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, Vote
+
+
+def _create_meeting_with_votes(server_address, hide_results):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     settings={"hide_results": hide_results})
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+    meeting_id = new_meeting.meeting_id
+
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    participants = [join_meeting_and_validate(server_address=server_address, meeting_id=meeting_id, name=name)
+                    for name in ["user2", "user3"]]
+    for participant in participants:
+        vote = CastVoteData(user_id=participant.id, user_token=participant.secret_token,
+                            date_id=date_id, vote=Vote.YES)
+        cast_vote_and_validate(server_address=server_address,
+                               meeting_id=meeting_id, data=vote)
+
+    return new_meeting, participants
+
+
+def test_votes_are_visible_by_default(server_address):
+    new_meeting, _ = _create_meeting_with_votes(server_address, hide_results=False)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert len(meeting_info.votes) == 2
+    assert not meeting_info.results_hidden
+
+
+def test_anonymous_viewer_sees_no_votes(server_address):
+    new_meeting, participants = _create_meeting_with_votes(server_address, hide_results=True)
+
+    # Participant id without token does not reveal their votes
+    for viewer in [None, participants[0].id]:
+        meeting_info = get_meeting_info_and_validate(
+            server_address=server_address, id=new_meeting.meeting_id, viewer=viewer)
+        assert meeting_info.votes == []
+        assert meeting_info.results_hidden
+
+
+def test_participant_sees_only_own_votes(server_address):
+    new_meeting, participants = _create_meeting_with_votes(server_address, hide_results=True)
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id,
+                                                 viewer=participants[0].id, viewer_token=participants[0].secret_token)
+    assert [vote.participant_id for vote in meeting_info.votes] == [participants[0].id]
+    assert meeting_info.results_hidden
+
+
+def test_creator_sees_all_votes(server_address):
+    new_meeting, _ = _create_meeting_with_votes(server_address, hide_results=True)
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id,
+                                                 viewer=new_meeting.user_id, viewer_token=new_meeting.user_secret_token)
+    assert len(meeting_info.votes) == 2
+    assert not meeting_info.results_hidden
+
+
+def test_invalid_viewer_token_returns_403(server_address):
+    new_meeting, participants = _create_meeting_with_votes(server_address, hide_results=True)
+
+    response = get_meeting_info(server_address=server_address, id=new_meeting.meeting_id,
+                                viewer=participants[0].id, viewer_token=participants[1].secret_token)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_votes_are_revealed_after_voting_is_closed(server_address):
+    new_meeting, participants = _create_meeting_with_votes(server_address, hide_results=True)
+    meeting_id = new_meeting.meeting_id
+
+    response = close_voting(server_address=server_address, meeting_id=meeting_id,
+                            user_id=participants[0].id, user_token=participants[0].secret_token)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+    response = close_voting(server_address=server_address, meeting_id=meeting_id,
+                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert len(meeting_info.votes) == 2
+    assert not meeting_info.results_hidden
+    assert meeting_info.voting_closed_at is not None
+
+    response = close_voting(server_address=server_address, meeting_id=meeting_id,
+                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    vote = CastVoteData(user_id=participants[0].id, user_token=participants[0].secret_token,
+                        date_id=meeting_info.proposed_dates[0].id, vote=Vote.NO)
+    response = cast_vote(server_address=server_address,
+                         meeting_id=meeting_id, data=vote)
+    assert response.status_code == 409, f"{response.status_code=}"
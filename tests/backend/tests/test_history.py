@@ -0,0 +1,170 @@
+from datetime import date
+
+from tests.utils.actions import add_proposed_date_and_validate, cast_vote_and_validate, close_voting, \
+    create_meeting_and_validate, finalize_meeting, get_meeting_history, get_meeting_history_and_validate, \
+    join_meeting_and_validate, post_comment_and_validate, review_proposed_date, set_participant_required
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, PostCommentData, Vote
+
+
+def _create_meeting(server_address, settings=None):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     settings=settings)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def _get_history(server_address, new_meeting):
+    return get_meeting_history_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+
+def test_new_meeting_has_empty_history(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    assert _get_history(server_address, new_meeting) == []
+
+
+def test_only_creator_can_see_history(server_address):
+    new_meeting = _create_meeting(server_address)
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2")
+
+    response = get_meeting_history(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                   user_id=participant.id, user_token=participant.secret_token)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_vote_changes_are_recorded(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+    for vote, comment in [(Vote.YES, None), (Vote.NO, "sorry")]:
+        data = CastVoteData(user_id=participant.id, user_token=participant.secret_token, date_id=date_id,
+                            vote=vote, comment=comment)
+        cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+    data = PostCommentData(user_id=participant.id, user_token=participant.secret_token, message="hello")
+    post_comment_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    history = _get_history(server_address, new_meeting)
+
+    assert [entry["action"] for entry in history] == ["date_proposed", "vote_cast", "vote_cast", "comment_posted"]
+    assert [entry["actor_id"] for entry in history] == \
+        [str(new_meeting.user_id)] + [str(participant.id)] * 3
+    assert history[0]["proposed_date_id"] == str(date_id)
+    assert history[0]["new_value"] == {"option": {"date": "2023-07-25"}, "approved": True}
+    assert history[1]["old_value"] is None
+    assert history[1]["new_value"] == {"vote": "yes", "comment": None}
+    assert history[2]["old_value"] == {"vote": "yes", "comment": None}
+    assert history[2]["new_value"] == {"vote": "no", "comment": "sorry"}
+    assert history[3]["new_value"]["message"] == "hello"
+    assert [entry["id"] for entry in history] == sorted(entry["id"] for entry in history)
+
+
+def test_date_reviews_are_recorded(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"participants_can_propose": True, "proposals_require_approval": True})
+    meeting_id = new_meeting.meeting_id
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+    date_ids = []
+    for day in [25, 26]:
+        data = AddProposedDateData(user_id=participant.id, user_token=participant.secret_token,
+                                   date=date(2023, 7, day))
+        date_ids.append(add_proposed_date_and_validate(
+            server_address=server_address, meeting_id=meeting_id, data=data).id)
+    for date_id, approve in zip(date_ids, [True, False]):
+        response = review_proposed_date(server_address=server_address, meeting_id=meeting_id, date_id=date_id,
+                                        user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                        approve=approve)
+        assert response.status_code == 204, f"{response.status_code=}"
+
+    history = _get_history(server_address, new_meeting)
+
+    assert [(entry["action"], entry["proposed_date_id"]) for entry in history] == [
+        ("date_proposed", str(date_ids[0])),
+        ("date_proposed", str(date_ids[1])),
+        ("date_approved", str(date_ids[0])),
+        ("date_rejected", str(date_ids[1])),
+    ]
+    assert history[2]["actor_id"] == str(new_meeting.user_id)
+    assert history[2]["new_value"] == {"approved": True}
+    assert history[3]["old_value"] == {"option": {"date": "2023-07-26"}, "approved": False}
+    assert history[3]["new_value"] is None
+
+
+def test_anonymous_votes_hide_actor(server_address):
+    new_meeting = _create_meeting(server_address, {"anonymous_votes": True})
+    meeting_id = new_meeting.meeting_id
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+    data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token, date_id=date_id,
+                        vote=Vote.MAYBE)
+    cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    history = _get_history(server_address, new_meeting)
+
+    assert history[0]["actor_id"] == str(new_meeting.user_id)
+    assert history[1]["action"] == "vote_cast"
+    assert history[1]["actor_id"] is None
+
+
+def test_required_participants_and_finalization_are_recorded(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+    response = set_participant_required(server_address=server_address, meeting_id=meeting_id,
+                                        participant_id=participant.id, user_id=new_meeting.user_id,
+                                        user_token=new_meeting.user_secret_token, required=True)
+    assert response.status_code == 204, f"{response.status_code=}"
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id,
+                                user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                date_id=date_id)
+    assert response.status_code == 200, f"{response.status_code=}"
+
+    history = _get_history(server_address, new_meeting)
+
+    assert [(entry["action"], entry["actor_id"]) for entry in history] == [
+        ("date_proposed", str(new_meeting.user_id)),
+        ("participant_required_changed", str(new_meeting.user_id)),
+        ("meeting_finalized", str(new_meeting.user_id)),
+    ]
+    assert history[1]["old_value"] == {"participant_id": str(participant.id), "required": False}
+    assert history[1]["new_value"] == {"participant_id": str(participant.id), "required": True}
+    assert history[2]["proposed_date_id"] == str(date_id)
+
+
+def test_closing_voting_is_recorded(server_address):
+    new_meeting = _create_meeting(server_address, {"auto_finalize": True})
+    meeting_id = new_meeting.meeting_id
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+    data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token, date_id=date_id,
+                        vote=Vote.YES)
+    cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+    response = close_voting(server_address=server_address, meeting_id=meeting_id,
+                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    history = _get_history(server_address, new_meeting)
+
+    # Automatic finalization has no actor
+    assert [(entry["action"], entry["actor_id"]) for entry in history] == [
+        ("date_proposed", str(new_meeting.user_id)),
+        ("vote_cast", str(new_meeting.user_id)),
+        ("voting_closed", str(new_meeting.user_id)),
+        ("meeting_finalized", None),
+    ]
+    assert history[3]["proposed_date_id"] == str(date_id)
@@ -0,0 +1,96 @@
+from datetime import date, datetime, timezone
+
+from tests.utils.actions import add_proposed_date, add_proposed_date_and_validate, create_meeting, \
+    create_meeting_and_validate, generate_proposed_dates_and_validate, get_meeting_info_and_validate
+from tests.utils.models import AddProposedDateData, CreateMeetingData
+
+
+def _create_meeting(server_address, settings):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     meeting_timezone="Europe/Warsaw", settings=settings)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_default_settings(server_address):
+    meeting_data = CreateMeetingData(
+        meeting_name="test name", meeting_description=None, user_name="user1")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+
+    assert meeting_info.settings == {
+        "holiday_calendar": None, "holiday_policy": "flag", "exclude_weekends": False,
+        "participants_can_propose": False, "proposals_require_approval": False, "hide_results": False,
+        "anonymous_votes": False, "auto_finalize": False,
+        "min_yes_votes": None, "max_capacity": None, "unique_participant_names": False}
+
+
+def test_holidays_and_weekends_are_flagged(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"holiday_calendar": "PL", "exclude_weekends": True})
+
+    for proposed_date in [
+        # Independence Day (Saturday)
+        AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                            date=date(2023, 11, 11)),
+        # Friday 23:30 UTC is All Saints' Day in Warsaw
+        AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                            starts_at=datetime(2023, 10, 31, 23, 30, tzinfo=timezone.utc), duration_minutes=60),
+        AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                            date=date(2023, 11, 14)),
+    ]:
+        add_proposed_date_and_validate(
+            server_address=server_address, meeting_id=new_meeting.meeting_id, data=proposed_date)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    marks = {(d.date or d.starts_at.date()): (d.holiday, d.weekend)
+             for d in meeting_info.proposed_dates}
+    assert marks == {
+        date(2023, 11, 11): ("Independence Day", True),
+        date(2023, 10, 31): ("All Saints' Day", False),
+        date(2023, 11, 14): (None, False),
+    }
+
+
+def test_proposing_holiday_with_reject_policy_returns_400_bad_request(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"holiday_calendar": "US", "holiday_policy": "reject", "exclude_weekends": True})
+
+    for day in [date(2023, 11, 23), date(2023, 11, 25)]:
+        data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                   date=day)
+        response = add_proposed_date(
+            server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+        assert response.status_code == 400, f"{day=} {response.status_code=}"
+
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 11, 24))
+    add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+
+def test_generating_skips_rejected_dates(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"holiday_calendar": "PL", "holiday_policy": "reject", "exclude_weekends": True})
+    rule = {"first_day": "2023-10-30", "last_day": "2023-11-12"}
+
+    generated = generate_proposed_dates_and_validate(server_address=server_address,
+                                                     meeting_id=new_meeting.meeting_id, user_id=new_meeting.user_id,
+                                                     user_token=new_meeting.user_secret_token, rule=rule)
+
+    assert [d.date for d in generated] == [
+        date(2023, 10, 30), date(2023, 10, 31), date(2023, 11, 2), date(2023, 11, 3),
+        date(2023, 11, 6), date(2023, 11, 7), date(2023, 11, 8), date(2023, 11, 9), date(2023, 11, 10),
+    ]
+
+
+def test_create_meeting_with_unknown_calendar_returns_400_bad_request(server_address):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     settings={"holiday_calendar": "XX"})
+
+    response = create_meeting(server_address=server_address, data=meeting_data)
+
+    assert response.status_code == 400, f"{response.status_code=}"
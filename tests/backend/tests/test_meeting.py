@@ -1,4 +1,4 @@
-from datetime import datetime, timezone
+from datetime import date, datetime, timezone
 import uuid
 
 import pytest
@@ -6,8 +6,9 @@ import requests
 
 from tests.utils.actions import create_meeting_and_validate, get_meeting_info_and_validate, \
     join_meeting_and_validate, get_meeting_info, post_comment, post_comment_and_validate, \
-    join_meeting
-from tests.utils.models import CreateMeetingData, MeetingParticipant, PostCommentData
+    join_meeting, add_proposed_date_and_validate, cast_vote_and_validate
+from tests.utils.models import CreateMeetingData, MeetingParticipant, PostCommentData, \
+    AddProposedDateData, CastVoteData, Vote
 
 
 def test_get_noexisting_meeting_returns_404_bad_request(server_address):
@@ -86,6 +87,56 @@ def test_join_meeting(server_address):
     assert meeting_participants == expected_participants
 
 
+def test_participants_dates_and_votes_are_ordered(server_address):
+    meeting_data = CreateMeetingData(
+        meeting_name="test name", meeting_description=None, user_name="User 1",
+        meeting_timezone="Europe/Warsaw")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+    meeting_id = new_meeting.meeting_id
+
+    users = [(new_meeting.user_id, new_meeting.user_secret_token)]
+    for name in ["User 2", "User 3", "User 4"]:
+        user = join_meeting_and_validate(
+            server_address=server_address, meeting_id=meeting_id, name=name)
+        users.append((user.id, user.secret_token))
+
+    creator_id, creator_token = users[0]
+    options = [
+        {"starts_at": datetime(2030, 1, 1, 10, tzinfo=timezone.utc), "duration_minutes": 60},
+        {"date": date(2030, 1, 3)},
+        {"starts_at": datetime(2030, 1, 1, 10, tzinfo=timezone.utc), "duration_minutes": 30},
+        {"date": date(2030, 1, 2)},
+        {"date": date(2030, 1, 1)},
+        # January 2 in meeting's timezone
+        {"starts_at": datetime(2030, 1, 1, 23, 30, tzinfo=timezone.utc), "duration_minutes": 30},
+    ]
+    date_ids = [
+        add_proposed_date_and_validate(
+            server_address=server_address, meeting_id=meeting_id,
+            data=AddProposedDateData(user_id=creator_id, user_token=creator_token, **option)).id
+        for option in options
+    ]
+
+    for user_id, user_token in reversed(users):
+        for date_id in date_ids:
+            cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id,
+                                   data=CastVoteData(user_id=user_id, user_token=user_token,
+                                                     date_id=date_id, vote=Vote.YES))
+
+    expected_participants = [user_id for user_id, _ in users]
+    expected_dates = [date_ids[4], date_ids[2], date_ids[0], date_ids[3], date_ids[5], date_ids[1]]
+    expected_votes = [(user_id, date_id)
+                      for user_id in expected_participants for date_id in expected_dates]
+    for _ in range(3):
+        meeting_info = get_meeting_info_and_validate(
+            server_address=server_address, id=meeting_id)
+
+        assert [p.id for p in meeting_info.participants] == expected_participants
+        assert [d.id for d in meeting_info.proposed_dates] == expected_dates
+        assert [(v.participant_id, v.date_id) for v in meeting_info.votes] == expected_votes
+
+
 def test_post_comment_with_invalid_user_id_returns_401_unauthorized(server_address):
     meeting_data = CreateMeetingData(
         meeting_name="test name", meeting_description=None, user_name="user")
@@ -0,0 +1,176 @@
+from datetime import date
+
+from tests.utils.actions import add_proposed_date, add_proposed_date_and_validate, cast_vote, \
+    cast_vote_and_validate, create_meeting_and_validate, get_meeting_info_and_validate, join_meeting_and_validate, \
+    review_proposed_date
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, MeetingVote, Vote
+
+
+def _create_meeting(server_address, settings=None):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     settings=settings)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_participant_can_not_propose_date_by_default(server_address):
+    new_meeting = _create_meeting(server_address)
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2")
+
+    data = AddProposedDateData(user_id=participant.id, user_token=participant.secret_token,
+                               date=date(2023, 7, 25))
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_user_from_other_meeting_can_not_propose_date(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"participants_can_propose": True})
+    other_meeting = _create_meeting(server_address)
+
+    data = AddProposedDateData(user_id=other_meeting.user_id, user_token=other_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_participant_proposed_date_is_votable(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"participants_can_propose": True})
+    meeting_id = new_meeting.meeting_id
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+
+    data = AddProposedDateData(user_id=participant.id, user_token=participant.secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    [proposed_date] = meeting_info.proposed_dates
+    assert proposed_date.proposed_by == participant.id
+    assert proposed_date.approved
+
+    vote = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                        date_id=date_id, vote=Vote.YES, comment="works for me")
+    cast_vote_and_validate(server_address=server_address,
+                           meeting_id=meeting_id, data=vote)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.votes == [MeetingVote(participant_id=new_meeting.user_id, date_id=date_id, vote=Vote.YES,
+                                              comment="works for me")]
+
+
+def test_vote_can_be_changed(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    for vote in [Vote.YES, Vote.NO]:
+        data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                            date_id=date_id, vote=vote)
+        cast_vote_and_validate(server_address=server_address,
+                               meeting_id=meeting_id, data=data)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.votes == [MeetingVote(participant_id=new_meeting.user_id, date_id=date_id, vote=Vote.NO,
+                                              comment=None)]
+
+
+def test_pending_date_must_be_approved_before_voting(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"participants_can_propose": True, "proposals_require_approval": True})
+    meeting_id = new_meeting.meeting_id
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+
+    data = AddProposedDateData(user_id=participant.id, user_token=participant.secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert not meeting_info.proposed_dates[0].approved
+
+    vote = CastVoteData(user_id=participant.id, user_token=participant.secret_token,
+                        date_id=date_id, vote=Vote.YES)
+    response = cast_vote(server_address=server_address,
+                         meeting_id=meeting_id, data=vote)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    response = review_proposed_date(server_address=server_address, meeting_id=meeting_id, date_id=date_id,
+                                    user_id=participant.id, user_token=participant.secret_token, approve=True)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+    response = review_proposed_date(server_address=server_address, meeting_id=meeting_id, date_id=date_id,
+                                    user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                    approve=True)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.proposed_dates[0].approved
+    cast_vote_and_validate(server_address=server_address,
+                           meeting_id=meeting_id, data=vote)
+
+
+def test_rejected_date_is_removed(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"participants_can_propose": True, "proposals_require_approval": True})
+    meeting_id = new_meeting.meeting_id
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+
+    data = AddProposedDateData(user_id=participant.id, user_token=participant.secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    response = review_proposed_date(server_address=server_address, meeting_id=meeting_id, date_id=date_id,
+                                    user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                    approve=False)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.proposed_dates == []
+
+
+def test_creator_dates_do_not_need_approval(server_address):
+    new_meeting = _create_meeting(
+        server_address, {"participants_can_propose": True, "proposals_require_approval": True})
+    meeting_id = new_meeting.meeting_id
+
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data).id
+
+    response = review_proposed_date(server_address=server_address, meeting_id=meeting_id, date_id=date_id,
+                                    user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                    approve=False)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+def test_vote_on_date_from_other_meeting_returns_404(server_address):
+    new_meeting = _create_meeting(server_address)
+    other_meeting = _create_meeting(server_address)
+    data = AddProposedDateData(user_id=other_meeting.user_id, user_token=other_meeting.user_secret_token,
+                               date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=other_meeting.meeting_id, data=data).id
+
+    vote = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                        date_id=date_id, vote=Vote.YES)
+    response = cast_vote(server_address=server_address,
+                         meeting_id=new_meeting.meeting_id, data=vote)
+    assert response.status_code == 404, f"{response.status_code=}"
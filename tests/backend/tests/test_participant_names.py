@@ -0,0 +1,47 @@
+import unicodedata
+
+from tests.utils.actions import create_meeting_and_validate, get_meeting_info_and_validate, join_meeting, \
+    join_meeting_and_validate
+from tests.utils.models import CreateMeetingData
+
+
+def _create_meeting(server_address, settings=None):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="Anna",
+                                     settings=settings)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_names_are_normalized(server_address):
+    new_meeting = _create_meeting(server_address)
+    decomposed = unicodedata.normalize("NFD", "Zoë")
+    join_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                              name=f"  {decomposed} \t van  Dijk ")
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id)
+    names = sorted(participant.name for participant in meeting_info.participants)
+    assert names == ["Anna", "Zoë van Dijk"]
+
+
+def test_duplicate_names_are_allowed_by_default(server_address):
+    new_meeting = _create_meeting(server_address)
+    join_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id, name="Anna ")
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id)
+    assert sorted(participant.name for participant in meeting_info.participants) == ["Anna", "Anna"]
+
+
+def test_unique_names_are_enforced_case_insensitively(server_address):
+    new_meeting = _create_meeting(server_address, settings={"unique_participant_names": True})
+    meeting_id = new_meeting.meeting_id
+
+    for name in ["Anna", "anna ", " ANNA"]:
+        response = join_meeting(server_address=server_address, meeting_id=meeting_id, name=name)
+        assert response.status_code == 409, f"{name=} {response.status_code=}"
+
+    join_meeting_and_validate(server_address=server_address, meeting_id=meeting_id,
+                              name=unicodedata.normalize("NFD", "Zoë"))
+    response = join_meeting(server_address=server_address, meeting_id=meeting_id, name="ZOË")
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=meeting_id)
+    assert sorted(participant.name for participant in meeting_info.participants) == ["Anna", "Zoë"]
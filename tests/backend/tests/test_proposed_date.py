@@ -0,0 +1,89 @@
+from datetime import date, datetime, timedelta, timezone
+
+import pytest
+
+from tests.utils.actions import add_proposed_date, add_proposed_date_and_validate, \
+    create_meeting_and_validate, get_meeting_info_and_validate, join_meeting_and_validate
+from tests.utils.models import AddProposedDateData, CreateMeetingData, LocalTimeSlot, \
+    MeetingProposedDate
+
+
+def _create_meeting(server_address):
+    meeting_data = CreateMeetingData(
+        meeting_name="test name", meeting_description=None, user_name="user")
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def test_add_proposed_dates(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+
+    all_day = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                  date=date(2023, 7, 25))
+    all_day_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=all_day).id
+
+    time_slot = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                    starts_at=datetime(2023, 7, 25, 14, tzinfo=timezone(timedelta(hours=2))),
+                                    duration_minutes=60)
+    time_slot_id = add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=time_slot).id
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    proposed_dates = sorted(meeting_info.proposed_dates,
+                            key=lambda d: d.id != all_day_id)
+
+    assert proposed_dates == [
+        MeetingProposedDate(id=all_day_id, date=date(2023, 7, 25), proposed_by=new_meeting.user_id),
+        MeetingProposedDate(id=time_slot_id, starts_at=datetime(2023, 7, 25, 12, tzinfo=timezone.utc),
+                            duration_minutes=60,
+                            local=LocalTimeSlot(timezone="Etc/UTC",
+                                                starts_at=datetime(
+                                                    2023, 7, 25, 12, tzinfo=timezone.utc),
+                                                ends_at=datetime(2023, 7, 25, 13, tzinfo=timezone.utc)),
+                            proposed_by=new_meeting.user_id),
+    ]
+
+
+@pytest.mark.parametrize("proposed_date", [
+    {"date": date(2023, 7, 25)},
+    {"starts_at": datetime(2023, 7, 25, 14, tzinfo=timezone.utc), "duration_minutes": 30},
+])
+def test_add_duplicated_proposed_date_returns_409_conflict(server_address, proposed_date):
+    new_meeting = _create_meeting(server_address)
+    data = AddProposedDateData(user_id=new_meeting.user_id,
+                               user_token=new_meeting.user_secret_token, **proposed_date)
+    add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+@pytest.mark.parametrize("duration_minutes", [0, 24 * 60 + 1])
+def test_add_time_slot_with_invalid_duration_returns_400_bad_request(server_address, duration_minutes):
+    new_meeting = _create_meeting(server_address)
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               starts_at=datetime(2023, 7, 25, 14, tzinfo=timezone.utc),
+                               duration_minutes=duration_minutes)
+
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    assert response.status_code == 400, f"{response.status_code=}"
+
+
+def test_add_proposed_date_by_participant_returns_403_forbidden(server_address):
+    new_meeting = _create_meeting(server_address)
+    user2 = join_meeting_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2")
+    data = AddProposedDateData(
+        user_id=user2.id, user_token=user2.secret_token, date=date(2023, 7, 25))
+
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, data=data)
+
+    assert response.status_code == 403, f"{response.status_code=}"
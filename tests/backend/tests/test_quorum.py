@@ -0,0 +1,74 @@
+from tests.test_results import _create_meeting_with_votes
+from tests.utils.actions import cast_vote, create_meeting, finalize_meeting, get_meeting_info_and_validate, \
+    get_results_and_validate
+from tests.utils.models import CastVoteData, CreateMeetingData, Vote
+
+
+def test_invalid_quorum_is_rejected(server_address):
+    for settings in [{"min_yes_votes": 0}, {"max_capacity": 0}, {"min_yes_votes": 3, "max_capacity": 2}]:
+        meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                         settings=settings)
+        response = create_meeting(server_address=server_address, data=meeting_data)
+        assert response.status_code == 400, f"{response.status_code=}"
+
+
+def test_results_report_viability(server_address):
+    new_meeting, _, date_ids = _create_meeting_with_votes(server_address, settings={"min_yes_votes": 2})
+
+    results = get_results_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id)
+    assert [d["viable"] for d in results["dates"]] == [True, False]
+    assert results["best_date_id"] == str(date_ids[0])
+
+
+def test_capacity_excludes_dates(server_address):
+    new_meeting, _, date_ids = _create_meeting_with_votes(server_address, settings={"max_capacity": 1})
+
+    results = get_results_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id)
+    assert [d["viable"] for d in results["dates"]] == [False, True]
+    assert results["best_date_id"] == str(date_ids[1])
+
+
+def test_finalizing_requires_viable_date(server_address):
+    new_meeting, _, date_ids = _create_meeting_with_votes(server_address, settings={"min_yes_votes": 2})
+    meeting_id = new_meeting.meeting_id
+
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id, user_id=new_meeting.user_id,
+                                user_token=new_meeting.user_secret_token, date_id=date_ids[1])
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id, user_id=new_meeting.user_id,
+                                user_token=new_meeting.user_secret_token, date_id=date_ids[1], force=True)
+    assert response.status_code == 200, f"{response.status_code=}"
+    response_data = response.json()
+    assert response_data["final_date_id"] == str(date_ids[1])
+    assert len(response_data["warnings"]) == 1
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id == date_ids[1]
+    assert meeting_info.voting_closed_at is not None
+
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id, user_id=new_meeting.user_id,
+                                user_token=new_meeting.user_secret_token, date_id=date_ids[0])
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+def test_finalizing_viable_date(server_address):
+    new_meeting, participants, date_ids = _create_meeting_with_votes(server_address)
+    meeting_id = new_meeting.meeting_id
+
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id, user_id=participants[0].id,
+                                user_token=participants[0].secret_token, date_id=date_ids[0])
+    assert response.status_code == 403, f"{response.status_code=}"
+
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id, user_id=new_meeting.user_id,
+                                user_token=new_meeting.user_secret_token, date_id=date_ids[0])
+    assert response.status_code == 200, f"{response.status_code=}"
+    assert response.json() == {"final_date_id": str(date_ids[0]), "warnings": []}
+
+    data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                        date_id=date_ids[0], vote=Vote.NO)
+    response = cast_vote(server_address=server_address, meeting_id=meeting_id, data=data)
+    assert response.status_code == 409, f"{response.status_code=}"
@@ -0,0 +1,123 @@
+from datetime import date
+
+from tests.utils.actions import add_proposed_date_and_validate, cast_vote_and_validate, \
+    create_meeting_and_validate, get_meeting_info_and_validate, get_results, get_results_and_validate, \
+    join_meeting_and_validate, set_participant_required
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, Vote
+
+
+def _create_meeting_with_votes(server_address, settings=None):
+    """Creates meeting with two dates. Creator votes `yes` on both, `user2`
+    votes `no` on the first one and `user3` votes `yes` on the first one."""
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     settings=settings)
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+    meeting_id = new_meeting.meeting_id
+
+    date_ids = []
+    for day in [25, 26]:
+        data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                   date=date(2023, 7, day))
+        date_ids.append(add_proposed_date_and_validate(
+            server_address=server_address, meeting_id=meeting_id, data=data).id)
+
+    participants = [join_meeting_and_validate(server_address=server_address, meeting_id=meeting_id, name=name)
+                    for name in ["user2", "user3"]]
+    for user_id, user_token, date_id, vote in [
+        (new_meeting.user_id, new_meeting.user_secret_token, date_ids[0], Vote.YES),
+        (new_meeting.user_id, new_meeting.user_secret_token, date_ids[1], Vote.YES),
+        (participants[0].id, participants[0].secret_token, date_ids[0], Vote.NO),
+        (participants[1].id, participants[1].secret_token, date_ids[0], Vote.YES),
+    ]:
+        data = CastVoteData(user_id=user_id, user_token=user_token, date_id=date_id, vote=vote)
+        cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    return new_meeting, participants, date_ids
+
+
+def test_results(server_address):
+    new_meeting, _, date_ids = _create_meeting_with_votes(server_address)
+
+    results = get_results_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id)
+    assert results == {
+        "dates": [
+            {"date_id": str(date_ids[0]), "yes": 2, "maybe": 0, "no": 1, "score": 4, "blocked": False,
+             "blocked_by": [], "viable": True},
+            {"date_id": str(date_ids[1]), "yes": 1, "maybe": 0, "no": 0, "score": 2, "blocked": False,
+             "blocked_by": [], "viable": True},
+        ],
+        "best_date_id": str(date_ids[0]),
+    }
+
+
+def test_required_participant_blocks_date(server_address):
+    new_meeting, participants, date_ids = _create_meeting_with_votes(server_address)
+    meeting_id = new_meeting.meeting_id
+
+    response = set_participant_required(server_address=server_address, meeting_id=meeting_id,
+                                        participant_id=participants[0].id, user_id=new_meeting.user_id,
+                                        user_token=new_meeting.user_secret_token, required=True)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert {p.id for p in meeting_info.participants if p.required} == {participants[0].id}
+
+    results = get_results_and_validate(
+        server_address=server_address, meeting_id=meeting_id)
+    assert results["dates"][0]["blocked"]
+    assert results["dates"][0]["blocked_by"] == [str(participants[0].id)]
+    assert not results["dates"][1]["blocked"]
+    assert results["best_date_id"] == str(date_ids[1])
+
+
+def test_blocking_participants_are_not_revealed_in_anonymous_meeting(server_address):
+    new_meeting, participants, date_ids = _create_meeting_with_votes(
+        server_address, {"anonymous_votes": True})
+    meeting_id = new_meeting.meeting_id
+    set_participant_required(server_address=server_address, meeting_id=meeting_id,
+                             participant_id=participants[0].id, user_id=new_meeting.user_id,
+                             user_token=new_meeting.user_secret_token, required=True)
+
+    results = get_results_and_validate(
+        server_address=server_address, meeting_id=meeting_id)
+    # Only identity of blocking participants is hidden. The date is still
+    # reported as blocked, which reveals the vote of the only required
+    # participant, since it decides whether the date is viable.
+    assert results["dates"][0]["blocked"]
+    assert not results["dates"][0]["viable"]
+    assert results["dates"][0]["blocked_by"] == []
+    assert results["best_date_id"] == str(date_ids[1])
+
+
+def test_only_creator_can_set_required_participants(server_address):
+    new_meeting, participants, _ = _create_meeting_with_votes(server_address)
+
+    response = set_participant_required(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                        participant_id=participants[0].id, user_id=participants[0].id,
+                                        user_token=participants[0].secret_token, required=True)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_set_required_unknown_participant_returns_404(server_address):
+    new_meeting, _, _ = _create_meeting_with_votes(server_address)
+    _, other_participants, _ = _create_meeting_with_votes(server_address)
+
+    response = set_participant_required(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                        participant_id=other_participants[0].id, user_id=new_meeting.user_id,
+                                        user_token=new_meeting.user_secret_token, required=True)
+    assert response.status_code == 404, f"{response.status_code=}"
+
+
+def test_hidden_results_are_forbidden(server_address):
+    new_meeting, participants, _ = _create_meeting_with_votes(
+        server_address, {"hide_results": True})
+
+    response = get_results(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                           viewer=participants[0].id, viewer_token=participants[0].secret_token)
+    assert response.status_code == 403, f"{response.status_code=}"
+
+    get_results_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                             viewer=new_meeting.user_id, viewer_token=new_meeting.user_secret_token)
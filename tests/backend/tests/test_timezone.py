@@ -0,0 +1,87 @@
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+from tests.utils.actions import add_proposed_date_and_validate, create_meeting, \
+    create_meeting_and_validate, get_meeting_info_and_validate, join_meeting, \
+    join_meeting_and_validate
+from tests.utils.models import AddProposedDateData, CreateMeetingData, LocalTimeSlot
+
+
+def test_time_slots_are_displayed_in_viewer_timezone(server_address):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     meeting_timezone="Europe/Warsaw")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+    meeting_id = new_meeting.meeting_id
+    user2 = join_meeting_and_validate(server_address=server_address, meeting_id=meeting_id, name="user2",
+                                      timezone="America/New_York")
+
+    starts_at = datetime(2023, 7, 25, 12, tzinfo=timezone.utc)
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               starts_at=starts_at, duration_minutes=90)
+    add_proposed_date_and_validate(
+        server_address=server_address, meeting_id=meeting_id, data=data)
+
+    # Creator did not set timezone, so meeting's timezone is used
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id, viewer=new_meeting.user_id)
+    assert meeting_info.timezone == "Europe/Warsaw"
+    proposed_date, = meeting_info.proposed_dates
+    assert proposed_date.starts_at == starts_at
+    assert proposed_date.starts_at.utcoffset() == timedelta(0)
+    assert proposed_date.local == LocalTimeSlot(timezone="Europe/Warsaw", starts_at=starts_at,
+                                                ends_at=starts_at + timedelta(minutes=90))
+    assert proposed_date.local.starts_at.utcoffset() == timedelta(hours=2)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id, viewer=user2.id)
+    proposed_date, = meeting_info.proposed_dates
+    assert proposed_date.local.timezone == "America/New_York"
+    assert proposed_date.local.starts_at == starts_at
+    assert proposed_date.local.starts_at.utcoffset() == timedelta(hours=-4)
+
+    participants = {p.id: p.timezone for p in meeting_info.participants}
+    assert participants == {new_meeting.user_id: None,
+                            user2.id: "America/New_York"}
+
+
+def test_timezone_aliases_are_stored_as_canonical_names(server_address):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     meeting_timezone="UTC", user_timezone="Pacific Standard Time")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+
+    assert meeting_info.timezone == "Etc/UTC"
+    creator, = meeting_info.participants
+    assert creator.timezone == "America/Los_Angeles"
+
+
+@pytest.mark.parametrize("meeting_timezone, user_timezone", [
+    ("Mars/Olympus_Mons", None),
+    ("Europe/Warsaw", "Mars/Olympus_Mons"),
+    ("", None),
+])
+def test_create_meeting_with_unknown_timezone_returns_400_bad_request(server_address, meeting_timezone,
+                                                                      user_timezone):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     meeting_timezone=meeting_timezone, user_timezone=user_timezone)
+
+    response = create_meeting(server_address=server_address, data=meeting_data)
+
+    assert response.status_code == 400, f"{response.status_code=}"
+
+
+def test_join_meeting_with_unknown_timezone_returns_400_bad_request(server_address):
+    meeting_data = CreateMeetingData(
+        meeting_name="test name", meeting_description=None, user_name="user1")
+    new_meeting = create_meeting_and_validate(
+        server_address=server_address, data=meeting_data)
+
+    response = join_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2",
+                            timezone="Mars/Olympus_Mons")
+
+    assert response.status_code == 400, f"{response.status_code=}"
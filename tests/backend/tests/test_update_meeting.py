@@ -0,0 +1,101 @@
+from tests.utils.actions import create_meeting_and_validate, get_meeting_history_and_validate, \
+    get_meeting_info_and_validate, join_meeting_and_validate, update_meeting
+from tests.utils.models import CreateMeetingData
+
+
+def _create_meeting(server_address):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description="test description",
+                                     user_name="user1")
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def _update(server_address, new_meeting, changes, version=None):
+    return update_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                          user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token, changes=changes,
+                          version=version)
+
+
+def test_update_name(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    response = _update(server_address, new_meeting, {"name": "new name"})
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.name == "new name"
+    assert meeting_info.description == "test description"
+    assert meeting_info.version == 2
+
+
+def test_update_and_clear_description(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    response = _update(server_address, new_meeting, {"description": "new description"})
+    assert response.status_code == 204, f"{response.status_code=}"
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.name == "test name"
+    assert meeting_info.description == "new description"
+
+    response = _update(server_address, new_meeting, {"description": None})
+    assert response.status_code == 204, f"{response.status_code=}"
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.description is None
+
+
+def test_invalid_updates_are_rejected(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    for changes in [{}, {"name": None}]:
+        response = _update(server_address, new_meeting, changes)
+        assert response.status_code == 400, f"{changes=} {response.status_code=}"
+    for changes in [{"name": ""}, {"name": "x" * 101}, {"description": " "}]:
+        response = _update(server_address, new_meeting, changes)
+        assert response.status_code == 422, f"{changes=} {response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.name == "test name"
+    assert meeting_info.description == "test description"
+    assert meeting_info.version == 1
+
+
+def test_only_creator_can_update(server_address):
+    new_meeting = _create_meeting(server_address)
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=new_meeting.meeting_id, name="user2")
+
+    response = update_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                              user_id=participant.id, user_token=participant.secret_token,
+                              changes={"name": "new name"})
+    assert response.status_code == 403, f"{response.status_code=}"
+
+
+def test_stale_update_is_rejected(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    response = _update(server_address, new_meeting, {"name": "first"}, version=1)
+    assert response.status_code == 204, f"{response.status_code=}"
+    response = _update(server_address, new_meeting, {"name": "second"}, version=1)
+    assert response.status_code == 412, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.name == "first"
+
+
+def test_update_is_recorded_in_history(server_address):
+    new_meeting = _create_meeting(server_address)
+
+    response = _update(server_address, new_meeting, {"description": None})
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    history = get_meeting_history_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id,
+                                               user_id=new_meeting.user_id,
+                                               user_token=new_meeting.user_secret_token)
+    assert len(history) == 1
+    assert history[0]["action"] == "details_updated"
+    assert history[0]["old_value"] == {"name": "test name", "description": "test description"}
+    assert history[0]["new_value"] == {"name": "test name", "description": None}
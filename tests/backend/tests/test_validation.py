@@ -0,0 +1,75 @@
+from datetime import date
+
+from tests.utils.actions import add_proposed_date_and_validate, cast_vote, create_meeting, \
+    create_meeting_and_validate, get_meeting_info_and_validate, join_meeting, join_meeting_and_validate, \
+    post_comment
+from tests.utils.models import AddProposedDateData, CastVoteData, CreateMeetingData, PostCommentData, Vote
+
+
+def _invalid_fields(response) -> list[str]:
+    assert response.status_code == 422, f"{response.status_code=}"
+    errors = response.json()["errors"]
+    assert all(error.keys() == {"field", "message"} for error in errors), f"{errors=}"
+    return [error["field"] for error in errors]
+
+
+def test_create_meeting_reports_every_invalid_field(server_address):
+    data = CreateMeetingData(meeting_name=" ", meeting_description="x" * 1001, user_name="a\x00b")
+    response = create_meeting(server_address=server_address, data=data)
+    assert _invalid_fields(response) == ["meeting_name", "meeting_description", "user_name"]
+
+
+def test_lengths_are_counted_in_characters(server_address):
+    data = CreateMeetingData(meeting_name="ż" * 100, meeting_description="ż" * 1000, user_name="ż" * 100)
+    new_meeting = create_meeting_and_validate(server_address=server_address, data=data)
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.name == "ż" * 100
+    assert meeting_info.participants[0].name == "ż" * 100
+
+    response = join_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id, name="ż" * 101)
+    assert _invalid_fields(response) == ["name"]
+
+
+def test_text_is_trimmed_and_line_breaks_are_normalized(server_address):
+    data = CreateMeetingData(meeting_name="  test name\t", meeting_description=" first\r\nsecond\n",
+                             user_name=" user1 ")
+    new_meeting = create_meeting_and_validate(server_address=server_address, data=data)
+    join_meeting_and_validate(server_address=server_address, meeting_id=new_meeting.meeting_id, name="\tuser2 ")
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.name == "test name"
+    assert meeting_info.description == "first\nsecond"
+    assert sorted(participant.name for participant in meeting_info.participants) == ["user1", "user2"]
+
+
+def test_control_characters_are_rejected(server_address):
+    data = CreateMeetingData(meeting_name="test name", meeting_description="line\tone\nline two",
+                             user_name="user1")
+    new_meeting = create_meeting_and_validate(server_address=server_address, data=data)
+
+    for name in ["user\x002", "user\x1b2", "user\x7f2"]:
+        response = join_meeting(server_address=server_address, meeting_id=new_meeting.meeting_id, name=name)
+        assert _invalid_fields(response) == ["name"], f"{name=}"
+
+
+def test_comments_are_validated(server_address):
+    data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1")
+    new_meeting = create_meeting_and_validate(server_address=server_address, data=data)
+    meeting_id = new_meeting.meeting_id
+    user_id, user_token = new_meeting.user_id, new_meeting.user_secret_token
+
+    for message in ["", "x" * 1001, "bell\x07"]:
+        comment = PostCommentData(user_id=user_id, user_token=user_token, message=message)
+        response = post_comment(server_address=server_address, meeting_id=meeting_id, data=comment)
+        assert _invalid_fields(response) == ["message"], f"{message=}"
+
+    date_data = AddProposedDateData(user_id=user_id, user_token=user_token, date=date(2023, 7, 25))
+    date_id = add_proposed_date_and_validate(server_address=server_address, meeting_id=meeting_id, data=date_data).id
+    vote = CastVoteData(user_id=user_id, user_token=user_token, date_id=date_id, vote=Vote.YES, comment="x" * 201)
+    response = cast_vote(server_address=server_address, meeting_id=meeting_id, data=vote)
+    assert _invalid_fields(response) == ["comment"]
+
+    meeting_info = get_meeting_info_and_validate(server_address=server_address, id=meeting_id)
+    assert meeting_info.comments == []
+    assert meeting_info.votes == []
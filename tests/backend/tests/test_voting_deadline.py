@@ -0,0 +1,164 @@
+import time
+from datetime import date, datetime, time as time_of_day, timedelta, timezone
+
+import pytest
+
+from tests.utils.actions import add_proposed_date, add_proposed_date_and_validate, cast_vote, \
+    cast_vote_and_validate, close_voting, create_meeting, create_meeting_and_validate, finalize_meeting, \
+    get_availability_and_validate, get_meeting_info_and_validate, join_meeting_and_validate, set_availability, \
+    set_availability_and_validate
+from tests.utils.models import AddProposedDateData, AvailabilityGrid, CastVoteData, CreateMeetingData, \
+    SetAvailabilityData, Vote
+
+
+def _create_meeting(server_address, voting_closes_at=None, settings=None):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     voting_closes_at=voting_closes_at, settings=settings)
+    return create_meeting_and_validate(server_address=server_address, data=meeting_data)
+
+
+def _add_date(server_address, meeting, day):
+    data = AddProposedDateData(user_id=meeting.user_id, user_token=meeting.user_secret_token,
+                               date=date(2023, 7, day))
+    return add_proposed_date_and_validate(server_address=server_address, meeting_id=meeting.meeting_id, data=data).id
+
+
+@pytest.mark.parametrize("voting_closes_at", [
+    datetime.now(timezone.utc) - timedelta(days=1),
+    datetime.now(timezone.utc) + timedelta(days=15),
+])
+def test_create_meeting_with_invalid_deadline_returns_400(server_address, voting_closes_at):
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     voting_closes_at=voting_closes_at)
+    response = create_meeting(server_address=server_address, data=meeting_data)
+    assert response.status_code == 400, f"{response.status_code=}"
+
+
+def test_deadline_is_shown(server_address):
+    voting_closes_at = datetime.now(timezone.utc).replace(microsecond=0) + timedelta(days=7)
+    new_meeting = _create_meeting(server_address, voting_closes_at)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=new_meeting.meeting_id)
+    assert meeting_info.voting_closes_at == voting_closes_at
+    assert meeting_info.voting_closed_at is None
+    assert meeting_info.final_date_id is None
+
+
+def test_votes_and_dates_are_rejected_after_deadline(server_address):
+    new_meeting = _create_meeting(
+        server_address, datetime.now(timezone.utc) + timedelta(seconds=1))
+    meeting_id = new_meeting.meeting_id
+    date_id = _add_date(server_address, new_meeting, 25)
+    time.sleep(1.5)
+
+    vote = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                        date_id=date_id, vote=Vote.YES)
+    response = cast_vote(server_address=server_address,
+                         meeting_id=meeting_id, data=vote)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    data = AddProposedDateData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               date=date(2023, 7, 26))
+    response = add_proposed_date(
+        server_address=server_address, meeting_id=meeting_id, data=data)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    response = close_voting(server_address=server_address, meeting_id=meeting_id,
+                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+
+def test_availability_is_rejected_after_deadline(server_address):
+    grid = AvailabilityGrid(first_day=date(2023, 7, 24), last_day=date(2023, 7, 24),
+                            day_starts_at=time_of_day(9), day_ends_at=time_of_day(11), slot_minutes=30)
+    meeting_data = CreateMeetingData(meeting_name="test name", meeting_description=None, user_name="user1",
+                                     voting_closes_at=datetime.now(timezone.utc) + timedelta(seconds=1),
+                                     availability_grid=grid)
+    new_meeting = create_meeting_and_validate(server_address=server_address, data=meeting_data)
+    meeting_id = new_meeting.meeting_id
+    data = SetAvailabilityData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               slots="1100")
+    set_availability_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+    time.sleep(1.5)
+
+    data = SetAvailabilityData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                               slots="0011")
+    response = set_availability(server_address=server_address, meeting_id=meeting_id, data=data)
+    assert response.status_code == 409, f"{response.status_code=}"
+
+    availability = get_availability_and_validate(server_address=server_address, meeting_id=meeting_id)
+    participant, = availability["participants"]
+    assert participant["slots"] == "1100"
+
+
+def test_meeting_is_finalized_after_deadline(server_address):
+    new_meeting = _create_meeting(server_address, datetime.now(timezone.utc) + timedelta(seconds=2),
+                                  {"auto_finalize": True})
+    meeting_id = new_meeting.meeting_id
+    date_ids = [_add_date(server_address, new_meeting, day) for day in [25, 26]]
+    participant = join_meeting_and_validate(
+        server_address=server_address, meeting_id=meeting_id, name="user2")
+    for user_id, user_token, votes in [
+        (new_meeting.user_id, new_meeting.user_secret_token, [Vote.MAYBE, Vote.YES]),
+        (participant.id, participant.secret_token, [Vote.YES, Vote.YES]),
+    ]:
+        for date_id, vote in zip(date_ids, votes):
+            data = CastVoteData(user_id=user_id, user_token=user_token, date_id=date_id, vote=vote)
+            cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id is None
+
+    time.sleep(2.5)
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id == date_ids[1]
+    version = meeting_info.version
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id == date_ids[1]
+    assert meeting_info.version == version
+
+    response = finalize_meeting(server_address=server_address, meeting_id=meeting_id,
+                                user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                                date_id=date_ids[0], version=version)
+    assert response.status_code == 409, f"{response.status_code=}"
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id == date_ids[1]
+
+
+def test_meeting_is_finalized_when_voting_is_closed(server_address):
+    new_meeting = _create_meeting(server_address, settings={"auto_finalize": True})
+    meeting_id = new_meeting.meeting_id
+    date_id = _add_date(server_address, new_meeting, 25)
+    data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                        date_id=date_id, vote=Vote.MAYBE)
+    cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    response = close_voting(server_address=server_address, meeting_id=meeting_id,
+                            user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id == date_id
+
+
+def test_meeting_is_not_finalized_by_default(server_address):
+    new_meeting = _create_meeting(server_address)
+    meeting_id = new_meeting.meeting_id
+    date_id = _add_date(server_address, new_meeting, 25)
+    data = CastVoteData(user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token,
+                        date_id=date_id, vote=Vote.YES)
+    cast_vote_and_validate(server_address=server_address, meeting_id=meeting_id, data=data)
+
+    close_voting(server_address=server_address, meeting_id=meeting_id,
+                 user_id=new_meeting.user_id, user_token=new_meeting.user_secret_token)
+
+    meeting_info = get_meeting_info_and_validate(
+        server_address=server_address, id=meeting_id)
+    assert meeting_info.final_date_id is None
@@ -3,7 +3,19 @@ from uuid import UUID
 import requests
 
 from tests.utils.models import CreateMeetingData, CreateMeetingResponse, Meeting, \
-    JoinMeetingResponse, JoinMeetingData, PostCommentData
+    JoinMeetingResponse, JoinMeetingData, PostCommentData, AddProposedDateData, AddProposedDateResponse, \
+    SetAvailabilityData, MeetingProposedDate, CastVoteData
+
+
+def _if_match(server_address: str, meeting_id: UUID, version: int | None) -> dict:
+    """Returns `If-Match` header with given version of meeting, or with its current version if not given"""
+
+    if version is None:
+        response = requests.get(url=f"http://{server_address}/meeting/{meeting_id}")
+        if response.status_code != 200:
+            return {}
+        version = response.json()["version"]
+    return {"If-Match": f'"{version}"'}
 
 
 def create_meeting(server_address: str, data: CreateMeetingData) -> requests.Response:
@@ -23,36 +35,54 @@ def create_meeting_and_validate(server_address: str, data: CreateMeetingData) ->
     return CreateMeetingResponse.from_json_dict(response_data)
 
 
-def get_meeting_info(server_address: str, id: UUID) -> requests.Response:
-    """Gets meeting info"""
+def get_meeting_info(server_address: str, id: UUID, viewer: UUID | None = None,
+                     viewer_token: UUID | None = None) -> requests.Response:
+    """Gets meeting info. Time slots are displayed in timezone of `viewer`"""
 
     url = f"http://{server_address}/meeting/{id}"
-    return requests.get(url=url)
+    params = {"user_id": str(viewer)} if viewer is not None else {}
+    if viewer_token is not None:
+        params["user_token"] = str(viewer_token)
+    return requests.get(url=url, params=params)
 
 
-def get_meeting_info_and_validate(server_address: str, id: UUID):
+def get_meeting_info_and_validate(server_address: str, id: UUID, viewer: UUID | None = None,
+                                  viewer_token: UUID | None = None):
     """Gets meeting info and validates response"""
 
-    response = get_meeting_info(server_address=server_address, id=id)
+    response = get_meeting_info(
+        server_address=server_address, id=id, viewer=viewer, viewer_token=viewer_token)
     assert response.status_code == 200, f"{response.status_code=}"
 
     response_data = response.json()
     return Meeting.from_json_dict(response_data)
 
 
-def join_meeting(server_address: str, meeting_id: UUID, name: str) -> requests.Response:
+def update_meeting(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID, changes: dict,
+                   version: int | None = None) -> requests.Response:
+    """Changes name or description of meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}"
+    data = {"user_id": str(user_id), "user_token": str(user_token), **changes}
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.patch(url=url, json=data, headers=headers)
+
+
+def join_meeting(server_address: str, meeting_id: UUID, name: str, timezone: str | None = None,
+                 account: dict | None = None) -> requests.Response:
     """Adds new participant to meeting with `meeting_id` with given `name`"""
 
     url = f"http://{server_address}/meeting/{meeting_id}/join"
-    data = JoinMeetingData(name=name)
+    data = JoinMeetingData(name=name, timezone=timezone, account=account)
     return requests.post(url=url, json=data.to_json_dict())
 
 
-def join_meeting_and_validate(server_address: str, meeting_id: UUID, name: str) -> JoinMeetingResponse:
+def join_meeting_and_validate(server_address: str, meeting_id: UUID, name: str,
+                              timezone: str | None = None, account: dict | None = None) -> JoinMeetingResponse:
     """Adds new participant to meeting with `meeting_id` with given `name` and validates response"""
 
     response = join_meeting(server_address=server_address,
-                            meeting_id=meeting_id, name=name)
+                            meeting_id=meeting_id, name=name, timezone=timezone, account=account)
     assert response.status_code == 201, f"{response.status_code=}"
 
     response_data = response.json()
@@ -73,3 +103,259 @@ def post_comment_and_validate(server_address: str, meeting_id: UUID, data: PostC
                             meeting_id=meeting_id, data=data)
     assert response.status_code == 201, f"{response.status_code=}"
     assert len(response.content) == 0, f"{response.content=}"
+
+
+def export_meeting(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID) -> requests.Response:
+    """Exports meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/export"
+    params = {"user_id": str(user_id), "user_token": str(user_token)}
+    return requests.get(url=url, params=params)
+
+
+def export_meeting_and_validate(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID) -> dict:
+    """Exports meeting as given user and validates response"""
+
+    response = export_meeting(server_address=server_address,
+                              meeting_id=meeting_id, user_id=user_id, user_token=user_token)
+    assert response.status_code == 200, f"{response.status_code=}"
+
+    return response.json()
+
+
+def import_meeting(server_address: str, admin_token: str, export: dict) -> requests.Response:
+    """Imports previously exported meeting"""
+
+    url = f"http://{server_address}/admin/import"
+    data = {"admin_token": admin_token, "meeting": export}
+    return requests.post(url=url, json=data)
+
+
+def add_proposed_date(server_address: str, meeting_id: UUID, data: AddProposedDateData,
+                      version: int | None = None) -> requests.Response:
+    """Proposes new date in meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/date"
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.post(url=url, json=data.to_json_dict(), headers=headers)
+
+
+def add_proposed_date_and_validate(server_address: str, meeting_id: UUID, data: AddProposedDateData,
+                                   version: int | None = None) -> AddProposedDateResponse:
+    """Proposes new date in meeting as given user and validates response"""
+
+    response = add_proposed_date(server_address=server_address,
+                                 meeting_id=meeting_id, data=data, version=version)
+    assert response.status_code == 201, f"{response.status_code=}"
+
+    return AddProposedDateResponse.from_json_dict(response.json())
+
+
+def set_availability(server_address: str, meeting_id: UUID, data: SetAvailabilityData) -> requests.Response:
+    """Sets availability of given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/availability"
+    return requests.put(url=url, json=data.to_json_dict())
+
+
+def set_availability_and_validate(server_address: str, meeting_id: UUID, data: SetAvailabilityData):
+    """Sets availability of given user and validates response"""
+
+    response = set_availability(server_address=server_address,
+                                meeting_id=meeting_id, data=data)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+
+def get_availability(server_address: str, meeting_id: UUID) -> requests.Response:
+    """Gets availability grid of meeting with heatmap and common free windows"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/availability"
+    return requests.get(url=url)
+
+
+def get_availability_and_validate(server_address: str, meeting_id: UUID) -> dict:
+    """Gets availability of meeting and validates response"""
+
+    response = get_availability(
+        server_address=server_address, meeting_id=meeting_id)
+    assert response.status_code == 200, f"{response.status_code=}"
+
+    return response.json()
+
+
+def generate_proposed_dates(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID,
+                            rule: dict, version: int | None = None) -> requests.Response:
+    """Proposes every date matching `rule` in meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/date/generate"
+    data = {"user_id": str(user_id), "user_token": str(user_token), **rule}
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.post(url=url, json=data, headers=headers)
+
+
+def generate_proposed_dates_and_validate(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID,
+                                         rule: dict) -> list[MeetingProposedDate]:
+    """Proposes every date matching `rule` in meeting as given user and validates response"""
+
+    response = generate_proposed_dates(server_address=server_address, meeting_id=meeting_id,
+                                       user_id=user_id, user_token=user_token, rule=rule)
+    assert response.status_code == 201, f"{response.status_code=}"
+
+    response_data = response.json()
+    assert len(response_data) == 1, "excessive items in data"
+    return [MeetingProposedDate.from_json_dict(d) for d in response_data["proposed_dates"]]
+
+
+def cast_vote(server_address: str, meeting_id: UUID, data: CastVoteData) -> requests.Response:
+    """Votes on proposed date as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/vote"
+    return requests.post(url=url, json=data.to_json_dict())
+
+
+def cast_vote_and_validate(server_address: str, meeting_id: UUID, data: CastVoteData):
+    """Votes on proposed date as given user and validates response"""
+
+    response = cast_vote(server_address=server_address,
+                         meeting_id=meeting_id, data=data)
+    assert response.status_code == 204, f"{response.status_code=}"
+
+
+def review_proposed_date(server_address: str, meeting_id: UUID, date_id: UUID, user_id: UUID, user_token: UUID,
+                         approve: bool, version: int | None = None) -> requests.Response:
+    """Approves or rejects date proposed by participant"""
+
+    action = "approve" if approve else "reject"
+    url = f"http://{server_address}/meeting/{meeting_id}/date/{date_id}/{action}"
+    data = {"user_id": str(user_id), "user_token": str(user_token)}
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.post(url=url, json=data, headers=headers)
+
+
+def close_voting(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID,
+                 version: int | None = None) -> requests.Response:
+    """Closes voting in meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/close"
+    data = {"user_id": str(user_id), "user_token": str(user_token)}
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.post(url=url, json=data, headers=headers)
+
+
+def finalize_meeting(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID, date_id: UUID,
+                     force: bool = False, version: int | None = None) -> requests.Response:
+    """Sets final date of meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/finalize"
+    data = {"user_id": str(user_id), "user_token": str(user_token), "date_id": str(date_id), "force": force}
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.post(url=url, json=data, headers=headers)
+
+
+def get_results(server_address: str, meeting_id: UUID, viewer: UUID | None = None,
+                viewer_token: UUID | None = None) -> requests.Response:
+    """Gets voting results of meeting"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/results"
+    params = {}
+    if viewer is not None:
+        params["user_id"] = str(viewer)
+    if viewer_token is not None:
+        params["user_token"] = str(viewer_token)
+    return requests.get(url=url, params=params)
+
+
+def get_results_and_validate(server_address: str, meeting_id: UUID, viewer: UUID | None = None,
+                             viewer_token: UUID | None = None) -> dict:
+    """Gets voting results of meeting and validates response"""
+
+    response = get_results(server_address=server_address, meeting_id=meeting_id,
+                           viewer=viewer, viewer_token=viewer_token)
+    assert response.status_code == 200, f"{response.status_code=}"
+
+    response_data = response.json()
+    assert len(response_data) == 2, "excessive items in data"
+    return response_data
+
+
+def set_participant_required(server_address: str, meeting_id: UUID, participant_id: UUID, user_id: UUID,
+                             user_token: UUID, required: bool, version: int | None = None) -> requests.Response:
+    """Marks participant of meeting as required or optional"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/participant/{participant_id}"
+    data = {"user_id": str(user_id), "user_token": str(user_token), "required": required}
+    headers = _if_match(server_address, meeting_id, version)
+    return requests.put(url=url, json=data, headers=headers)
+
+
+def get_meeting_history(server_address: str, meeting_id: UUID, user_id: UUID, user_token: UUID) -> requests.Response:
+    """Gets history of meeting as given user"""
+
+    url = f"http://{server_address}/meeting/{meeting_id}/history"
+    params = {"user_id": str(user_id), "user_token": str(user_token)}
+    return requests.get(url=url, params=params)
+
+
+def get_meeting_history_and_validate(server_address: str, meeting_id: UUID, user_id: UUID,
+                                     user_token: UUID) -> list[dict]:
+    """Gets history of meeting and validates response"""
+
+    response = get_meeting_history(server_address=server_address, meeting_id=meeting_id, user_id=user_id,
+                                   user_token=user_token)
+    assert response.status_code == 200, f"{response.status_code=}"
+
+    response_data = response.json()
+    assert len(response_data) == 1, "excessive items in data"
+    entries = response_data["entries"]
+    for entry in entries:
+        assert len(entry) == 7, "excessive items in entry"
+    return entries
+
+
+def create_account(server_address: str, name: str) -> requests.Response:
+    """Creates persistent account"""
+
+    url = f"http://{server_address}/account"
+    return requests.post(url=url, json={"name": name})
+
+
+def create_account_and_validate(server_address: str, name: str) -> dict:
+    """Creates persistent account and returns credentials to be passed along with meeting data"""
+
+    response = create_account(server_address=server_address, name=name)
+    assert response.status_code == 201, f"{response.status_code=}"
+
+    response_data = response.json()
+    assert response_data.keys() == {"id", "secret_token"}, f"{response_data=}"
+    return {"account_id": response_data["id"], "account_token": response_data["secret_token"]}
+
+
+def get_account_meetings(server_address: str, account: dict) -> requests.Response:
+    """Lists meetings created or joined by account"""
+
+    url = f"http://{server_address}/account/{account['account_id']}/meetings"
+    return requests.get(url=url, params={"account_token": account["account_token"]})
+
+
+def get_account_meetings_and_validate(server_address: str, account: dict) -> list[dict]:
+    """Lists meetings created or joined by account and validates response"""
+
+    response = get_account_meetings(server_address=server_address, account=account)
+    assert response.status_code == 200, f"{response.status_code=}"
+
+    response_data = response.json()
+    assert len(response_data) == 1, "excessive items in data"
+    meetings = response_data["meetings"]
+    for meeting in meetings:
+        assert len(meeting) == 6, "excessive items in meeting"
+    return meetings
+
+
+def link_account(server_address: str, account: dict, meeting_id: UUID, user_id: UUID,
+                 user_token: UUID) -> requests.Response:
+    """Links existing participant of meeting to account"""
+
+    url = f"http://{server_address}/account/{account['account_id']}/meetings"
+    data = {"account_token": account["account_token"], "meeting_id": str(meeting_id), "user_id": str(user_id),
+            "user_token": str(user_token)}
+    return requests.post(url=url, json=data)
@@ -1,23 +1,57 @@
 from dataclasses import dataclass
-from datetime import datetime, date
+from datetime import datetime, date, time
 from enum import Enum
 from operator import itemgetter
 from typing import Self
 from uuid import UUID
 
 
+@dataclass
+class AvailabilityGrid:
+    first_day: date
+    last_day: date
+    day_starts_at: time
+    day_ends_at: time
+    slot_minutes: int
+
+    def to_json_dict(self) -> dict:
+        return {
+            "first_day": self.first_day.isoformat(),
+            "last_day": self.last_day.isoformat(),
+            "day_starts_at": self.day_starts_at.isoformat(),
+            "day_ends_at": self.day_ends_at.isoformat(),
+            "slot_minutes": self.slot_minutes,
+        }
+
+
 @dataclass
 class CreateMeetingData:
     meeting_name: str
     meeting_description: str | None
     user_name: str
+    meeting_timezone: str | None = None
+    user_timezone: str | None = None
+    availability_grid: AvailabilityGrid | None = None
+    settings: dict | None = None
+    voting_closes_at: datetime | None = None
+    account: dict | None = None
 
     def to_json_dict(self) -> dict:
-        return {
+        data = {
             "meeting_name": self.meeting_name,
             "meeting_description": self.meeting_description,
+            "meeting_timezone": self.meeting_timezone,
             "user_name": self.user_name,
+            "user_timezone": self.user_timezone,
+            "availability_grid": self.availability_grid.to_json_dict() if self.availability_grid else None,
         }
+        if self.settings is not None:
+            data["settings"] = self.settings
+        if self.voting_closes_at is not None:
+            data["voting_closes_at"] = self.voting_closes_at.isoformat()
+        if self.account is not None:
+            data["account"] = self.account
+        return data
 
 
 @dataclass
@@ -67,35 +101,81 @@ class MeetingComment:
 class MeetingParticipant:
     id: UUID
     name: str
+    timezone: str | None = None
+    required: bool = False
 
     @staticmethod
     def from_json_dict(data: dict) -> Self:
         try:
-            id, name = itemgetter("id", "name")(data)
-            assert len(data) == 2, "excessive items in data"
+            id, name, timezone, required = itemgetter(
+                "id", "name", "timezone", "required")(data)
+            assert len(data) == 4, "excessive items in data"
 
             id = UUID(id)
 
-            return MeetingParticipant(id=id, name=name)
+            return MeetingParticipant(id=id, name=name, timezone=timezone, required=required)
         except Exception as e:
             raise ValueError(f"failed to parse data: {data}") from e
 
 
 @dataclass
-class MeetingProposedDate:
-    id: UUID
-    date: date
+class LocalTimeSlot:
+    timezone: str
+    starts_at: datetime
+    ends_at: datetime
 
     @staticmethod
     def from_json_dict(data: dict) -> Self:
         try:
-            id, _date = itemgetter("id", "date")(data)
-            assert len(data) == 2, "excessive items in data"
+            timezone, starts_at, ends_at = itemgetter(
+                "timezone", "starts_at", "ends_at")(data)
+            assert len(data) == 3, "excessive items in data"
 
-            id = UUID(id)
-            _date = date.fromisoformat(_date)
+            starts_at = datetime.fromisoformat(starts_at)
+            ends_at = datetime.fromisoformat(ends_at)
 
-            return MeetingProposedDate(id=id, date=_date)
+            return LocalTimeSlot(timezone=timezone, starts_at=starts_at, ends_at=ends_at)
+        except Exception as e:
+            raise ValueError(f"failed to parse data: {data}") from e
+
+
+@dataclass
+class MeetingProposedDate:
+    """Proposed date is either a whole day (`date` is set) or a time slot
+    (`starts_at` and `duration_minutes` are set)"""
+    id: UUID
+    date: "date | None" = None
+    starts_at: datetime | None = None
+    duration_minutes: int | None = None
+    local: LocalTimeSlot | None = None
+    proposed_by: UUID | None = None
+    approved: bool = True
+    holiday: str | None = None
+    weekend: bool = False
+
+    @staticmethod
+    def from_json_dict(data: dict) -> Self:
+        try:
+            id, local, holiday, weekend = UUID(data["id"]), data["local"], data["holiday"], data["weekend"]
+            proposed_by, approved = UUID(data["proposed_by"]), data["approved"]
+            assert isinstance(approved, bool), "approved is not a bool"
+            if "date" in data:
+                assert len(data) == 7, "excessive items in data"
+                assert local is None, "whole day can not have local time"
+                _date = date.fromisoformat(data["date"])
+
+                return MeetingProposedDate(id=id, date=_date, proposed_by=proposed_by, approved=approved,
+                                           holiday=holiday, weekend=weekend)
+            else:
+                starts_at, duration_minutes = itemgetter(
+                    "starts_at", "duration_minutes")(data)
+                assert len(data) == 8, "excessive items in data"
+                starts_at = datetime.fromisoformat(starts_at)
+                local = LocalTimeSlot.from_json_dict(local)
+
+                return MeetingProposedDate(id=id, starts_at=starts_at, duration_minutes=duration_minutes,
+                                           local=local, proposed_by=proposed_by, approved=approved,
+                                           holiday=holiday, weekend=weekend)
         except Exception as e:
             raise ValueError(f"failed to parse data: {data}") from e
 
@@ -141,43 +221,94 @@ class MeetingVote:
             raise ValueError(f"failed to parse data: {data}") from e
 
 
+@dataclass
+class DateVoteCounts:
+    date_id: UUID
+    yes: int
+    maybe: int
+    no: int
+    comments: list[str]
+
+    @staticmethod
+    def from_json_dict(data: dict) -> Self:
+        try:
+            date_id, yes, maybe, no, comments = itemgetter(
+                "date_id", "yes", "maybe", "no", "comments")(data)
+            assert len(data) == 5, "excessive items in data"
+
+            date_id = UUID(date_id)
+
+            return DateVoteCounts(date_id=date_id, yes=yes, maybe=maybe, no=no, comments=comments)
+        except Exception as e:
+            raise ValueError(f"failed to parse data: {data}") from e
+
+
 @dataclass
 class Meeting:
     name: str
     description: str | None
     created_by: UUID
     created_at: datetime
+    timezone: str
+    mode: str
+    settings: dict
+    voting_closes_at: datetime | None
+    voting_closed_at: datetime | None
+    final_date_id: UUID | None
+    version: int
     comments: list[MeetingComment]
     participants: list[MeetingParticipant]
     proposed_dates: list[MeetingProposedDate]
     votes: list[MeetingVote]
+    vote_counts: list[DateVoteCounts]
+    results_hidden: bool
 
     @staticmethod
     def from_json_dict(data: dict) -> Self:
         try:
-            name, description, created_by, created_at, comments, participants, proposed_dates, votes = \
-                itemgetter("name", "description", "created_by", "created_at",
-                           "comments", "participants", "proposed_dates", "votes")(data)
-            assert len(data) == 8, "excessive items in data"
+            name, description, created_by, created_at, timezone, mode, settings, voting_closes_at, \
+                voting_closed_at, final_date_id, version, comments, participants, proposed_dates, votes, \
+                vote_counts, results_hidden = itemgetter(
+                    "name", "description", "created_by", "created_at", "timezone", "mode", "settings",
+                    "voting_closes_at", "voting_closed_at", "final_date_id", "version", "comments",
+                    "participants", "proposed_dates", "votes", "vote_counts", "results_hidden")(data)
+            assert len(data) == 17, "excessive items in data"
+            assert mode in ("poll", "availability"), f"invalid mode: {mode}"
 
             created_by = UUID(created_by)
             created_at = datetime.fromisoformat(created_at)
+            if voting_closes_at is not None:
+                voting_closes_at = datetime.fromisoformat(voting_closes_at)
+            if voting_closed_at is not None:
+                voting_closed_at = datetime.fromisoformat(voting_closed_at)
+            if final_date_id is not None:
+                final_date_id = UUID(final_date_id)
             comments = [MeetingComment.from_json_dict(c) for c in comments]
             participants = [MeetingParticipant.from_json_dict(
                 p) for p in participants]
             proposed_dates = [MeetingProposedDate.from_json_dict(
                 d) for d in proposed_dates]
             votes = [MeetingVote.from_json_dict(v) for v in votes]
+            vote_counts = [DateVoteCounts.from_json_dict(c) for c in vote_counts]
 
             return Meeting(
                 name=name,
                 description=description,
                 created_by=created_by,
                 created_at=created_at,
+                timezone=timezone,
+                mode=mode,
+                settings=settings,
+                voting_closes_at=voting_closes_at,
+                voting_closed_at=voting_closed_at,
+                final_date_id=final_date_id,
+                version=version,
                 comments=comments,
                 participants=participants,
                 proposed_dates=proposed_dates,
                 votes=votes,
+                vote_counts=vote_counts,
+                results_hidden=results_hidden,
             )
         except Exception as e:
             raise ValueError(f"failed to parse data: {data}") from e
@@ -186,11 +317,17 @@ class Meeting:
 @dataclass
 class JoinMeetingData:
     name: str
+    timezone: str | None = None
+    account: dict | None = None
 
     def to_json_dict(self) -> dict:
-        return {
+        data = {
             "name": self.name,
+            "timezone": self.timezone,
         }
+        if self.account is not None:
+            data["account"] = self.account
+        return data
 
 
 @dataclass
@@ -224,3 +361,73 @@ class PostCommentData:
             "user_token": str(self.user_token),
             "message": self.message,
         }
+
+
+@dataclass
+class AddProposedDateData:
+    """Either `date` or both `starts_at` and `duration_minutes` should be set"""
+    user_id: UUID
+    user_token: UUID
+    date: "date | None" = None
+    starts_at: datetime | None = None
+    duration_minutes: int | None = None
+
+    def to_json_dict(self) -> dict:
+        data = {
+            "user_id": str(self.user_id),
+            "user_token": str(self.user_token),
+        }
+        if self.date is not None:
+            data["date"] = self.date.isoformat()
+        if self.starts_at is not None:
+            data["starts_at"] = self.starts_at.isoformat()
+        if self.duration_minutes is not None:
+            data["duration_minutes"] = self.duration_minutes
+        return data
+
+
+@dataclass
+class AddProposedDateResponse:
+    id: UUID
+
+    @staticmethod
+    def from_json_dict(data: dict) -> Self:
+        try:
+            id = UUID(data["id"])
+            assert len(data) == 1, "excessive items in data"
+
+            return AddProposedDateResponse(id=id)
+        except Exception as e:
+            raise ValueError(f"failed to parse data: {data}") from e
+
+
+@dataclass
+class SetAvailabilityData:
+    user_id: UUID
+    user_token: UUID
+    slots: str
+
+    def to_json_dict(self) -> dict:
+        return {
+            "user_id": str(self.user_id),
+            "user_token": str(self.user_token),
+            "slots": self.slots,
+        }
+
+
+@dataclass
+class CastVoteData:
+    user_id: UUID
+    user_token: UUID
+    date_id: UUID
+    vote: Vote
+    comment: str | None = None
+
+    def to_json_dict(self) -> dict:
+        return {
+            "user_id": str(self.user_id),
+            "user_token": str(self.user_token),
+            "date_id": str(self.date_id),
+            "vote": self.vote.value,
+            "comment": self.comment,
+        }